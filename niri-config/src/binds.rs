@@ -12,6 +12,7 @@ use smithay::input::keyboard::keysyms::KEY_NoSymbol;
 use smithay::input::keyboard::xkb::{keysym_from_name, KEYSYM_CASE_INSENSITIVE, KEYSYM_NO_FLAGS};
 use smithay::input::keyboard::Keysym;
 
+use crate::gestures::SimulatedGesture;
 use crate::recent_windows::{MruDirection, MruFilter, MruScope};
 use crate::utils::{expect_only_children, MergeWith};
 
@@ -24,11 +25,48 @@ pub struct Bind {
     pub action: Action,
     pub repeat: bool,
     pub cooldown: Option<Duration>,
+    /// Trigger on the second press within this time, rather than on every press.
+    pub double_press: Option<Duration>,
     pub allow_when_locked: bool,
     pub allow_inhibiting: bool,
     pub hotkey_overlay_title: Option<Option<String>>,
 }
 
+impl Bind {
+    /// Returns whether both binds trigger on the same input, so one replaces the other.
+    pub fn same_trigger(&self, other: &Bind) -> bool {
+        self.key == other.key && self.double_press.is_some() == other.double_press.is_some()
+    }
+}
+
+/// Named modes with their own binds, like a resize mode.
+#[derive(Debug, Default, PartialEq)]
+pub struct BindModes(pub Vec<BindMode>);
+
+/// Binds that replace the regular binds while the mode is active.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindMode {
+    pub name: String,
+    pub binds: Vec<Bind>,
+}
+
+/// Wheel scroll binds that apply only with the pointer over a part of the screen.
+///
+/// They take precedence over the regular binds there.
+#[derive(Debug, Default, PartialEq)]
+pub struct RegionBinds {
+    /// Binds over the empty workspace background.
+    pub background: Vec<Bind>,
+    /// Binds over the window borders and tab indicators.
+    pub border: Vec<Bind>,
+}
+
+impl BindModes {
+    pub fn find(&self, name: &str) -> Option<&BindMode> {
+        self.0.iter().find(|mode| mode.name == name)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Key {
     pub trigger: Trigger,
@@ -51,6 +89,8 @@ pub enum Trigger {
     TouchpadScrollUp,
     TouchpadScrollLeft,
     TouchpadScrollRight,
+    /// Tapping the bind modifiers on their own, with no other key in between.
+    ModifierTap,
 }
 
 bitflags! {
@@ -108,8 +148,24 @@ pub enum Action {
     ToggleDebugTint,
     DebugToggleOpaqueRegions,
     DebugToggleDamage,
+    DebugToggleGestureOverlay,
+    ResetMagnifier,
+    #[knuffel(skip)]
+    SimulateGesture(SimulatedGesture),
     Spawn(#[knuffel(arguments)] Vec<String>),
     SpawnSh(#[knuffel(argument)] String),
+    SendKeys(#[knuffel(arguments)] Vec<Key>),
+    #[knuffel(skip)]
+    SendKeysById {
+        id: u64,
+        keys: Vec<Key>,
+    },
+    SwitchBindMode(#[knuffel(argument)] String),
+    EnterResizeMode,
+    #[knuffel(skip)]
+    ConfirmResizeMode,
+    #[knuffel(skip)]
+    CancelResizeMode,
     DoScreenTransition(#[knuffel(property(name = "delay-ms"))] Option<u16>),
     #[knuffel(skip)]
     ConfirmScreenshot {
@@ -130,6 +186,20 @@ pub enum Action {
         // Path; not settable from knuffel
         Option<String>,
     ),
+    #[knuffel(skip)]
+    ScreenshotOutput {
+        output: String,
+        write_to_disk: bool,
+        show_pointer: bool,
+        path: Option<String>,
+    },
+    #[knuffel(skip)]
+    ScreenshotRegion {
+        region: niri_ipc::Region,
+        write_to_disk: bool,
+        show_pointer: bool,
+        path: Option<String>,
+    },
     ScreenshotWindow(
         #[knuffel(property(name = "write-to-disk"), default = true)] bool,
         #[knuffel(property(name = "show-pointer"), default = false)] bool,
@@ -143,10 +213,20 @@ pub enum Action {
         show_pointer: bool,
         path: Option<String>,
     },
+    PickColor,
     ToggleKeyboardShortcutsInhibit,
     CloseWindow,
     #[knuffel(skip)]
     CloseWindowById(u64),
+    ForceCloseWindow(#[knuffel(property(name = "under-cursor"), default)] bool),
+    #[knuffel(skip)]
+    ForceCloseWindowById(u64),
+    MinimizeWindow,
+    #[knuffel(skip)]
+    MinimizeWindowById(u64),
+    UnminimizeWindow,
+    #[knuffel(skip)]
+    UnminimizeWindowById(u64),
     FullscreenWindow,
     #[knuffel(skip)]
     FullscreenWindowById(u64),
@@ -206,6 +286,7 @@ pub enum Action {
     SwapWindowLeft,
     SwapWindowRight,
     ToggleColumnTabbedDisplay,
+    ToggleColumnAccordionDisplay,
     SetColumnDisplay(#[knuffel(argument, str)] ColumnDisplay),
     CenterColumn,
     CenterWindow,
@@ -322,6 +403,9 @@ pub enum Action {
     MaximizeWindowToEdgesById(u64),
     SetColumnWidth(#[knuffel(argument, str)] SizeChange),
     ExpandColumnToAvailableWidth,
+    EqualizeColumnWidths,
+    DistributeColumnWidths,
+    ToggleMonocle,
     SwitchLayout(#[knuffel(argument, str)] LayoutSwitchTarget),
     ShowHotkeyOverlay,
     MoveWorkspaceToMonitorLeft,
@@ -339,6 +423,12 @@ pub enum Action {
     MoveWindowToTiling,
     #[knuffel(skip)]
     MoveWindowToTilingById(u64),
+    ToggleWindowPinned,
+    #[knuffel(skip)]
+    ToggleWindowPinnedById(u64),
+    ToggleWindowAlwaysOnTop,
+    #[knuffel(skip)]
+    ToggleWindowAlwaysOnTopById(u64),
     FocusFloating,
     FocusTiling,
     SwitchFocusBetweenFloatingAndTiling,
@@ -351,6 +441,23 @@ pub enum Action {
     ToggleWindowRuleOpacity,
     #[knuffel(skip)]
     ToggleWindowRuleOpacityById(u64),
+    ToggleWindowTag(#[knuffel(argument)] String),
+    #[knuffel(skip)]
+    ToggleWindowTagById {
+        id: u64,
+        tag: String,
+    },
+    FocusWindowWithTag(#[knuffel(argument)] String),
+    BringWindowsWithTag(#[knuffel(argument)] String),
+    SetMark(#[knuffel(argument, str)] char),
+    #[knuffel(skip)]
+    SetMarkById {
+        id: u64,
+        mark: char,
+    },
+    GotoMark(#[knuffel(argument, str)] char),
+    SetWindowRuleProfile(#[knuffel(argument)] Option<String>),
+    ToggleWindowRuleProfile(#[knuffel(argument)] String),
     SetDynamicCastWindow,
     #[knuffel(skip)]
     SetDynamicCastWindowById(u64),
@@ -393,19 +500,56 @@ impl From<niri_ipc::Action> for Action {
     fn from(value: niri_ipc::Action) -> Self {
         match value {
             niri_ipc::Action::Quit { skip_confirmation } => Self::Quit(skip_confirmation),
+            niri_ipc::Action::Suspend {} => Self::Suspend,
             niri_ipc::Action::PowerOffMonitors {} => Self::PowerOffMonitors,
             niri_ipc::Action::PowerOnMonitors {} => Self::PowerOnMonitors,
             niri_ipc::Action::Spawn { command } => Self::Spawn(command),
             niri_ipc::Action::SpawnSh { command } => Self::SpawnSh(command),
+            niri_ipc::Action::SendKeys { window, keys } => {
+                let keys = keys
+                    .iter()
+                    .filter_map(|key| key.parse::<Key>().ok())
+                    .filter(|key| matches!(key.trigger, Trigger::Keysym(_)))
+                    .collect();
+                match window {
+                    None => Self::SendKeys(keys),
+                    Some(id) => Self::SendKeysById { id, keys },
+                }
+            }
+            niri_ipc::Action::SwitchBindMode { name } => Self::SwitchBindMode(name),
+            niri_ipc::Action::EnterResizeMode {} => Self::EnterResizeMode,
             niri_ipc::Action::DoScreenTransition { delay_ms } => Self::DoScreenTransition(delay_ms),
             niri_ipc::Action::Screenshot { show_pointer, path } => {
                 Self::Screenshot(show_pointer, path)
             }
             niri_ipc::Action::ScreenshotScreen {
+                output: None,
                 write_to_disk,
                 show_pointer,
                 path,
             } => Self::ScreenshotScreen(write_to_disk, show_pointer, path),
+            niri_ipc::Action::ScreenshotScreen {
+                output: Some(output),
+                write_to_disk,
+                show_pointer,
+                path,
+            } => Self::ScreenshotOutput {
+                output,
+                write_to_disk,
+                show_pointer,
+                path,
+            },
+            niri_ipc::Action::ScreenshotRegion {
+                region,
+                write_to_disk,
+                show_pointer,
+                path,
+            } => Self::ScreenshotRegion {
+                region,
+                write_to_disk,
+                show_pointer,
+                path,
+            },
             niri_ipc::Action::ScreenshotWindow {
                 id: None,
                 write_to_disk,
@@ -423,11 +567,23 @@ impl From<niri_ipc::Action> for Action {
                 show_pointer,
                 path,
             },
+            niri_ipc::Action::PickColor {} => Self::PickColor,
             niri_ipc::Action::ToggleKeyboardShortcutsInhibit {} => {
                 Self::ToggleKeyboardShortcutsInhibit
             }
             niri_ipc::Action::CloseWindow { id: None } => Self::CloseWindow,
             niri_ipc::Action::CloseWindow { id: Some(id) } => Self::CloseWindowById(id),
+            niri_ipc::Action::ForceCloseWindow {
+                id: None,
+                under_cursor,
+            } => Self::ForceCloseWindow(under_cursor),
+            niri_ipc::Action::ForceCloseWindow { id: Some(id), .. } => {
+                Self::ForceCloseWindowById(id)
+            }
+            niri_ipc::Action::MinimizeWindow { id: None } => Self::MinimizeWindow,
+            niri_ipc::Action::MinimizeWindow { id: Some(id) } => Self::MinimizeWindowById(id),
+            niri_ipc::Action::UnminimizeWindow { id: None } => Self::UnminimizeWindow,
+            niri_ipc::Action::UnminimizeWindow { id: Some(id) } => Self::UnminimizeWindowById(id),
             niri_ipc::Action::FullscreenWindow { id: None } => Self::FullscreenWindow,
             niri_ipc::Action::FullscreenWindow { id: Some(id) } => Self::FullscreenWindowById(id),
             niri_ipc::Action::ToggleWindowedFullscreen { id: None } => {
@@ -496,6 +652,7 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::SwapWindowRight {} => Self::SwapWindowRight,
             niri_ipc::Action::SwapWindowLeft {} => Self::SwapWindowLeft,
             niri_ipc::Action::ToggleColumnTabbedDisplay {} => Self::ToggleColumnTabbedDisplay,
+            niri_ipc::Action::ToggleColumnAccordionDisplay {} => Self::ToggleColumnAccordionDisplay,
             niri_ipc::Action::SetColumnDisplay { display } => Self::SetColumnDisplay(display),
             niri_ipc::Action::CenterColumn {} => Self::CenterColumn,
             niri_ipc::Action::CenterWindow { id: None } => Self::CenterWindow,
@@ -623,6 +780,9 @@ impl From<niri_ipc::Action> for Action {
             }
             niri_ipc::Action::SetColumnWidth { change } => Self::SetColumnWidth(change),
             niri_ipc::Action::ExpandColumnToAvailableWidth {} => Self::ExpandColumnToAvailableWidth,
+            niri_ipc::Action::EqualizeColumnWidths {} => Self::EqualizeColumnWidths,
+            niri_ipc::Action::DistributeColumnWidths {} => Self::DistributeColumnWidths,
+            niri_ipc::Action::ToggleMonocle {} => Self::ToggleMonocle,
             niri_ipc::Action::SwitchLayout { layout } => Self::SwitchLayout(layout),
             niri_ipc::Action::ShowHotkeyOverlay {} => Self::ShowHotkeyOverlay,
             niri_ipc::Action::MoveWorkspaceToMonitorLeft {} => Self::MoveWorkspaceToMonitorLeft,
@@ -658,6 +818,27 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::ToggleDebugTint {} => Self::ToggleDebugTint,
             niri_ipc::Action::DebugToggleOpaqueRegions {} => Self::DebugToggleOpaqueRegions,
             niri_ipc::Action::DebugToggleDamage {} => Self::DebugToggleDamage,
+            niri_ipc::Action::DebugToggleGestureOverlay {} => Self::DebugToggleGestureOverlay,
+            niri_ipc::Action::ResetMagnifier {} => Self::ResetMagnifier,
+            niri_ipc::Action::SimulateGesture {
+                kind,
+                fingers,
+                dx,
+                dy,
+                scale,
+                rotation,
+                duration_ms,
+                cancelled,
+            } => Self::SimulateGesture(SimulatedGesture {
+                kind: kind.into(),
+                fingers,
+                dx,
+                dy,
+                scale,
+                rotation,
+                duration_ms,
+                cancelled,
+            }),
             niri_ipc::Action::ToggleWindowFloating { id: None } => Self::ToggleWindowFloating,
             niri_ipc::Action::ToggleWindowFloating { id: Some(id) } => {
                 Self::ToggleWindowFloatingById(id)
@@ -670,6 +851,14 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::MoveWindowToTiling { id: Some(id) } => {
                 Self::MoveWindowToTilingById(id)
             }
+            niri_ipc::Action::ToggleWindowPinned { id: None } => Self::ToggleWindowPinned,
+            niri_ipc::Action::ToggleWindowPinned { id: Some(id) } => {
+                Self::ToggleWindowPinnedById(id)
+            }
+            niri_ipc::Action::ToggleWindowAlwaysOnTop { id: None } => Self::ToggleWindowAlwaysOnTop,
+            niri_ipc::Action::ToggleWindowAlwaysOnTop { id: Some(id) } => {
+                Self::ToggleWindowAlwaysOnTopById(id)
+            }
             niri_ipc::Action::FocusFloating {} => Self::FocusFloating,
             niri_ipc::Action::FocusTiling {} => Self::FocusTiling,
             niri_ipc::Action::SwitchFocusBetweenFloatingAndTiling {} => {
@@ -682,6 +871,21 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::ToggleWindowRuleOpacity { id: Some(id) } => {
                 Self::ToggleWindowRuleOpacityById(id)
             }
+            niri_ipc::Action::ToggleWindowTag { tag, id: None } => Self::ToggleWindowTag(tag),
+            niri_ipc::Action::ToggleWindowTag { tag, id: Some(id) } => {
+                Self::ToggleWindowTagById { id, tag }
+            }
+            niri_ipc::Action::FocusWindowWithTag { tag } => Self::FocusWindowWithTag(tag),
+            niri_ipc::Action::BringWindowsWithTag { tag } => Self::BringWindowsWithTag(tag),
+            niri_ipc::Action::SetMark { mark, id: None } => Self::SetMark(mark),
+            niri_ipc::Action::SetMark { mark, id: Some(id) } => Self::SetMarkById { id, mark },
+            niri_ipc::Action::GotoMark { mark } => Self::GotoMark(mark),
+            niri_ipc::Action::SetWindowRuleProfile { profile } => {
+                Self::SetWindowRuleProfile(profile)
+            }
+            niri_ipc::Action::ToggleWindowRuleProfile { profile } => {
+                Self::ToggleWindowRuleProfile(profile)
+            }
             niri_ipc::Action::SetDynamicCastWindow { id: None } => Self::SetDynamicCastWindow,
             niri_ipc::Action::SetDynamicCastWindow { id: Some(id) } => {
                 Self::SetDynamicCastWindowById(id)
@@ -756,6 +960,56 @@ impl<S: knuffel::traits::ErrorSpan> knuffel::DecodeScalar<S> for WorkspaceRefere
     }
 }
 
+impl<S: knuffel::traits::ErrorSpan> knuffel::DecodeScalar<S> for Key {
+    fn type_check(
+        type_name: &Option<knuffel::span::Spanned<knuffel::ast::TypeName, S>>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) {
+        if let Some(type_name) = &type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+    }
+
+    fn raw_decode(
+        val: &knuffel::span::Spanned<knuffel::ast::Literal, S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Key, DecodeError<S>> {
+        let dummy = Key {
+            trigger: Trigger::Keysym(Keysym::NoSymbol),
+            modifiers: Modifiers::empty(),
+        };
+
+        match &**val {
+            knuffel::ast::Literal::String(ref s) => match s.parse::<Key>() {
+                // Keys used as values are sent to clients, so they must be keyboard keys.
+                Ok(key) if matches!(key.trigger, Trigger::Keysym(_)) => Ok(key),
+                Ok(_) => {
+                    ctx.emit_error(DecodeError::conversion(
+                        val,
+                        "key must be a keyboard key",
+                    ));
+                    Ok(dummy)
+                }
+                Err(e) => {
+                    ctx.emit_error(DecodeError::conversion(val, e.wrap_err("invalid key")));
+                    Ok(dummy)
+                }
+            },
+            _ => {
+                ctx.emit_error(DecodeError::unsupported(
+                    val,
+                    "Unsupported value, only strings are recognized",
+                ));
+                Ok(dummy)
+            }
+        }
+    }
+}
+
 impl<S> knuffel::Decode<S> for Binds
 where
     S: knuffel::traits::ErrorSpan,
@@ -766,56 +1020,177 @@ where
     ) -> Result<Self, DecodeError<S>> {
         expect_only_children(node, ctx);
 
-        let mut seen_keys = HashSet::new();
+        // Bind modes and region binds are decoded separately, see BindMode and RegionBinds.
+        let children = node.children().filter(|child| {
+            !matches!(
+                &**child.node_name,
+                "mode" | "over-background" | "over-border"
+            )
+        });
+        Ok(Self(decode_binds(children, ctx)))
+    }
+}
 
-        let mut binds = Vec::new();
+impl<S> knuffel::Decode<S> for BindMode
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
 
-        for child in node.children() {
-            match Bind::decode_node(child, ctx) {
-                Err(e) => {
-                    ctx.emit_error(e);
-                }
-                Ok(bind) => {
-                    if seen_keys.insert(bind.key) {
-                        binds.push(bind);
-                    } else {
-                        // ideally, this error should point to the previous instance of this keybind
-                        //
-                        // i (sodiboo) have tried to implement this in various ways:
-                        // miette!(), #[derive(Diagnostic)]
-                        // DecodeError::Custom, DecodeError::Conversion
-                        // nothing seems to work, and i suspect it's not possible.
-                        //
-                        // DecodeError is fairly restrictive.
-                        // even DecodeError::Custom just wraps a std::error::Error
-                        // and this erases all rich information from miette. (why???)
-                        //
-                        // why does knuffel do this?
-                        // from what i can tell, it doesn't even use DecodeError for much.
-                        // it only ever converts them to a Report anyways!
-                        // https://github.com/tailhook/knuffel/blob/c44c6b0c0f31ea6d1174d5d2ed41064922ea44ca/src/wrappers.rs#L55-L58
-                        //
-                        // besides like, allowing downstream users (such as us!)
-                        // to match on parse failure, i don't understand why
-                        // it doesn't just use a generic error type
-                        //
-                        // even the matching isn't consistent,
-                        // because errors can also be omitted as ctx.emit_error.
-                        // why does *that one* especially, require a DecodeError?
-                        //
-                        // anyways if you can make it format nicely, definitely do fix this
-                        ctx.emit_error(DecodeError::unexpected(
-                            &child.node_name,
-                            "keybind",
-                            "duplicate keybind",
-                        ));
-                    }
-                }
+        for name in node.properties.keys() {
+            ctx.emit_error(DecodeError::unexpected(
+                name,
+                "property",
+                "no properties expected for this node",
+            ));
+        }
+
+        let mut arguments = node.arguments.iter();
+        let Some(name) = arguments.next() else {
+            return Err(DecodeError::missing(node, "mode name is required"));
+        };
+        let name: String = knuffel::traits::DecodeScalar::decode(name, ctx)?;
+
+        for val in arguments {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "only one mode name is expected",
+            ));
+        }
+
+        if name == "default" {
+            ctx.emit_error(DecodeError::conversion(
+                &node.arguments[0].literal,
+                "the default mode uses the regular binds",
+            ));
+        }
+
+        let binds = decode_binds(node.children(), ctx);
+
+        // Scroll binds are looked up only for the modifiers bound in the regular binds.
+        for bind in &binds {
+            if matches!(
+                bind.key.trigger,
+                Trigger::WheelScrollDown
+                    | Trigger::WheelScrollUp
+                    | Trigger::WheelScrollLeft
+                    | Trigger::WheelScrollRight
+                    | Trigger::TouchpadScrollDown
+                    | Trigger::TouchpadScrollUp
+                    | Trigger::TouchpadScrollLeft
+                    | Trigger::TouchpadScrollRight
+            ) {
+                ctx.emit_error(DecodeError::unexpected(
+                    node,
+                    "keybind",
+                    "scroll binds are not supported in bind modes",
+                ));
             }
         }
 
-        Ok(Self(binds))
+        Ok(Self { name, binds })
+    }
+}
+
+/// Decodes an `over-background` or `over-border` section of [`RegionBinds`].
+pub fn decode_region_binds<S>(
+    node: &knuffel::ast::SpannedNode<S>,
+    ctx: &mut knuffel::decode::Context<S>,
+) -> Vec<Bind>
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    expect_only_children(node, ctx);
+
+    let binds = decode_binds(node.children(), ctx);
+
+    for bind in &binds {
+        if !matches!(
+            bind.key.trigger,
+            Trigger::WheelScrollDown
+                | Trigger::WheelScrollUp
+                | Trigger::WheelScrollLeft
+                | Trigger::WheelScrollRight
+        ) {
+            ctx.emit_error(DecodeError::unexpected(
+                node,
+                "keybind",
+                "only wheel scroll binds are supported here",
+            ));
+        }
+    }
+
+    binds
+}
+
+fn decode_binds<'a, S>(
+    nodes: impl Iterator<Item = &'a knuffel::ast::SpannedNode<S>>,
+    ctx: &mut knuffel::decode::Context<S>,
+) -> Vec<Bind>
+where
+    S: knuffel::traits::ErrorSpan + 'a,
+{
+    // Double-press binds can share the key with a single-press bind.
+    let mut seen_keys = HashSet::new();
+
+    let mut binds = Vec::new();
+
+    for child in nodes {
+        match Bind::decode_node(child, ctx) {
+            Err(e) => {
+                ctx.emit_error(e);
+            }
+            Ok(bind) => {
+                if seen_keys.insert((bind.key, bind.double_press.is_some())) {
+                    binds.push(bind);
+                } else {
+                    // ideally, this error should point to the previous instance of this keybind
+                    //
+                    // i (sodiboo) have tried to implement this in various ways:
+                    // miette!(), #[derive(Diagnostic)]
+                    // DecodeError::Custom, DecodeError::Conversion
+                    // nothing seems to work, and i suspect it's not possible.
+                    //
+                    // DecodeError is fairly restrictive.
+                    // even DecodeError::Custom just wraps a std::error::Error
+                    // and this erases all rich information from miette. (why???)
+                    //
+                    // why does knuffel do this?
+                    // from what i can tell, it doesn't even use DecodeError for much.
+                    // it only ever converts them to a Report anyways!
+                    // https://github.com/tailhook/knuffel/blob/c44c6b0c0f31ea6d1174d5d2ed41064922ea44ca/src/wrappers.rs#L55-L58
+                    //
+                    // besides like, allowing downstream users (such as us!)
+                    // to match on parse failure, i don't understand why
+                    // it doesn't just use a generic error type
+                    //
+                    // even the matching isn't consistent,
+                    // because errors can also be omitted as ctx.emit_error.
+                    // why does *that one* especially, require a DecodeError?
+                    //
+                    // anyways if you can make it format nicely, definitely do fix this
+                    ctx.emit_error(DecodeError::unexpected(
+                        &child.node_name,
+                        "keybind",
+                        "duplicate keybind",
+                    ));
+                }
+            }
+        }
     }
+
+    binds
 }
 
 impl<S> knuffel::Decode<S> for Bind
@@ -849,6 +1224,8 @@ where
 
         let mut repeat = true;
         let mut cooldown = None;
+        let mut double_press = None;
+        let mut double_press_node = None;
         let mut allow_when_locked = false;
         let mut allow_when_locked_node = None;
         let mut allow_inhibiting = true;
@@ -863,6 +1240,12 @@ where
                         knuffel::traits::DecodeScalar::decode(val, ctx)?,
                     ));
                 }
+                "double-press-ms" => {
+                    double_press = Some(Duration::from_millis(
+                        knuffel::traits::DecodeScalar::decode(val, ctx)?,
+                    ));
+                    double_press_node = Some(name);
+                }
                 "allow-when-locked" => {
                     allow_when_locked = knuffel::traits::DecodeScalar::decode(val, ctx)?;
                     allow_when_locked_node = Some(name);
@@ -883,6 +1266,16 @@ where
             }
         }
 
+        if let Some(node) = double_press_node {
+            if !matches!(key.trigger, Trigger::Keysym(_) | Trigger::ModifierTap) {
+                ctx.emit_error(DecodeError::unexpected(
+                    node,
+                    "property",
+                    "double-press-ms can only be set on keyboard key and modifier tap binds",
+                ));
+            }
+        }
+
         let mut children = node.children();
 
         // If the action is invalid but the key is fine, we still want to return something.
@@ -893,6 +1286,7 @@ where
             action: Action::Spawn(vec![]),
             repeat: true,
             cooldown: None,
+            double_press: None,
             allow_when_locked: false,
             allow_inhibiting: true,
             hotkey_overlay_title: None,
@@ -929,6 +1323,7 @@ where
                         action,
                         repeat,
                         cooldown,
+                        double_press,
                         allow_when_locked,
                         allow_inhibiting,
                         hotkey_overlay_title,
@@ -960,27 +1355,10 @@ impl FromStr for Key {
 
         for part in split {
             let part = part.trim();
-            if part.eq_ignore_ascii_case("mod") {
-                modifiers |= Modifiers::COMPOSITOR
-            } else if part.eq_ignore_ascii_case("ctrl") || part.eq_ignore_ascii_case("control") {
-                modifiers |= Modifiers::CTRL;
-            } else if part.eq_ignore_ascii_case("shift") {
-                modifiers |= Modifiers::SHIFT;
-            } else if part.eq_ignore_ascii_case("alt") {
-                modifiers |= Modifiers::ALT;
-            } else if part.eq_ignore_ascii_case("super") || part.eq_ignore_ascii_case("win") {
-                modifiers |= Modifiers::SUPER;
-            } else if part.eq_ignore_ascii_case("iso_level3_shift")
-                || part.eq_ignore_ascii_case("mod5")
-            {
-                modifiers |= Modifiers::ISO_LEVEL3_SHIFT;
-            } else if part.eq_ignore_ascii_case("iso_level5_shift")
-                || part.eq_ignore_ascii_case("mod3")
-            {
-                modifiers |= Modifiers::ISO_LEVEL5_SHIFT;
-            } else {
+            let Some(modifier) = parse_modifier(part) else {
                 return Err(miette!("invalid modifier: {part}"));
-            }
+            };
+            modifiers |= modifier;
         }
 
         let trigger = if key.eq_ignore_ascii_case("MouseLeft") {
@@ -1009,6 +1387,12 @@ impl FromStr for Key {
             Trigger::TouchpadScrollLeft
         } else if key.eq_ignore_ascii_case("TouchpadScrollRight") {
             Trigger::TouchpadScrollRight
+        } else if let Some(modifier) = parse_modifier(key)
+            // Some modifier names like ISO_Level3_Shift are keysyms too; keep binding those.
+            .filter(|_| keysym_from_name(key, KEYSYM_CASE_INSENSITIVE).raw() == KEY_NoSymbol)
+        {
+            modifiers |= modifier;
+            Trigger::ModifierTap
         } else {
             let mut keysym = keysym_from_name(key, KEYSYM_CASE_INSENSITIVE);
             // The keyboard event handling code can receive either
@@ -1048,10 +1432,57 @@ impl FromStr for Key {
     }
 }
 
+fn parse_modifier(part: &str) -> Option<Modifiers> {
+    let modifier = if part.eq_ignore_ascii_case("mod") {
+        Modifiers::COMPOSITOR
+    } else if part.eq_ignore_ascii_case("ctrl") || part.eq_ignore_ascii_case("control") {
+        Modifiers::CTRL
+    } else if part.eq_ignore_ascii_case("shift") {
+        Modifiers::SHIFT
+    } else if part.eq_ignore_ascii_case("alt") {
+        Modifiers::ALT
+    } else if part.eq_ignore_ascii_case("super") || part.eq_ignore_ascii_case("win") {
+        Modifiers::SUPER
+    } else if part.eq_ignore_ascii_case("iso_level3_shift") || part.eq_ignore_ascii_case("mod5") {
+        Modifiers::ISO_LEVEL3_SHIFT
+    } else if part.eq_ignore_ascii_case("iso_level5_shift") || part.eq_ignore_ascii_case("mod3") {
+        Modifiers::ISO_LEVEL5_SHIFT
+    } else {
+        return None;
+    };
+    Some(modifier)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_modifier_tap() {
+        assert_eq!(
+            "Mod".parse::<Key>().unwrap(),
+            Key {
+                trigger: Trigger::ModifierTap,
+                modifiers: Modifiers::COMPOSITOR,
+            },
+        );
+        assert_eq!(
+            "Ctrl+Shift".parse::<Key>().unwrap(),
+            Key {
+                trigger: Trigger::ModifierTap,
+                modifiers: Modifiers::CTRL | Modifiers::SHIFT,
+            },
+        );
+        // Modifier keysyms still bind the key itself.
+        assert_eq!(
+            "ISO_Level3_Shift".parse::<Key>().unwrap(),
+            Key {
+                trigger: Trigger::Keysym(Keysym::ISO_Level3_Shift),
+                modifiers: Modifiers::empty(),
+            },
+        );
+    }
+
     #[test]
     fn parse_xf86_screensaver() {
         assert_eq!(
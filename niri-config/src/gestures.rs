@@ -1,14 +1,80 @@
-use crate::utils::MergeWith;
-use crate::FloatOrInt;
+use std::collections::HashSet;
+use std::str::FromStr;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq)]
+use knuffel::errors::DecodeError;
+use miette::miette;
+use smithay::input::keyboard::Keysym;
+
+use crate::utils::{expect_only_children, Flag, MergeWith, RegexEq};
+use crate::{Action, FloatOrInt, Key, Modifiers, Trigger};
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Gestures {
     pub dnd_edge_view_scroll: DndEdgeViewScroll,
     pub dnd_edge_workspace_switch: DndEdgeWorkspaceSwitch,
     pub hot_corners: HotCorners,
+    pub thresholds: GestureThresholds,
+    pub binds: GestureBinds,
+    pub profiles: Vec<GestureProfile>,
+    pub sequences: Vec<GestureSequence>,
+    pub devices: Vec<GestureDevice>,
+    pub mouse: MouseGestures,
+    /// Single-finger swipes from the touchscreen edges.
+    pub edge_swipes: Vec<EdgeSwipe>,
+    /// Finger count of the hold that turns into a window move when the fingers start moving.
+    pub hold_drag_move: Option<u8>,
+    /// Finger count of the pinch that continuously opens and closes the overview.
+    pub overview_pinch: Option<u8>,
+    /// Whether the vertical three-finger swipe maximizes the focused window instead of switching
+    /// workspaces.
+    pub maximize_swipe: bool,
+    /// Finger count of the pinch over a floating window that resizes it.
+    pub resize_pinch: Option<u8>,
+    /// Finger count of the pinch over the desktop that zooms in with the magnifier.
+    pub magnifier_pinch: Option<u8>,
+    /// Whether to show how far the gesture bind in progress is from triggering.
+    pub progress_indicator: bool,
+    /// Where the built-in three-finger swipe goes along each axis.
+    pub three_finger_swipe: ThreeFingerSwipe,
+    /// Whether dragging on the empty desktop background scrolls the view.
+    pub background_drag: bool,
+    /// Whether dragging a window border with the left button resizes the window.
+    pub border_resize: bool,
+    /// Key that cancels the gesture in progress.
+    pub cancel_key: Key,
 }
 
-#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+impl Default for Gestures {
+    fn default() -> Self {
+        Self {
+            dnd_edge_view_scroll: DndEdgeViewScroll::default(),
+            dnd_edge_workspace_switch: DndEdgeWorkspaceSwitch::default(),
+            hot_corners: HotCorners::default(),
+            thresholds: GestureThresholds::default(),
+            binds: GestureBinds::default(),
+            profiles: Vec::new(),
+            sequences: Vec::new(),
+            devices: Vec::new(),
+            mouse: MouseGestures::default(),
+            edge_swipes: Vec::new(),
+            hold_drag_move: None,
+            overview_pinch: None,
+            maximize_swipe: false,
+            resize_pinch: None,
+            magnifier_pinch: None,
+            progress_indicator: false,
+            three_finger_swipe: ThreeFingerSwipe::default(),
+            background_drag: false,
+            border_resize: false,
+            cancel_key: Key {
+                trigger: Trigger::Keysym(Keysym::Escape),
+                modifiers: Modifiers::empty(),
+            },
+        }
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
 pub struct GesturesPart {
     #[knuffel(child)]
     pub dnd_edge_view_scroll: Option<DndEdgeViewScrollPart>,
@@ -16,6 +82,40 @@ pub struct GesturesPart {
     pub dnd_edge_workspace_switch: Option<DndEdgeWorkspaceSwitchPart>,
     #[knuffel(child)]
     pub hot_corners: Option<HotCorners>,
+    #[knuffel(child)]
+    pub thresholds: Option<GestureThresholdsPart>,
+    #[knuffel(child)]
+    pub binds: Option<GestureBinds>,
+    #[knuffel(children(name = "profile"))]
+    pub profiles: Vec<GestureProfile>,
+    #[knuffel(children(name = "sequence"))]
+    pub sequences: Vec<GestureSequence>,
+    #[knuffel(children(name = "device"))]
+    pub devices: Vec<GestureDevice>,
+    #[knuffel(child)]
+    pub mouse: Option<MouseGestures>,
+    #[knuffel(children(name = "edge-swipe"))]
+    pub edge_swipes: Vec<EdgeSwipe>,
+    #[knuffel(child, unwrap(argument))]
+    pub hold_drag_move: Option<u8>,
+    #[knuffel(child, unwrap(argument))]
+    pub overview_pinch: Option<u8>,
+    #[knuffel(child)]
+    pub maximize_swipe: Option<Flag>,
+    #[knuffel(child, unwrap(argument))]
+    pub resize_pinch: Option<u8>,
+    #[knuffel(child, unwrap(argument))]
+    pub magnifier_pinch: Option<u8>,
+    #[knuffel(child)]
+    pub progress_indicator: Option<Flag>,
+    #[knuffel(child)]
+    pub three_finger_swipe: Option<ThreeFingerSwipe>,
+    #[knuffel(child)]
+    pub background_drag: Option<Flag>,
+    #[knuffel(child)]
+    pub border_resize: Option<Flag>,
+    #[knuffel(child, unwrap(argument))]
+    pub cancel_key: Option<Key>,
 }
 
 impl MergeWith<GesturesPart> for Gestures {
@@ -24,8 +124,195 @@ impl MergeWith<GesturesPart> for Gestures {
             (self, part),
             dnd_edge_view_scroll,
             dnd_edge_workspace_switch,
+            thresholds,
+            maximize_swipe,
+            progress_indicator,
+            three_finger_swipe,
+            background_drag,
+            border_resize,
+        );
+        merge_clone!((self, part), hot_corners, mouse, cancel_key);
+        merge_clone_opt!(
+            (self, part),
+            hold_drag_move,
+            overview_pinch,
+            resize_pinch,
+            magnifier_pinch,
         );
-        merge_clone!((self, part), hot_corners);
+
+        if let Some(part) = &part.binds {
+            // Like with regular binds, replace conflicting gesture binds rather than error.
+            self.binds
+                .0
+                .retain(|bind| !part.0.iter().any(|new| new.trigger == bind.trigger));
+            self.binds.0.extend(part.0.iter().cloned());
+        }
+
+        self.profiles.extend(part.profiles.iter().cloned());
+
+        for sequence in &part.sequences {
+            self.sequences.retain(|old| old.steps != sequence.steps);
+            self.sequences.push(sequence.clone());
+        }
+
+        for device in &part.devices {
+            self.devices.retain(|old| old.name != device.name);
+            self.devices.push(device.clone());
+        }
+
+        for swipe in &part.edge_swipes {
+            self.edge_swipes
+                .retain(|old| (old.edge, old.tablet_mode) != (swipe.edge, swipe.tablet_mode));
+            self.edge_swipes.push(swipe.clone());
+        }
+    }
+}
+
+impl Gestures {
+    /// Returns the edge swipe bound to this edge.
+    ///
+    /// Edge swipes limited to one tablet mode state take precedence over the unlimited ones.
+    pub fn find_edge_swipe(&self, edge: ScreenEdge, is_tablet_mode: bool) -> Option<&EdgeSwipe> {
+        let mut swipes = self.edge_swipes.iter().filter(|swipe| swipe.edge == edge);
+        let fallback = swipes.clone().find(|swipe| swipe.tablet_mode.is_none());
+        swipes
+            .find(|swipe| swipe.tablet_mode == Some(is_tablet_mode))
+            .or(fallback)
+    }
+
+    /// Finds the gesture bind for the target.
+    ///
+    /// The binds changed over IPC take precedence over everything else. Then, binds from matching
+    /// profiles take precedence over the global binds, and later profiles take precedence over
+    /// earlier ones.
+    pub fn find_bind(
+        &self,
+        target: GestureTarget,
+        overrides: &GestureOverrides,
+        kind: GestureKind,
+        fingers: u8,
+        direction: Option<GestureDirection>,
+        fling: bool,
+    ) -> Option<&GestureBind> {
+        let trigger = GestureTrigger {
+            kind,
+            fingers,
+            direction,
+            fling,
+        };
+        if overrides.disabled.contains(&trigger) {
+            return None;
+        }
+
+        self.binds_for(target, overrides)
+            .find_map(|binds| binds.find(kind, fingers, direction, fling))
+    }
+
+    /// Returns the swipe directions with fling binds with this many fingers for the target.
+    pub fn fling_swipe_directions(
+        &self,
+        target: GestureTarget,
+        overrides: &GestureOverrides,
+        fingers: u8,
+    ) -> Vec<GestureDirection> {
+        self.enabled_binds_for(target, overrides)
+            .map(|bind| bind.trigger)
+            .filter(|trigger| {
+                trigger.kind == GestureKind::Swipe && trigger.fingers == fingers && trigger.fling
+            })
+            .filter_map(|trigger| trigger.direction)
+            .collect()
+    }
+
+    /// Returns whether there are any binds for this kind of gesture with this many fingers for the
+    /// target.
+    pub fn has_any_bind(
+        &self,
+        target: GestureTarget,
+        overrides: &GestureOverrides,
+        kind: GestureKind,
+        fingers: u8,
+    ) -> bool {
+        self.enabled_binds_for(target, overrides)
+            .any(|bind| bind.trigger.kind == kind && bind.trigger.fingers == fingers)
+            || self
+                .sequence_steps()
+                .any(|step| step.kind == kind && step.fingers == fingers)
+    }
+
+    /// Returns whether there are any diagonal swipe binds with this many fingers for the target.
+    pub fn has_diagonal_swipe_bind(
+        &self,
+        target: GestureTarget,
+        overrides: &GestureOverrides,
+        fingers: u8,
+    ) -> bool {
+        let is_diagonal_swipe = |trigger: &GestureTrigger| {
+            trigger.kind == GestureKind::Swipe
+                && trigger.fingers == fingers
+                && trigger.direction.is_some_and(|d| d.is_diagonal())
+        };
+        self.enabled_binds_for(target, overrides)
+            .any(|bind| is_diagonal_swipe(&bind.trigger))
+            || self.sequence_steps().any(is_diagonal_swipe)
+    }
+
+    /// Returns the thresholds for the input device with the given name.
+    ///
+    /// The device overrides apply on top of the global thresholds, and then the distances scale
+    /// with the touchpad width in millimeters, if known.
+    pub fn thresholds_for_device(&self, name: &str, width_mm: Option<f64>) -> GestureThresholds {
+        let mut thresholds = self.thresholds;
+
+        let device = self.devices.iter().find(|device| device.name == name);
+        if let Some(part) = device.and_then(|device| device.thresholds.as_ref()) {
+            thresholds.merge_with(part);
+        }
+
+        if let Some(width_mm) = width_mm {
+            thresholds.scale_to_width(width_mm);
+        }
+
+        thresholds
+    }
+
+    /// Returns whether this kind of gesture is disabled on the input device with the given name.
+    pub fn is_disabled_on_device(&self, name: &str, kind: GestureKind) -> bool {
+        self.devices
+            .iter()
+            .find(|device| device.name == name)
+            .is_some_and(|device| device.is_disabled(kind))
+    }
+
+    fn sequence_steps(&self) -> impl Iterator<Item = &GestureTrigger> {
+        self.sequences.iter().flat_map(|sequence| &sequence.steps)
+    }
+
+    fn binds_for<'a>(
+        &'a self,
+        target: GestureTarget<'a>,
+        overrides: &'a GestureOverrides,
+    ) -> impl Iterator<Item = &'a GestureBinds> + 'a {
+        let profiles = self
+            .profiles
+            .iter()
+            .rev()
+            .filter(move |profile| profile.matches(target))
+            .map(|profile| &profile.binds);
+        [&overrides.binds]
+            .into_iter()
+            .chain(profiles)
+            .chain([&self.binds])
+    }
+
+    fn enabled_binds_for<'a>(
+        &'a self,
+        target: GestureTarget<'a>,
+        overrides: &'a GestureOverrides,
+    ) -> impl Iterator<Item = &'a GestureBind> + 'a {
+        self.binds_for(target, overrides)
+            .flat_map(|binds| &binds.0)
+            .filter(|bind| !overrides.disabled.contains(&bind.trigger))
     }
 }
 
@@ -97,16 +384,1127 @@ impl MergeWith<DndEdgeWorkspaceSwitchPart> for DndEdgeWorkspaceSwitch {
     }
 }
 
-#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
 pub struct HotCorners {
     #[knuffel(child)]
     pub off: bool,
     #[knuffel(child)]
-    pub top_left: bool,
+    pub top_left: Option<HotCorner>,
+    #[knuffel(child)]
+    pub top_right: Option<HotCorner>,
+    #[knuffel(child)]
+    pub bottom_left: Option<HotCorner>,
+    #[knuffel(child)]
+    pub bottom_right: Option<HotCorner>,
+    /// How long the pointer needs to stay in the corner before it triggers.
+    #[knuffel(child, unwrap(argument), default)]
+    pub dwell_ms: u16,
+    /// Shortest time from one trigger to the next.
+    #[knuffel(child, unwrap(argument), default)]
+    pub retrigger_delay_ms: u16,
+    /// How far the pointer needs to push into the corner before it triggers, in logical pixels.
+    #[knuffel(child, unwrap(argument), default)]
+    pub pressure: u16,
+    /// Whether the hot corners work over a fullscreen window.
+    #[knuffel(child)]
+    pub in_fullscreen: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HotCorner {
+    /// Action to run; toggles the overview when unset.
+    pub action: Option<Action>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl HotCorners {
+    /// Returns the action of the corner, or `None` if the corner is disabled.
+    pub fn action(&self, corner: Corner) -> Option<Action> {
+        if self.off {
+            return None;
+        }
+
+        // If the user didn't explicitly set any corners, we default to top-left.
+        let any_set = self.top_left.is_some()
+            || self.top_right.is_some()
+            || self.bottom_left.is_some()
+            || self.bottom_right.is_some();
+        if !any_set {
+            return (corner == Corner::TopLeft).then_some(Action::ToggleOverview);
+        }
+
+        let hot_corner = match corner {
+            Corner::TopLeft => &self.top_left,
+            Corner::TopRight => &self.top_right,
+            Corner::BottomLeft => &self.bottom_left,
+            Corner::BottomRight => &self.bottom_right,
+        };
+        let hot_corner = hot_corner.as_ref()?;
+        Some(hot_corner.action.clone().unwrap_or(Action::ToggleOverview))
+    }
+}
+
+/// Targets of the built-in three-finger swipe, also used as a window rule.
+///
+/// Unset axes fall back to the config, and then to the layout.
+#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+pub struct ThreeFingerSwipe {
+    #[knuffel(property)]
+    pub horizontal: Option<SwipeTarget>,
+    #[knuffel(property)]
+    pub vertical: Option<SwipeTarget>,
+}
+
+#[derive(knuffel::DecodeScalar, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeTarget {
+    /// Scroll the view horizontally, or switch workspaces vertically.
+    #[default]
+    Layout,
+    /// Send the swipe to the window under the cursor as kinetic scrolling.
+    Client,
+}
+
+impl MergeWith<Self> for ThreeFingerSwipe {
+    fn merge_with(&mut self, part: &Self) {
+        merge_clone_opt!((self, part), horizontal, vertical);
+    }
+}
+
+/// Gesture settings for the input device with a matching name.
+#[derive(knuffel::Decode, Debug, Clone, PartialEq)]
+pub struct GestureDevice {
+    #[knuffel(argument)]
+    pub name: String,
+    #[knuffel(child)]
+    pub off: bool,
+    #[knuffel(child)]
+    pub disable_swipe: bool,
     #[knuffel(child)]
-    pub top_right: bool,
+    pub disable_pinch: bool,
     #[knuffel(child)]
-    pub bottom_left: bool,
+    pub disable_hold: bool,
     #[knuffel(child)]
-    pub bottom_right: bool,
+    pub thresholds: Option<GestureThresholdsPart>,
+}
+
+impl GestureDevice {
+    pub fn is_disabled(&self, kind: GestureKind) -> bool {
+        self.off
+            || match kind {
+                GestureKind::Swipe => self.disable_swipe,
+                GestureKind::Pinch => self.disable_pinch,
+                GestureKind::Hold => self.disable_hold,
+            }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureThresholds {
+    /// Distance, in unaccelerated touchpad units, that a swipe must travel to trigger.
+    pub swipe_distance: f64,
+    /// Pinch scale below which a pinch in triggers.
+    pub pinch_in_scale: f64,
+    /// Pinch scale above which a pinch out triggers.
+    pub pinch_out_scale: f64,
+    /// Rotation, in degrees, that a pinch must turn to trigger a rotation.
+    pub rotation_angle: f64,
+    /// Maximum angle, in degrees, between a swipe and a diagonal for the swipe to be diagonal.
+    pub diagonal_angle: f64,
+    /// Speed, in unaccelerated touchpad units per second, at which a swipe ending counts as a
+    /// fling.
+    pub fling_velocity: f64,
+    /// How far, in multiples of the swipe distance, a triggered swipe must turn to the side to
+    /// change its direction; 0 keeps the first direction.
+    pub swipe_hysteresis: f64,
+    /// Touchpad width, in millimeters, that the swipe distances are meant for; 0 keeps them the
+    /// same on every touchpad.
+    pub reference_width_mm: f64,
+    /// Time that a hold must last before moving the fingers turns it into a window move.
+    pub hold_drag_delay_ms: u16,
+    /// Time that a two-finger hold must last to trigger, to tell it apart from a tap or from
+    /// fingers resting before a scroll.
+    pub two_finger_hold_delay_ms: u16,
+    /// Time within which the next gesture of a sequence must trigger.
+    pub sequence_timeout_ms: u16,
+}
+
+impl Default for GestureThresholds {
+    fn default() -> Self {
+        Self {
+            swipe_distance: 50.,
+            pinch_in_scale: 0.75,
+            pinch_out_scale: 1.33,
+            rotation_angle: 30.,
+            diagonal_angle: 15.,
+            fling_velocity: 1000.,
+            swipe_hysteresis: 0.,
+            reference_width_mm: 0.,
+            hold_drag_delay_ms: 300,
+            two_finger_hold_delay_ms: 500,
+            sequence_timeout_ms: 500,
+        }
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
+pub struct GestureThresholdsPart {
+    #[knuffel(child, unwrap(argument))]
+    pub swipe_distance: Option<FloatOrInt<0, 65535>>,
+    #[knuffel(child, unwrap(argument))]
+    pub pinch_in_scale: Option<FloatOrInt<0, 1>>,
+    #[knuffel(child, unwrap(argument))]
+    pub pinch_out_scale: Option<FloatOrInt<1, 100>>,
+    #[knuffel(child, unwrap(argument))]
+    pub rotation_angle: Option<FloatOrInt<0, 360>>,
+    #[knuffel(child, unwrap(argument))]
+    pub diagonal_angle: Option<FloatOrInt<0, 45>>,
+    #[knuffel(child, unwrap(argument))]
+    pub fling_velocity: Option<FloatOrInt<0, 1_000_000>>,
+    #[knuffel(child, unwrap(argument))]
+    pub swipe_hysteresis: Option<FloatOrInt<0, 100>>,
+    #[knuffel(child, unwrap(argument))]
+    pub reference_width_mm: Option<FloatOrInt<0, 1000>>,
+    #[knuffel(child, unwrap(argument))]
+    pub hold_drag_delay_ms: Option<u16>,
+    #[knuffel(child, unwrap(argument))]
+    pub two_finger_hold_delay_ms: Option<u16>,
+    #[knuffel(child, unwrap(argument))]
+    pub sequence_timeout_ms: Option<u16>,
+}
+
+impl GestureThresholds {
+    /// Scales the swipe distances from the reference width to a touchpad of this width.
+    pub fn scale_to_width(&mut self, width_mm: f64) {
+        if self.reference_width_mm <= 0. || width_mm <= 0. {
+            return;
+        }
+
+        let factor = width_mm / self.reference_width_mm;
+        self.swipe_distance *= factor;
+        self.fling_velocity *= factor;
+    }
+}
+
+impl MergeWith<GestureThresholdsPart> for GestureThresholds {
+    fn merge_with(&mut self, part: &GestureThresholdsPart) {
+        merge!(
+            (self, part),
+            swipe_distance,
+            pinch_in_scale,
+            pinch_out_scale,
+            rotation_angle,
+            diagonal_angle,
+            fling_velocity,
+            swipe_hysteresis,
+            reference_width_mm,
+        );
+        merge_clone!(
+            (self, part),
+            hold_drag_delay_ms,
+            two_finger_hold_delay_ms,
+            sequence_timeout_ms,
+        );
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GestureBinds(pub Vec<GestureBind>);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureBind {
+    pub trigger: GestureTrigger,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GestureTrigger {
+    pub kind: GestureKind,
+    pub fingers: u8,
+    /// Direction of the gesture; always `None` for hold gestures.
+    pub direction: Option<GestureDirection>,
+    /// Whether this swipe must end with a fling.
+    pub fling: bool,
+}
+
+/// Gestures that trigger an action when they happen one after another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GestureSequence {
+    /// Gestures of the sequence, in order; never fling swipes.
+    pub steps: Vec<GestureTrigger>,
+    pub action: Action,
+}
+
+/// Gesture bind changes made over IPC, layered over the configured binds.
+///
+/// They are not part of the config; the compositor keeps them and passes them in when looking up
+/// the binds. They apply to every gesture target and survive config reloads until they are reset.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GestureOverrides {
+    /// Binds that take precedence over the configured ones.
+    pub binds: GestureBinds,
+    /// Gestures that don't trigger any bind.
+    pub disabled: Vec<GestureTrigger>,
+}
+
+impl GestureOverrides {
+    /// Makes the gesture trigger nothing.
+    pub fn disable(&mut self, trigger: GestureTrigger) {
+        self.reset(trigger);
+        self.disabled.push(trigger);
+    }
+
+    /// Undoes disabling the gesture.
+    pub fn enable(&mut self, trigger: GestureTrigger) {
+        self.disabled.retain(|disabled| *disabled != trigger);
+    }
+
+    /// Makes the gesture trigger this action instead of the configured one.
+    pub fn rebind(&mut self, trigger: GestureTrigger, action: Action) {
+        self.reset(trigger);
+        self.binds.0.push(GestureBind { trigger, action });
+    }
+
+    /// Puts back the configured bind of the gesture.
+    pub fn reset(&mut self, trigger: GestureTrigger) {
+        self.enable(trigger);
+        self.binds.0.retain(|bind| bind.trigger != trigger);
+    }
+
+    pub fn is_overridden(&self, trigger: GestureTrigger) -> bool {
+        self.disabled.contains(&trigger) || self.binds.0.iter().any(|bind| bind.trigger == trigger)
+    }
+}
+
+/// Scripted touchpad gesture fed to the gesture binds over IPC.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulatedGesture {
+    pub kind: GestureKind,
+    pub fingers: u8,
+    /// Total swipe movement, in touchpad units.
+    pub dx: f64,
+    pub dy: f64,
+    /// Final pinch scale.
+    pub scale: f64,
+    /// Total pinch rotation in degrees, clockwise.
+    pub rotation: f64,
+    pub duration_ms: u32,
+    pub cancelled: bool,
+}
+
+/// Strokes drawn with the mouse while holding a button.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct MouseGestures {
+    /// Button to hold while drawing; mouse gestures are off without one.
+    #[knuffel(child, unwrap(argument))]
+    pub button: Option<MouseGestureButton>,
+    #[knuffel(children(name = "stroke"))]
+    pub strokes: Vec<MouseStroke>,
+}
+
+#[derive(knuffel::DecodeScalar, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseGestureButton {
+    Right,
+    Middle,
+    Back,
+    Forward,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MouseStroke {
+    /// Directions of the stroke segments, in order; one or two of left, right, up and down.
+    pub segments: Vec<GestureDirection>,
+    pub action: Action,
+}
+
+/// Swipe with one finger from an edge of a touchscreen towards its middle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeSwipe {
+    pub edge: ScreenEdge,
+    /// Whether the swipe only works in or out of tablet mode.
+    pub tablet_mode: Option<bool>,
+    pub action: Action,
+}
+
+#[derive(knuffel::DecodeScalar, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GestureKind {
+    Swipe,
+    Pinch,
+    Hold,
+}
+
+#[derive(knuffel::DecodeScalar, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GestureDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+    /// Fingers moving towards each other.
+    In,
+    /// Fingers moving away from each other.
+    Out,
+    /// Fingers rotating clockwise.
+    RotateCw,
+    /// Fingers rotating counter-clockwise.
+    RotateCcw,
+}
+
+impl GestureDirection {
+    pub fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Self::UpLeft | Self::UpRight | Self::DownLeft | Self::DownRight
+        )
+    }
+
+    fn is_valid_for(self, kind: GestureKind) -> bool {
+        match kind {
+            GestureKind::Swipe => {
+                matches!(self, Self::Left | Self::Right | Self::Up | Self::Down)
+                    || self.is_diagonal()
+            }
+            GestureKind::Pinch => {
+                matches!(
+                    self,
+                    Self::In | Self::Out | Self::RotateCw | Self::RotateCcw
+                )
+            }
+            GestureKind::Hold => false,
+        }
+    }
+}
+
+impl From<GestureKind> for niri_ipc::GestureKind {
+    fn from(value: GestureKind) -> Self {
+        match value {
+            GestureKind::Swipe => Self::Swipe,
+            GestureKind::Pinch => Self::Pinch,
+            GestureKind::Hold => Self::Hold,
+        }
+    }
+}
+
+impl From<niri_ipc::GestureKind> for GestureKind {
+    fn from(value: niri_ipc::GestureKind) -> Self {
+        match value {
+            niri_ipc::GestureKind::Swipe => Self::Swipe,
+            niri_ipc::GestureKind::Pinch => Self::Pinch,
+            niri_ipc::GestureKind::Hold => Self::Hold,
+        }
+    }
+}
+
+impl From<GestureDirection> for niri_ipc::GestureDirection {
+    fn from(value: GestureDirection) -> Self {
+        match value {
+            GestureDirection::Left => Self::Left,
+            GestureDirection::Right => Self::Right,
+            GestureDirection::Up => Self::Up,
+            GestureDirection::Down => Self::Down,
+            GestureDirection::UpLeft => Self::UpLeft,
+            GestureDirection::UpRight => Self::UpRight,
+            GestureDirection::DownLeft => Self::DownLeft,
+            GestureDirection::DownRight => Self::DownRight,
+            GestureDirection::In => Self::In,
+            GestureDirection::Out => Self::Out,
+            GestureDirection::RotateCw => Self::RotateCw,
+            GestureDirection::RotateCcw => Self::RotateCcw,
+        }
+    }
+}
+
+impl From<niri_ipc::GestureDirection> for GestureDirection {
+    fn from(value: niri_ipc::GestureDirection) -> Self {
+        match value {
+            niri_ipc::GestureDirection::Left => Self::Left,
+            niri_ipc::GestureDirection::Right => Self::Right,
+            niri_ipc::GestureDirection::Up => Self::Up,
+            niri_ipc::GestureDirection::Down => Self::Down,
+            niri_ipc::GestureDirection::UpLeft => Self::UpLeft,
+            niri_ipc::GestureDirection::UpRight => Self::UpRight,
+            niri_ipc::GestureDirection::DownLeft => Self::DownLeft,
+            niri_ipc::GestureDirection::DownRight => Self::DownRight,
+            niri_ipc::GestureDirection::In => Self::In,
+            niri_ipc::GestureDirection::Out => Self::Out,
+            niri_ipc::GestureDirection::RotateCw => Self::RotateCw,
+            niri_ipc::GestureDirection::RotateCcw => Self::RotateCcw,
+        }
+    }
+}
+
+impl From<GestureTrigger> for niri_ipc::GestureTrigger {
+    fn from(value: GestureTrigger) -> Self {
+        Self {
+            kind: value.kind.into(),
+            fingers: value.fingers,
+            direction: value.direction.map(Into::into),
+            fling: value.fling,
+        }
+    }
+}
+
+impl TryFrom<niri_ipc::GestureTrigger> for GestureTrigger {
+    type Error = miette::Error;
+
+    fn try_from(value: niri_ipc::GestureTrigger) -> Result<Self, Self::Error> {
+        let kind = GestureKind::from(value.kind);
+        check_fingers(kind, value.fingers)?;
+
+        let direction = value.direction.map(GestureDirection::from);
+        match direction {
+            Some(direction) if !direction.is_valid_for(kind) => {
+                return Err(miette!("invalid direction for a {kind:?} gesture"));
+            }
+            None if kind != GestureKind::Hold => {
+                return Err(miette!("{kind:?} gestures need a direction"));
+            }
+            _ => (),
+        }
+
+        if value.fling && kind != GestureKind::Swipe {
+            return Err(miette!("only swipes can be flung"));
+        }
+
+        Ok(Self {
+            kind,
+            fingers: value.fingers,
+            direction,
+            fling: value.fling,
+        })
+    }
+}
+
+impl GestureBinds {
+    pub fn find(
+        &self,
+        kind: GestureKind,
+        fingers: u8,
+        direction: Option<GestureDirection>,
+        fling: bool,
+    ) -> Option<&GestureBind> {
+        let trigger = GestureTrigger {
+            kind,
+            fingers,
+            direction,
+            fling,
+        };
+        self.0.iter().find(|bind| bind.trigger == trigger)
+    }
+}
+
+impl FromStr for GestureKind {
+    type Err = miette::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "swipe" => Ok(Self::Swipe),
+            "pinch" => Ok(Self::Pinch),
+            "hold" => Ok(Self::Hold),
+            _ => Err(miette!(
+                "invalid gesture kind, can be \"swipe\", \"pinch\" or \"hold\""
+            )),
+        }
+    }
+}
+
+impl FromStr for GestureDirection {
+    type Err = miette::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "up" => Ok(Self::Up),
+            "down" => Ok(Self::Down),
+            "up-left" => Ok(Self::UpLeft),
+            "up-right" => Ok(Self::UpRight),
+            "down-left" => Ok(Self::DownLeft),
+            "down-right" => Ok(Self::DownRight),
+            "in" => Ok(Self::In),
+            "out" => Ok(Self::Out),
+            "rotate-cw" => Ok(Self::RotateCw),
+            "rotate-ccw" => Ok(Self::RotateCcw),
+            _ => Err(miette!("invalid gesture direction: {s}")),
+        }
+    }
+}
+
+/// Parses a sequence step written as the gesture name followed by its direction, for example
+/// `swipe-3 down`.
+fn parse_gesture_step(s: &str) -> miette::Result<GestureTrigger> {
+    let (name, direction) = match s.split_once(' ') {
+        Some((name, direction)) => (name, Some(direction.trim())),
+        None => (s, None),
+    };
+
+    let (kind, fingers) = parse_gesture_name(name)?;
+    let direction = direction.map(GestureDirection::from_str).transpose()?;
+    match direction {
+        Some(direction) if !direction.is_valid_for(kind) => {
+            return Err(miette!("invalid direction for a {name} gesture"));
+        }
+        None if kind != GestureKind::Hold => {
+            return Err(miette!(
+                "expected a direction after the gesture, for example {name} {}",
+                if kind == GestureKind::Swipe {
+                    "down"
+                } else {
+                    "in"
+                }
+            ));
+        }
+        _ => (),
+    }
+
+    Ok(GestureTrigger {
+        kind,
+        fingers,
+        direction,
+        fling: false,
+    })
+}
+
+fn parse_gesture_name(s: &str) -> miette::Result<(GestureKind, u8)> {
+    let Some((kind, fingers)) = s.rsplit_once('-') else {
+        return Err(miette!(
+            "gesture must be written as kind-fingers, for example swipe-3"
+        ));
+    };
+
+    let kind = kind.parse()?;
+    let fingers = fingers
+        .parse()
+        .map_err(|_| miette!("invalid finger count: {fingers}"))?;
+    check_fingers(kind, fingers)?;
+
+    Ok((kind, fingers))
+}
+
+fn check_fingers(kind: GestureKind, fingers: u8) -> miette::Result<()> {
+    if kind == GestureKind::Swipe && fingers == 2 {
+        // libinput reports two-finger swipes as scrolling, and doesn't expose the touchpad finger
+        // positions, so there's no way to tell an edge swipe from a regular one.
+        return Err(miette!(
+            "two-finger swipes are scrolling; bind TouchpadScroll keys in the binds section instead"
+        ));
+    }
+    if kind == GestureKind::Swipe {
+        if !(3..=4).contains(&fingers) {
+            return Err(miette!("swipe finger count must be 3 or 4"));
+        }
+    } else if !(2..=4).contains(&fingers) {
+        // Unlike swipes, libinput recognizes two-finger pinches and holds.
+        return Err(miette!("finger count must be 2, 3 or 4"));
+    }
+
+    Ok(())
+}
+
+impl<S> knuffel::Decode<S> for GestureBinds
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        expect_only_children(node, ctx);
+
+        let mut seen = HashSet::new();
+
+        let mut binds = Vec::new();
+
+        for child in node.children() {
+            match GestureBind::decode_node(child, ctx) {
+                Ok(bind) => {
+                    if !seen.insert(bind.trigger) {
+                        ctx.emit_error(DecodeError::unexpected(
+                            &child.node_name,
+                            "gesture",
+                            "duplicate gesture bind",
+                        ));
+                        continue;
+                    }
+
+                    binds.push(bind);
+                }
+                Err(e) => {
+                    ctx.emit_error(e);
+                }
+            }
+        }
+
+        Ok(Self(binds))
+    }
+}
+
+impl<S> knuffel::Decode<S> for GestureBind
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        for val in node.arguments.iter() {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "no arguments expected for this node",
+            ));
+        }
+
+        let (kind, fingers) = parse_gesture_name(&node.node_name)
+            .map_err(|e| DecodeError::conversion(&node.node_name, e.wrap_err("invalid gesture")))?;
+
+        let mut direction = None;
+        let mut fling = false;
+        for (name, val) in &node.properties {
+            match &***name {
+                "direction" => {
+                    let value: GestureDirection = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+                    if !value.is_valid_for(kind) {
+                        ctx.emit_error(DecodeError::conversion(
+                            &val.literal,
+                            match kind {
+                                GestureKind::Swipe => {
+                                    "swipe direction must be left, right, up, down, \
+                                     up-left, up-right, down-left or down-right"
+                                }
+                                GestureKind::Pinch => {
+                                    "pinch direction must be in, out, rotate-cw or rotate-ccw"
+                                }
+                                GestureKind::Hold => "hold gestures have no direction",
+                            },
+                        ));
+                    }
+                    direction = Some(value);
+                }
+                "fling" => {
+                    fling = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+                    if kind != GestureKind::Swipe {
+                        ctx.emit_error(DecodeError::unexpected(
+                            name,
+                            "property",
+                            "only swipes can be flung",
+                        ));
+                    }
+                }
+                name_str => {
+                    ctx.emit_error(DecodeError::unexpected(
+                        name,
+                        "property",
+                        format!("unexpected property `{}`", name_str.escape_default()),
+                    ));
+                }
+            }
+        }
+
+        if direction.is_none() && kind != GestureKind::Hold {
+            ctx.emit_error(DecodeError::missing(
+                node,
+                "expected a direction for this gesture",
+            ));
+        }
+
+        let trigger = GestureTrigger {
+            kind,
+            fingers,
+            direction,
+            fling,
+        };
+
+        let mut children = node.children();
+
+        // If the action is invalid but the gesture is fine, we still want to return something.
+        // That way, the parent can handle the existence of duplicate gesture binds, even if their
+        // contents are not valid.
+        let dummy = Self {
+            trigger,
+            action: Action::Spawn(vec![]),
+        };
+
+        if let Some(child) = children.next() {
+            for unwanted_child in children {
+                ctx.emit_error(DecodeError::unexpected(
+                    unwanted_child,
+                    "node",
+                    "only one action is allowed per gesture bind",
+                ));
+            }
+            match Action::decode_node(child, ctx) {
+                Ok(action) => Ok(Self { trigger, action }),
+                Err(e) => {
+                    ctx.emit_error(e);
+                    Ok(dummy)
+                }
+            }
+        } else {
+            ctx.emit_error(DecodeError::missing(
+                node,
+                "expected an action for this gesture bind",
+            ));
+            Ok(dummy)
+        }
+    }
+}
+
+impl MouseGestures {
+    /// Returns the stroke bound to these segments.
+    pub fn find(&self, segments: &[GestureDirection]) -> Option<&MouseStroke> {
+        self.strokes
+            .iter()
+            .find(|stroke| stroke.segments == segments)
+    }
+}
+
+impl<S> knuffel::Decode<S> for MouseStroke
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        for name in node.properties.keys() {
+            ctx.emit_error(DecodeError::unexpected(
+                name,
+                "property",
+                format!("unexpected property `{}`", name.escape_default()),
+            ));
+        }
+
+        let mut segments: Vec<GestureDirection> = Vec::new();
+        for val in node.arguments.iter() {
+            let segment: GestureDirection = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+            if !matches!(
+                segment,
+                GestureDirection::Left
+                    | GestureDirection::Right
+                    | GestureDirection::Up
+                    | GestureDirection::Down
+            ) {
+                ctx.emit_error(DecodeError::conversion(
+                    &val.literal,
+                    "stroke direction must be left, right, up or down",
+                ));
+            } else if segments.last() == Some(&segment) {
+                ctx.emit_error(DecodeError::conversion(
+                    &val.literal,
+                    "stroke segments must change direction",
+                ));
+            }
+            segments.push(segment);
+        }
+
+        if !(1..=2).contains(&segments.len()) {
+            ctx.emit_error(DecodeError::missing(
+                node,
+                "a stroke needs one or two directions",
+            ));
+        }
+
+        let mut children = node.children();
+        let Some(child) = children.next() else {
+            return Err(DecodeError::missing(
+                node,
+                "expected an action for this stroke",
+            ));
+        };
+        for unwanted_child in children {
+            ctx.emit_error(DecodeError::unexpected(
+                unwanted_child,
+                "node",
+                "only one action is allowed per stroke",
+            ));
+        }
+        let action = Action::decode_node(child, ctx)?;
+
+        Ok(Self { segments, action })
+    }
+}
+
+impl<S> knuffel::Decode<S> for HotCorner
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        for val in node.arguments.iter() {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "no arguments expected for this node",
+            ));
+        }
+
+        for name in node.properties.keys() {
+            ctx.emit_error(DecodeError::unexpected(
+                name,
+                "property",
+                format!("unexpected property `{}`", name.escape_default()),
+            ));
+        }
+
+        let mut children = node.children();
+        let action = match children.next() {
+            Some(child) => Some(Action::decode_node(child, ctx)?),
+            None => None,
+        };
+        for unwanted_child in children {
+            ctx.emit_error(DecodeError::unexpected(
+                unwanted_child,
+                "node",
+                "only one action is allowed per hot corner",
+            ));
+        }
+
+        Ok(Self { action })
+    }
+}
+
+impl<S> knuffel::Decode<S> for EdgeSwipe
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        let mut tablet_mode = None;
+        for (name, val) in &node.properties {
+            match &***name {
+                "tablet-mode" => {
+                    tablet_mode = Some(knuffel::traits::DecodeScalar::decode(val, ctx)?);
+                }
+                name_str => {
+                    ctx.emit_error(DecodeError::unexpected(
+                        name,
+                        "property",
+                        format!("unexpected property `{}`", name_str.escape_default()),
+                    ));
+                }
+            }
+        }
+
+        let mut arguments = node.arguments.iter();
+        let Some(edge) = arguments.next() else {
+            return Err(DecodeError::missing(
+                node,
+                "expected the edge to swipe from",
+            ));
+        };
+        let edge = knuffel::traits::DecodeScalar::decode(edge, ctx)?;
+        for unwanted in arguments {
+            ctx.emit_error(DecodeError::unexpected(
+                &unwanted.literal,
+                "argument",
+                "only one edge is allowed per edge swipe",
+            ));
+        }
+
+        let mut children = node.children();
+        let Some(child) = children.next() else {
+            return Err(DecodeError::missing(
+                node,
+                "expected an action for this edge swipe",
+            ));
+        };
+        for unwanted_child in children {
+            ctx.emit_error(DecodeError::unexpected(
+                unwanted_child,
+                "node",
+                "only one action is allowed per edge swipe",
+            ));
+        }
+        let action = Action::decode_node(child, ctx)?;
+
+        Ok(Self {
+            edge,
+            tablet_mode,
+            action,
+        })
+    }
+}
+
+impl<S> knuffel::Decode<S> for GestureSequence
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        for name in node.properties.keys() {
+            ctx.emit_error(DecodeError::unexpected(
+                name,
+                "property",
+                format!("unexpected property `{}`", name.escape_default()),
+            ));
+        }
+
+        let mut steps = Vec::new();
+        for val in node.arguments.iter() {
+            let step: String = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+            match parse_gesture_step(&step) {
+                Ok(step) => steps.push(step),
+                Err(e) => ctx.emit_error(DecodeError::conversion(
+                    &val.literal,
+                    e.wrap_err("invalid gesture"),
+                )),
+            }
+        }
+
+        if node.arguments.len() < 2 {
+            ctx.emit_error(DecodeError::missing(
+                node,
+                "a sequence needs at least two gestures",
+            ));
+        }
+
+        let mut children = node.children();
+        let Some(child) = children.next() else {
+            return Err(DecodeError::missing(
+                node,
+                "expected an action for this gesture sequence",
+            ));
+        };
+        for unwanted_child in children {
+            ctx.emit_error(DecodeError::unexpected(
+                unwanted_child,
+                "node",
+                "only one action is allowed per gesture sequence",
+            ));
+        }
+        let action = Action::decode_node(child, ctx)?;
+
+        Ok(Self { steps, action })
+    }
+}
+
+/// Gesture binds that apply only when the focused window matches.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct GestureProfile {
+    #[knuffel(children(name = "match"))]
+    pub matches: Vec<GestureMatch>,
+    #[knuffel(children(name = "exclude"))]
+    pub excludes: Vec<GestureMatch>,
+    #[knuffel(child, default)]
+    pub binds: GestureBinds,
+}
+
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct GestureMatch {
+    #[knuffel(property, str)]
+    pub app_id: Option<RegexEq>,
+    #[knuffel(property, str)]
+    pub title: Option<RegexEq>,
+    #[knuffel(property)]
+    pub tablet_mode: Option<bool>,
+}
+
+/// What the gesture profiles match against.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GestureTarget<'a> {
+    /// App ID of the focused window.
+    pub app_id: Option<&'a str>,
+    /// Title of the focused window.
+    pub title: Option<&'a str>,
+    /// Whether the laptop is in tablet mode.
+    pub is_tablet_mode: bool,
+}
+
+impl GestureProfile {
+    pub fn matches(&self, target: GestureTarget) -> bool {
+        let matches = |m: &GestureMatch| m.matches(target);
+        (self.matches.is_empty() || self.matches.iter().any(matches))
+            && !self.excludes.iter().any(matches)
+    }
+}
+
+impl GestureMatch {
+    pub fn matches(&self, target: GestureTarget) -> bool {
+        if let Some(app_id_re) = &self.app_id {
+            if !target
+                .app_id
+                .is_some_and(|app_id| app_id_re.0.is_match(app_id))
+            {
+                return false;
+            }
+        }
+
+        if let Some(title_re) = &self.title {
+            if !target.title.is_some_and(|title| title_re.0.is_match(title)) {
+                return false;
+            }
+        }
+
+        if let Some(tablet_mode) = self.tablet_mode {
+            if target.is_tablet_mode != tablet_mode {
+                return false;
+            }
+        }
+
+        true
+    }
 }
@@ -1,10 +1,11 @@
 use std::str::FromStr;
 
+use knuffel::errors::DecodeError;
 use miette::miette;
 use smithay::input::keyboard::XkbConfig;
 use smithay::reexports::input;
 
-use crate::binds::Modifiers;
+use crate::binds::{Action, Modifiers};
 use crate::utils::{Flag, MergeWith, Percent};
 use crate::FloatOrInt;
 
@@ -17,10 +18,12 @@ pub struct Input {
     pub trackball: Trackball,
     pub tablet: Tablet,
     pub touch: Touch,
+    pub devices: Vec<InputDevice>,
     pub disable_power_key_handling: bool,
     pub warp_mouse_to_focus: Option<WarpMouseToFocus>,
     pub focus_follows_mouse: Option<FocusFollowsMouse>,
     pub workspace_auto_back_and_forth: bool,
+    pub focus_at_edge: FocusAtEdge,
     pub mod_key: Option<ModKey>,
     pub mod_key_nested: Option<ModKey>,
 }
@@ -41,6 +44,8 @@ pub struct InputPart {
     pub tablet: Option<Tablet>,
     #[knuffel(child)]
     pub touch: Option<Touch>,
+    #[knuffel(children(name = "device"))]
+    pub devices: Vec<InputDevice>,
     #[knuffel(child)]
     pub disable_power_key_handling: Option<Flag>,
     #[knuffel(child)]
@@ -49,6 +54,8 @@ pub struct InputPart {
     pub focus_follows_mouse: Option<FocusFollowsMouse>,
     #[knuffel(child)]
     pub workspace_auto_back_and_forth: Option<Flag>,
+    #[knuffel(child, unwrap(argument))]
+    pub focus_at_edge: Option<FocusAtEdge>,
     #[knuffel(child, unwrap(argument, str))]
     pub mod_key: Option<ModKey>,
     #[knuffel(child, unwrap(argument, str))]
@@ -72,6 +79,7 @@ impl MergeWith<InputPart> for Input {
             trackball,
             tablet,
             touch,
+            focus_at_edge,
         );
 
         merge_clone_opt!(
@@ -81,6 +89,8 @@ impl MergeWith<InputPart> for Input {
             mod_key,
             mod_key_nested,
         );
+
+        self.devices.extend(part.devices.iter().cloned());
     }
 }
 
@@ -91,6 +101,7 @@ pub struct Keyboard {
     pub repeat_rate: u8,
     pub track_layout: TrackLayout,
     pub numlock: bool,
+    pub accessibility: KeyboardAccessibility,
 }
 
 impl Default for Keyboard {
@@ -102,6 +113,7 @@ impl Default for Keyboard {
             repeat_rate: 25,
             track_layout: Default::default(),
             numlock: Default::default(),
+            accessibility: Default::default(),
         }
     }
 }
@@ -118,11 +130,20 @@ pub struct KeyboardPart {
     pub track_layout: Option<TrackLayout>,
     #[knuffel(child)]
     pub numlock: Option<Flag>,
+    #[knuffel(child)]
+    pub accessibility: Option<KeyboardAccessibility>,
 }
 
 impl MergeWith<KeyboardPart> for Keyboard {
     fn merge_with(&mut self, part: &KeyboardPart) {
-        merge_clone!((self, part), xkb, repeat_delay, repeat_rate, track_layout);
+        merge_clone!(
+            (self, part),
+            xkb,
+            repeat_delay,
+            repeat_rate,
+            track_layout,
+            accessibility,
+        );
         merge!((self, part), numlock);
     }
 }
@@ -155,6 +176,27 @@ impl Xkb {
     }
 }
 
+#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KeyboardAccessibility {
+    /// Tapping a modifier applies it to the next key, instead of holding it down.
+    #[knuffel(child)]
+    pub sticky_keys: bool,
+    /// Keys must be held down this long before they count as pressed.
+    #[knuffel(child, unwrap(argument))]
+    pub slow_keys_ms: Option<u16>,
+}
+
+#[derive(knuffel::DecodeScalar, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FocusAtEdge {
+    /// Directional focus stops at the edge of the workspace.
+    #[default]
+    Stop,
+    /// Directional focus wraps around to the other edge of the workspace.
+    Wrap,
+    /// Directional focus continues onto the adjacent monitor.
+    Monitor,
+}
+
 #[derive(knuffel::DecodeScalar, Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum TrackLayout {
     /// The layout change is global.
@@ -291,12 +333,68 @@ pub struct Trackball {
     pub middle_emulation: bool,
 }
 
+/// Libinput settings for the input device with a matching name.
+///
+/// Settings left unset keep the values from the device type section, such as `touchpad`.
+#[derive(knuffel::Decode, Debug, Clone, PartialEq)]
+pub struct InputDevice {
+    #[knuffel(argument)]
+    pub name: String,
+    #[knuffel(child, unwrap(argument))]
+    pub tap: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub natural_scroll: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub accel_speed: Option<FloatOrInt<-1, 1>>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub accel_profile: Option<AccelProfile>,
+    #[knuffel(child, unwrap(argument))]
+    pub dwt: Option<bool>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub click_method: Option<ClickMethod>,
+    #[knuffel(children(name = "remap-button"))]
+    pub remap_buttons: Vec<ButtonRemap>,
+}
+
+/// Remaps a mouse button of the device to another button or to an action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ButtonRemap {
+    /// Evdev code of the remapped button.
+    pub button: u32,
+    pub target: ButtonRemapTarget,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ButtonRemapTarget {
+    /// Evdev code of the button to send instead.
+    Button(u32),
+    Action(Action),
+}
+
+/// Mouse button given by name or by evdev code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ButtonCode(u32);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClickMethod {
     Clickfinger,
     ButtonAreas,
 }
 
+impl Input {
+    /// Returns how to remap the button of the named device, if at all.
+    pub fn button_remap(&self, device: &str, button: u32) -> Option<&ButtonRemapTarget> {
+        // Later device sections take precedence, like with the libinput settings.
+        self.devices
+            .iter()
+            .rev()
+            .filter(|config| config.name == device)
+            .flat_map(|config| &config.remap_buttons)
+            .find(|remap| remap.button == button)
+            .map(|remap| &remap.target)
+    }
+}
+
 impl From<ClickMethod> for input::ClickMethod {
     fn from(value: ClickMethod) -> Self {
         match value {
@@ -364,9 +462,51 @@ pub struct Tablet {
     #[knuffel(child, unwrap(argument))]
     pub map_to_output: Option<String>,
     #[knuffel(child)]
+    pub map_to_region: Option<TabletRegion>,
+    #[knuffel(child)]
     pub left_handed: bool,
+    #[knuffel(child, default)]
+    pub pad: TabletPad,
 }
 
+/// Part of the mapped output to map the tablet to, in logical coordinates within the output.
+#[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TabletRegion {
+    #[knuffel(property, default)]
+    pub x: i32,
+    #[knuffel(property, default)]
+    pub y: i32,
+    #[knuffel(property)]
+    pub width: i32,
+    #[knuffel(property)]
+    pub height: i32,
+}
+
+/// Actions for the buttons, rings and strips of the tablet pads.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
+pub struct TabletPad {
+    #[knuffel(children(name = "button"))]
+    pub buttons: Vec<PadButton>,
+    #[knuffel(child)]
+    pub ring_cw: Option<PadAction>,
+    #[knuffel(child)]
+    pub ring_ccw: Option<PadAction>,
+    #[knuffel(child)]
+    pub strip_up: Option<PadAction>,
+    #[knuffel(child)]
+    pub strip_down: Option<PadAction>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PadButton {
+    /// Button number on the pad, starting from 0.
+    pub button: u32,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PadAction(pub Action);
+
 #[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
 pub struct Touch {
     #[knuffel(child)]
@@ -506,6 +646,220 @@ impl FromStr for TapButtonMap {
     }
 }
 
+impl<S> knuffel::Decode<S> for PadButton
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        let mut arguments = node.arguments.iter();
+        let Some(button) = arguments.next() else {
+            return Err(DecodeError::missing(node, "pad button number is required"));
+        };
+        let button = knuffel::traits::DecodeScalar::decode(button, ctx)?;
+
+        for val in arguments {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "only one pad button number is expected",
+            ));
+        }
+
+        let PadAction(action) = PadAction::decode_node(node, ctx)?;
+        Ok(Self { button, action })
+    }
+}
+
+impl<S> knuffel::Decode<S> for PadAction
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        for name in node.properties.keys() {
+            ctx.emit_error(DecodeError::unexpected(
+                name,
+                "property",
+                "no properties expected for this node",
+            ));
+        }
+
+        // Pad buttons take the button number as an argument and check the arguments themselves.
+        if &**node.node_name != "button" {
+            for val in node.arguments.iter() {
+                ctx.emit_error(DecodeError::unexpected(
+                    &val.literal,
+                    "argument",
+                    "no arguments expected for this node",
+                ));
+            }
+        }
+
+        let mut children = node.children();
+        let Some(child) = children.next() else {
+            return Err(DecodeError::missing(node, "expected an action"));
+        };
+        for unwanted_child in children {
+            ctx.emit_error(DecodeError::unexpected(
+                unwanted_child,
+                "node",
+                "only one action is allowed",
+            ));
+        }
+
+        Ok(Self(Action::decode_node(child, ctx)?))
+    }
+}
+
+impl<S> knuffel::Decode<S> for ButtonRemap
+where
+    S: knuffel::traits::ErrorSpan,
+{
+    fn decode_node(
+        node: &knuffel::ast::SpannedNode<S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<Self, DecodeError<S>> {
+        if let Some(type_name) = &node.type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+
+        for name in node.properties.keys() {
+            ctx.emit_error(DecodeError::unexpected(
+                name,
+                "property",
+                "no properties expected for this node",
+            ));
+        }
+
+        let mut arguments = node.arguments.iter();
+        let Some(button) = arguments.next() else {
+            return Err(DecodeError::missing(node, "button to remap is required"));
+        };
+        let ButtonCode(button) = knuffel::traits::DecodeScalar::decode(button, ctx)?;
+
+        let target_button = arguments.next();
+        for val in arguments {
+            ctx.emit_error(DecodeError::unexpected(
+                &val.literal,
+                "argument",
+                "only the button and the button to remap it to are expected",
+            ));
+        }
+
+        let mut children = node.children();
+        let action = children.next();
+        for unwanted_child in children {
+            ctx.emit_error(DecodeError::unexpected(
+                unwanted_child,
+                "node",
+                "only one action is allowed per remapped button",
+            ));
+        }
+
+        let target = match (target_button, action) {
+            (Some(val), None) => {
+                let ButtonCode(code) = knuffel::traits::DecodeScalar::decode(val, ctx)?;
+                ButtonRemapTarget::Button(code)
+            }
+            (None, Some(child)) => ButtonRemapTarget::Action(Action::decode_node(child, ctx)?),
+            (Some(val), Some(_)) => {
+                return Err(DecodeError::unexpected(
+                    &val.literal,
+                    "argument",
+                    "remap the button either to another button or to an action, not both",
+                ));
+            }
+            (None, None) => {
+                return Err(DecodeError::missing(
+                    node,
+                    "expected a button or an action to remap the button to",
+                ));
+            }
+        };
+
+        Ok(Self { button, target })
+    }
+}
+
+impl<S: knuffel::traits::ErrorSpan> knuffel::DecodeScalar<S> for ButtonCode {
+    fn type_check(
+        type_name: &Option<knuffel::span::Spanned<knuffel::ast::TypeName, S>>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) {
+        if let Some(type_name) = &type_name {
+            ctx.emit_error(DecodeError::unexpected(
+                type_name,
+                "type name",
+                "no type name expected for this node",
+            ));
+        }
+    }
+
+    fn raw_decode(
+        val: &knuffel::span::Spanned<knuffel::ast::Literal, S>,
+        ctx: &mut knuffel::decode::Context<S>,
+    ) -> Result<ButtonCode, DecodeError<S>> {
+        match &**val {
+            knuffel::ast::Literal::String(ref s) => match s.parse() {
+                Ok(button) => Ok(button),
+                Err(e) => {
+                    ctx.emit_error(DecodeError::conversion(val, e));
+                    Ok(ButtonCode(0))
+                }
+            },
+            knuffel::ast::Literal::Int(ref value) => match value.try_into() {
+                Ok(code) => Ok(ButtonCode(code)),
+                Err(e) => {
+                    ctx.emit_error(DecodeError::conversion(val, e));
+                    Ok(ButtonCode(0))
+                }
+            },
+            _ => {
+                ctx.emit_error(DecodeError::unsupported(
+                    val,
+                    "Unsupported value, only button codes and names are recognized",
+                ));
+                Ok(ButtonCode(0))
+            }
+        }
+    }
+}
+
+impl FromStr for ButtonCode {
+    type Err = miette::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Codes from linux/input-event-codes.h.
+        match s {
+            "left" => Ok(Self(0x110)),
+            "right" => Ok(Self(0x111)),
+            "middle" => Ok(Self(0x112)),
+            "back" => Ok(Self(0x113)),
+            "forward" => Ok(Self(0x114)),
+            _ => Err(miette!(
+                r#"invalid button, can be "left", "right", "middle", "back", "forward" or a button code"#
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_debug_snapshot;
@@ -20,8 +20,14 @@ pub struct Layout {
     pub center_focused_column: CenterFocusedColumn,
     pub always_center_single_column: bool,
     pub empty_workspace_above_first: bool,
+    /// Number of columns to fit on screen by rebalancing widths when windows open and close.
+    pub auto_balance_columns: Option<u8>,
+    /// Whether to drop gaps, borders and focus rings while a single column is on the workspace.
+    pub smart_gaps: bool,
+    pub new_window_placement: NewWindowPlacement,
     pub default_column_display: ColumnDisplay,
     pub gaps: f64,
+    pub outer_gaps: OuterGaps,
     pub struts: Struts,
     pub background_color: Color,
 }
@@ -43,8 +49,12 @@ impl Default for Layout {
             center_focused_column: CenterFocusedColumn::Never,
             always_center_single_column: false,
             empty_workspace_above_first: false,
+            auto_balance_columns: None,
+            smart_gaps: false,
+            new_window_placement: NewWindowPlacement::RightOfFocused,
             default_column_display: ColumnDisplay::Normal,
             gaps: 16.,
+            outer_gaps: OuterGaps::default(),
             struts: Struts::default(),
             preset_window_heights: vec![
                 PresetSize::Proportion(1. / 3.),
@@ -67,6 +77,7 @@ impl MergeWith<LayoutPart> for Layout {
             insert_hint,
             always_center_single_column,
             empty_workspace_above_first,
+            smart_gaps,
             gaps,
         );
 
@@ -75,10 +86,13 @@ impl MergeWith<LayoutPart> for Layout {
             preset_column_widths,
             preset_window_heights,
             center_focused_column,
+            new_window_placement,
             default_column_display,
+            outer_gaps,
             struts,
             background_color,
         );
+        merge_clone_opt!((self, part), auto_balance_columns);
 
         if let Some(x) = part.default_column_width {
             self.default_column_width = x.0;
@@ -118,11 +132,19 @@ pub struct LayoutPart {
     pub always_center_single_column: Option<Flag>,
     #[knuffel(child)]
     pub empty_workspace_above_first: Option<Flag>,
+    #[knuffel(child, unwrap(argument))]
+    pub auto_balance_columns: Option<u8>,
+    #[knuffel(child)]
+    pub smart_gaps: Option<Flag>,
+    #[knuffel(child, unwrap(argument))]
+    pub new_window_placement: Option<NewWindowPlacement>,
     #[knuffel(child, unwrap(argument, str))]
     pub default_column_display: Option<ColumnDisplay>,
     #[knuffel(child, unwrap(argument))]
     pub gaps: Option<FloatOrInt<0, 65535>>,
     #[knuffel(child)]
+    pub outer_gaps: Option<OuterGaps>,
+    #[knuffel(child)]
     pub struts: Option<Struts>,
     #[knuffel(child)]
     pub background_color: Option<Color>,
@@ -146,6 +168,21 @@ impl From<PresetSize> for SizeChange {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DefaultPresetSize(pub Option<PresetSize>);
 
+/// Gaps between the windows and the edges of the working area.
+///
+/// Unset edges use the regular gaps.
+#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
+pub struct OuterGaps {
+    #[knuffel(child, unwrap(argument))]
+    pub left: Option<FloatOrInt<0, 65535>>,
+    #[knuffel(child, unwrap(argument))]
+    pub right: Option<FloatOrInt<0, 65535>>,
+    #[knuffel(child, unwrap(argument))]
+    pub top: Option<FloatOrInt<0, 65535>>,
+    #[knuffel(child, unwrap(argument))]
+    pub bottom: Option<FloatOrInt<0, 65535>>,
+}
+
 #[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq)]
 pub struct Struts {
     #[knuffel(child, unwrap(argument), default)]
@@ -170,6 +207,19 @@ pub enum CenterFocusedColumn {
     OnOverflow,
 }
 
+#[derive(knuffel::DecodeScalar, Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum NewWindowPlacement {
+    /// New windows open in a new column to the right of the focused column.
+    #[default]
+    RightOfFocused,
+    /// New windows open in a new column to the left of the focused column.
+    LeftOfFocused,
+    /// New windows open in a new column at the end of the workspace.
+    End,
+    /// New windows open at the bottom of the focused column.
+    FocusedColumn,
+}
+
 impl<S> knuffel::Decode<S> for DefaultPresetSize
 where
     S: knuffel::traits::ErrorSpan,
@@ -49,7 +49,7 @@ pub use crate::appearance::*;
 pub use crate::binds::*;
 pub use crate::debug::Debug;
 pub use crate::error::{ConfigIncludeError, ConfigParseResult};
-pub use crate::gestures::Gestures;
+pub use crate::gestures::{GestureDirection, GestureKind, Gestures};
 pub use crate::input::{Input, ModKey, ScrollMethod, TrackLayout, WarpMouseToFocusMode, Xkb};
 pub use crate::layer_rule::LayerRule;
 pub use crate::layout::*;
@@ -80,11 +80,15 @@ pub struct Config {
     pub animations: Animations,
     pub gestures: Gestures,
     pub overview: Overview,
+    pub resize_mode: ResizeMode,
+    pub hooks: Hooks,
     pub environment: Environment,
     pub xwayland_satellite: XwaylandSatellite,
     pub window_rules: Vec<WindowRule>,
     pub layer_rules: Vec<LayerRule>,
     pub binds: Binds,
+    pub bind_modes: BindModes,
+    pub region_binds: RegionBinds,
     pub switch_events: SwitchBinds,
     pub debug: Debug,
     pub workspaces: Vec<Workspace>,
@@ -196,6 +200,8 @@ where
                 "animations" => m_merge!(animations),
                 "gestures" => m_merge!(gestures),
                 "overview" => m_merge!(overview),
+                "resize-mode" => m_merge!(resize_mode),
+                "hooks" => m_merge!(hooks),
                 "xwayland-satellite" => m_merge!(xwayland_satellite),
                 "switch-events" => m_merge!(switch_events),
                 "debug" => m_merge!(debug),
@@ -221,9 +227,37 @@ where
                     let mut config = config.borrow_mut();
                     let binds = &mut config.binds.0;
                     // Remove existing binds matching any new bind.
-                    binds.retain(|bind| !part.0.iter().any(|new| new.key == bind.key));
+                    binds.retain(|bind| !part.0.iter().any(|new| new.same_trigger(bind)));
                     // Add all new binds.
                     binds.extend(part.0);
+
+                    for node in node.children().filter(|child| &**child.node_name == "mode") {
+                        let part = BindMode::decode_node(node, ctx)?;
+
+                        // Merge modes with the same name the same way as the regular binds.
+                        let modes = &mut config.bind_modes.0;
+                        if let Some(mode) = modes.iter_mut().find(|mode| mode.name == part.name) {
+                            let binds = &mut mode.binds;
+                            binds.retain(|bind| {
+                                !part.binds.iter().any(|new| new.same_trigger(bind))
+                            });
+                            binds.extend(part.binds);
+                        } else {
+                            modes.push(part);
+                        }
+                    }
+
+                    for node in node.children() {
+                        let binds = match &**node.node_name {
+                            "over-background" => &mut config.region_binds.background,
+                            "over-border" => &mut config.region_binds.border,
+                            _ => continue,
+                        };
+
+                        let part = binds::decode_region_binds(node, ctx);
+                        binds.retain(|bind| !part.iter().any(|new| new.key == bind.key));
+                        binds.extend(part);
+                    }
                 }
                 "environment" => {
                     let part = Environment::decode_node(node, ctx)?;
@@ -650,6 +684,10 @@ mod tests {
                         layout "us,ru"
                         options "grp:win_space_toggle"
                     }
+                    accessibility {
+                        sticky-keys
+                        slow-keys-ms 300
+                    }
                 }
 
                 touchpad {
@@ -701,19 +739,34 @@ mod tests {
 
                 tablet {
                     map-to-output "eDP-1"
+                    map-to-region x=100 width=1280 height=800
                     calibration-matrix 1.0 2.0 3.0 \
                                        4.0 5.0 6.0
+
+                    pad {
+                        button 0 { toggle-overview; }
+                        ring-cw { focus-column-right; }
+                    }
                 }
 
                 touch {
                     map-to-output "eDP-1"
                 }
 
+                device "Logitech MX Master 3" {
+                    natural-scroll false
+                    accel-speed 0.3
+                    accel-profile "adaptive"
+                    remap-button 275 "middle"
+                    remap-button "forward" { toggle-overview; }
+                }
+
                 disable-power-key-handling
 
                 warp-mouse-to-focus
                 focus-follows-mouse
                 workspace-auto-back-and-forth
+                focus-at-edge "monitor"
 
                 mod-key "Mod5"
                 mod-key-nested "Super"
@@ -730,9 +783,11 @@ mod tests {
                 hot-corners {
                     off
                     top-left
-                    top-right
+                    top-right { spawn "swaylock"; }
                     bottom-left
                     bottom-right
+                    dwell-ms 150
+                    in-fullscreen
                 }
             }
 
@@ -784,6 +839,11 @@ mod tests {
 
                 gaps 8
 
+                outer-gaps {
+                    top 4
+                    bottom 0
+                }
+
                 struts {
                     left 1
                     right 2
@@ -791,6 +851,9 @@ mod tests {
                 }
 
                 center-focused-column "on-overflow"
+                auto-balance-columns 3
+                smart-gaps
+                new-window-placement "left-of-focused"
 
                 default-column-display "tabbed"
 
@@ -822,6 +885,16 @@ mod tests {
                 skip-at-startup
             }
 
+            resize-mode {
+                width-step "5%"
+                height-step "50"
+            }
+
+            hooks {
+                on-window-open "notify-send" "Window opened"
+                on-output-connect "sh" "-c" "echo $NIRI_OUTPUT"
+            }
+
             animations {
                 slowdown 2.0
 
@@ -850,6 +923,51 @@ mod tests {
                     trigger-width 10
                     max-speed 50
                 }
+
+                thresholds {
+                    swipe-distance 40
+                    pinch-in-scale 0.8
+                    swipe-hysteresis 1.5
+                }
+
+                hold-drag-move 3
+                overview-pinch 4
+                maximize-swipe
+                resize-pinch 2
+                magnifier-pinch 3
+                progress-indicator
+                three-finger-swipe horizontal="client"
+                background-drag
+                border-resize
+                cancel-key "Ctrl+Escape"
+
+                binds {
+                    swipe-3 direction="left" { focus-column-right; }
+                    hold-4 { close-window; }
+                }
+
+                profile {
+                    match app-id="^firefox$" tablet-mode=false
+                    exclude title="Private Browsing$"
+
+                    binds {
+                        swipe-4 direction="left" { focus-column-left; }
+                    }
+                }
+
+                sequence "swipe-3 down" "hold-3" { toggle-overview; }
+
+                device "Apple Inc. Magic Trackpad" {
+                    disable-pinch
+                }
+
+                mouse {
+                    button "right"
+                    stroke "left" { focus-column-left; }
+                    stroke "down" "right" { close-window; }
+                }
+
+                edge-swipe "bottom" tablet-mode=true { spawn "wvkbd-mobintl"; }
             }
 
             environment {
@@ -867,9 +985,16 @@ mod tests {
                 open-fullscreen false
                 open-floating false
                 open-focused true
+                new-window-placement "end"
                 default-window-height { fixed 500; }
                 default-column-display "tabbed"
                 default-floating-position x=100 y=-200 relative-to="bottom-left"
+                allow-keyboard-shortcuts-inhibit false
+                disable-pointer-gestures true
+                allow-swallowing true
+                always-on-top true
+                exclude-from-gesture-actions true
+                tags "chat" "work"
 
                 focus-ring {
                     off
@@ -907,6 +1032,16 @@ mod tests {
                 Mod+Shift+E allow-inhibiting=false { quit skip-confirmation=true; }
                 Mod+WheelScrollDown cooldown-ms=150 { focus-workspace-down; }
                 Super+Alt+S allow-when-locked=true { spawn-sh "pkill orca || exec orca"; }
+                Mod double-press-ms=300 { show-hotkey-overlay; }
+
+                mode "resize" {
+                    Mod+H { focus-column-left; }
+                    Mod+Return { switch-bind-mode "default"; }
+                }
+
+                over-background {
+                    WheelScrollDown { focus-column-right; }
+                }
             }
 
             switch-events {
@@ -965,6 +1100,12 @@ mod tests {
                     repeat_rate: 25,
                     track_layout: Window,
                     numlock: false,
+                    accessibility: KeyboardAccessibility {
+                        sticky_keys: true,
+                        slow_keys_ms: Some(
+                            300,
+                        ),
+                    },
                 },
                 touchpad: Touchpad {
                     off: false,
@@ -1093,7 +1234,31 @@ mod tests {
                     map_to_output: Some(
                         "eDP-1",
                     ),
+                    map_to_region: Some(
+                        TabletRegion {
+                            x: 100,
+                            y: 0,
+                            width: 1280,
+                            height: 800,
+                        },
+                    ),
                     left_handed: false,
+                    pad: TabletPad {
+                        buttons: [
+                            PadButton {
+                                button: 0,
+                                action: ToggleOverview,
+                            },
+                        ],
+                        ring_cw: Some(
+                            PadAction(
+                                FocusColumnRight,
+                            ),
+                        ),
+                        ring_ccw: None,
+                        strip_up: None,
+                        strip_down: None,
+                    },
                 },
                 touch: Touch {
                     off: false,
@@ -1102,6 +1267,39 @@ mod tests {
                         "eDP-1",
                     ),
                 },
+                devices: [
+                    InputDevice {
+                        name: "Logitech MX Master 3",
+                        tap: None,
+                        natural_scroll: Some(
+                            false,
+                        ),
+                        accel_speed: Some(
+                            FloatOrInt(
+                                0.3,
+                            ),
+                        ),
+                        accel_profile: Some(
+                            Adaptive,
+                        ),
+                        dwt: None,
+                        click_method: None,
+                        remap_buttons: [
+                            ButtonRemap {
+                                button: 275,
+                                target: Button(
+                                    274,
+                                ),
+                            },
+                            ButtonRemap {
+                                button: 276,
+                                target: Action(
+                                    ToggleOverview,
+                                ),
+                            },
+                        ],
+                    },
+                ],
                 disable_power_key_handling: true,
                 warp_mouse_to_focus: Some(
                     WarpMouseToFocus {
@@ -1114,6 +1312,7 @@ mod tests {
                     },
                 ),
                 workspace_auto_back_and_forth: true,
+                focus_at_edge: Monitor,
                 mod_key: Some(
                     IsoLevel3Shift,
                 ),
@@ -1169,10 +1368,36 @@ mod tests {
                         hot_corners: Some(
                             HotCorners {
                                 off: true,
-                                top_left: true,
-                                top_right: true,
-                                bottom_left: true,
-                                bottom_right: true,
+                                top_left: Some(
+                                    HotCorner {
+                                        action: None,
+                                    },
+                                ),
+                                top_right: Some(
+                                    HotCorner {
+                                        action: Some(
+                                            Spawn(
+                                                [
+                                                    "swaylock",
+                                                ],
+                                            ),
+                                        ),
+                                    },
+                                ),
+                                bottom_left: Some(
+                                    HotCorner {
+                                        action: None,
+                                    },
+                                ),
+                                bottom_right: Some(
+                                    HotCorner {
+                                        action: None,
+                                    },
+                                ),
+                                dwell_ms: 150,
+                                retrigger_delay_ms: 0,
+                                pressure: 0,
+                                in_fullscreen: true,
                             },
                         ),
                         layout: None,
@@ -1429,8 +1654,27 @@ mod tests {
                 center_focused_column: OnOverflow,
                 always_center_single_column: false,
                 empty_workspace_above_first: false,
+                auto_balance_columns: Some(
+                    3,
+                ),
+                smart_gaps: true,
+                new_window_placement: LeftOfFocused,
                 default_column_display: Tabbed,
                 gaps: 8.0,
+                outer_gaps: OuterGaps {
+                    left: None,
+                    right: None,
+                    top: Some(
+                        FloatOrInt(
+                            4.0,
+                        ),
+                    ),
+                    bottom: Some(
+                        FloatOrInt(
+                            0.0,
+                        ),
+                    ),
+                },
                 struts: Struts {
                     left: FloatOrInt(
                         1.0,
@@ -1629,10 +1873,192 @@ mod tests {
                 },
                 hot_corners: HotCorners {
                     off: false,
-                    top_left: false,
-                    top_right: false,
-                    bottom_left: false,
-                    bottom_right: false,
+                    top_left: None,
+                    top_right: None,
+                    bottom_left: None,
+                    bottom_right: None,
+                    dwell_ms: 0,
+                    retrigger_delay_ms: 0,
+                    pressure: 0,
+                    in_fullscreen: false,
+                },
+                thresholds: GestureThresholds {
+                    swipe_distance: 40.0,
+                    pinch_in_scale: 0.8,
+                    pinch_out_scale: 1.33,
+                    rotation_angle: 30.0,
+                    diagonal_angle: 15.0,
+                    fling_velocity: 1000.0,
+                    swipe_hysteresis: 1.5,
+                    reference_width_mm: 0.0,
+                    hold_drag_delay_ms: 300,
+                    two_finger_hold_delay_ms: 500,
+                    sequence_timeout_ms: 500,
+                },
+                binds: GestureBinds(
+                    [
+                        GestureBind {
+                            trigger: GestureTrigger {
+                                kind: Swipe,
+                                fingers: 3,
+                                direction: Some(
+                                    Left,
+                                ),
+                                fling: false,
+                            },
+                            action: FocusColumnRight,
+                        },
+                        GestureBind {
+                            trigger: GestureTrigger {
+                                kind: Hold,
+                                fingers: 4,
+                                direction: None,
+                                fling: false,
+                            },
+                            action: CloseWindow,
+                        },
+                    ],
+                ),
+                profiles: [
+                    GestureProfile {
+                        matches: [
+                            GestureMatch {
+                                app_id: Some(
+                                    RegexEq(
+                                        Regex(
+                                            "^firefox$",
+                                        ),
+                                    ),
+                                ),
+                                title: None,
+                                tablet_mode: Some(
+                                    false,
+                                ),
+                            },
+                        ],
+                        excludes: [
+                            GestureMatch {
+                                app_id: None,
+                                title: Some(
+                                    RegexEq(
+                                        Regex(
+                                            "Private Browsing$",
+                                        ),
+                                    ),
+                                ),
+                                tablet_mode: None,
+                            },
+                        ],
+                        binds: GestureBinds(
+                            [
+                                GestureBind {
+                                    trigger: GestureTrigger {
+                                        kind: Swipe,
+                                        fingers: 4,
+                                        direction: Some(
+                                            Left,
+                                        ),
+                                        fling: false,
+                                    },
+                                    action: FocusColumnLeft,
+                                },
+                            ],
+                        ),
+                    },
+                ],
+                sequences: [
+                    GestureSequence {
+                        steps: [
+                            GestureTrigger {
+                                kind: Swipe,
+                                fingers: 3,
+                                direction: Some(
+                                    Down,
+                                ),
+                                fling: false,
+                            },
+                            GestureTrigger {
+                                kind: Hold,
+                                fingers: 3,
+                                direction: None,
+                                fling: false,
+                            },
+                        ],
+                        action: ToggleOverview,
+                    },
+                ],
+                devices: [
+                    GestureDevice {
+                        name: "Apple Inc. Magic Trackpad",
+                        off: false,
+                        disable_swipe: false,
+                        disable_pinch: true,
+                        disable_hold: false,
+                        thresholds: None,
+                    },
+                ],
+                mouse: MouseGestures {
+                    button: Some(
+                        Right,
+                    ),
+                    strokes: [
+                        MouseStroke {
+                            segments: [
+                                Left,
+                            ],
+                            action: FocusColumnLeft,
+                        },
+                        MouseStroke {
+                            segments: [
+                                Down,
+                                Right,
+                            ],
+                            action: CloseWindow,
+                        },
+                    ],
+                },
+                edge_swipes: [
+                    EdgeSwipe {
+                        edge: Bottom,
+                        tablet_mode: Some(
+                            true,
+                        ),
+                        action: Spawn(
+                            [
+                                "wvkbd-mobintl",
+                            ],
+                        ),
+                    },
+                ],
+                hold_drag_move: Some(
+                    3,
+                ),
+                overview_pinch: Some(
+                    4,
+                ),
+                maximize_swipe: true,
+                resize_pinch: Some(
+                    2,
+                ),
+                magnifier_pinch: Some(
+                    3,
+                ),
+                progress_indicator: true,
+                three_finger_swipe: ThreeFingerSwipe {
+                    horizontal: Some(
+                        Client,
+                    ),
+                    vertical: None,
+                },
+                background_drag: true,
+                border_resize: true,
+                cancel_key: Key {
+                    trigger: Keysym(
+                        XK_Escape,
+                    ),
+                    modifiers: Modifiers(
+                        CTRL,
+                    ),
                 },
             },
             overview: Overview {
@@ -1663,6 +2089,36 @@ mod tests {
                     },
                 },
             },
+            resize_mode: ResizeMode {
+                width_step: SetProportion(
+                    5.0,
+                ),
+                height_step: SetFixed(
+                    50,
+                ),
+            },
+            hooks: Hooks {
+                on_window_open: Some(
+                    Hook {
+                        command: [
+                            "notify-send",
+                            "Window opened",
+                        ],
+                    },
+                ),
+                on_window_close: None,
+                on_workspace_focus: None,
+                on_output_connect: Some(
+                    Hook {
+                        command: [
+                            "sh",
+                            "-c",
+                            "echo $NIRI_OUTPUT",
+                        ],
+                    },
+                ),
+                on_output_disconnect: None,
+            },
             environment: Environment(
                 [
                     EnvironmentVariable {
@@ -1700,6 +2156,7 @@ mod tests {
                             is_window_cast_target: None,
                             is_urgent: None,
                             at_startup: None,
+                            profile: None,
                         },
                     ],
                     excludes: [
@@ -1719,6 +2176,7 @@ mod tests {
                             is_window_cast_target: None,
                             is_urgent: None,
                             at_startup: None,
+                            profile: None,
                         },
                         Match {
                             app_id: None,
@@ -1734,6 +2192,7 @@ mod tests {
                             is_window_cast_target: None,
                             is_urgent: None,
                             at_startup: None,
+                            profile: None,
                         },
                     ],
                     default_column_width: None,
@@ -1763,6 +2222,9 @@ mod tests {
                     open_focused: Some(
                         true,
                     ),
+                    new_window_placement: Some(
+                        End,
+                    ),
                     min_width: None,
                     min_height: None,
                     max_width: None,
@@ -1845,6 +2307,32 @@ mod tests {
                     ),
                     scroll_factor: None,
                     tiled_state: None,
+                    forward_pinch_gestures: None,
+                    allow_keyboard_shortcuts_inhibit: Some(
+                        false,
+                    ),
+                    disable_pointer_gestures: Some(
+                        true,
+                    ),
+                    allow_swallowing: Some(
+                        true,
+                    ),
+                    always_on_top: Some(
+                        true,
+                    ),
+                    exclude_from_gesture_actions: Some(
+                        true,
+                    ),
+                    tags: Some(
+                        [
+                            "chat",
+                            "work",
+                        ],
+                    ),
+                    three_finger_swipe: ThreeFingerSwipe {
+                        horizontal: None,
+                        vertical: None,
+                    },
                 },
             ],
             layer_rules: [
@@ -1895,6 +2383,7 @@ mod tests {
                         action: ToggleKeyboardShortcutsInhibit,
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: false,
                         hotkey_overlay_title: Some(
@@ -1915,6 +2404,7 @@ mod tests {
                         action: ToggleKeyboardShortcutsInhibit,
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: false,
                         hotkey_overlay_title: None,
@@ -1935,6 +2425,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: true,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -1951,6 +2442,7 @@ mod tests {
                         action: CloseWindow,
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: Some(
@@ -1969,6 +2461,7 @@ mod tests {
                         action: FocusMonitorLeft,
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -1987,6 +2480,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2003,6 +2497,7 @@ mod tests {
                         action: MoveWindowToMonitorRight,
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2021,6 +2516,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2039,6 +2535,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2055,6 +2552,7 @@ mod tests {
                         action: ConsumeWindowIntoColumn,
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2075,6 +2573,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2095,6 +2594,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2113,6 +2613,7 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: false,
                         hotkey_overlay_title: None,
@@ -2129,6 +2630,7 @@ mod tests {
                         cooldown: Some(
                             150ms,
                         ),
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2147,12 +2649,95 @@ mod tests {
                         ),
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: true,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
                     },
+                    Bind {
+                        key: Key {
+                            trigger: ModifierTap,
+                            modifiers: Modifiers(
+                                COMPOSITOR,
+                            ),
+                        },
+                        action: ShowHotkeyOverlay,
+                        repeat: true,
+                        cooldown: None,
+                        double_press: Some(
+                            300ms,
+                        ),
+                        allow_when_locked: false,
+                        allow_inhibiting: true,
+                        hotkey_overlay_title: None,
+                    },
                 ],
             ),
+            bind_modes: BindModes(
+                [
+                    BindMode {
+                        name: "resize",
+                        binds: [
+                            Bind {
+                                key: Key {
+                                    trigger: Keysym(
+                                        XK_h,
+                                    ),
+                                    modifiers: Modifiers(
+                                        COMPOSITOR,
+                                    ),
+                                },
+                                action: FocusColumnLeft,
+                                repeat: true,
+                                cooldown: None,
+                                double_press: None,
+                                allow_when_locked: false,
+                                allow_inhibiting: true,
+                                hotkey_overlay_title: None,
+                            },
+                            Bind {
+                                key: Key {
+                                    trigger: Keysym(
+                                        XK_Return,
+                                    ),
+                                    modifiers: Modifiers(
+                                        COMPOSITOR,
+                                    ),
+                                },
+                                action: SwitchBindMode(
+                                    "default",
+                                ),
+                                repeat: true,
+                                cooldown: None,
+                                double_press: None,
+                                allow_when_locked: false,
+                                allow_inhibiting: true,
+                                hotkey_overlay_title: None,
+                            },
+                        ],
+                    },
+                ],
+            ),
+            region_binds: RegionBinds {
+                background: [
+                    Bind {
+                        key: Key {
+                            trigger: WheelScrollDown,
+                            modifiers: Modifiers(
+                                0x0,
+                            ),
+                        },
+                        action: FocusColumnRight,
+                        repeat: true,
+                        cooldown: None,
+                        double_press: None,
+                        allow_when_locked: false,
+                        allow_inhibiting: true,
+                        hotkey_overlay_title: None,
+                    },
+                ],
+                border: [],
+            },
             switch_events: SwitchBinds {
                 lid_open: None,
                 lid_close: None,
@@ -2202,6 +2787,7 @@ mod tests {
                 honor_xdg_activation_with_invalid_serial: false,
                 deactivate_unfocused_windows: false,
                 skip_cursor_only_updates_during_vrr: false,
+                trace_input_latency: false,
             },
             workspaces: [
                 Workspace {
@@ -2271,6 +2857,7 @@ mod tests {
                         },
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2293,6 +2880,7 @@ mod tests {
                         },
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -2317,6 +2905,7 @@ mod tests {
                         },
                         repeat: true,
                         cooldown: None,
+                        double_press: None,
                         allow_when_locked: false,
                         allow_inhibiting: true,
                         hotkey_overlay_title: None,
@@ -1,3 +1,5 @@
+use niri_ipc::SizeChange;
+
 use crate::appearance::{Color, WorkspaceShadow, WorkspaceShadowPart, DEFAULT_BACKDROP_COLOR};
 use crate::utils::{Flag, MergeWith};
 use crate::FloatOrInt;
@@ -152,6 +154,69 @@ impl MergeWith<OverviewPart> for Overview {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResizeMode {
+    pub width_step: SizeChange,
+    pub height_step: SizeChange,
+}
+
+impl Default for ResizeMode {
+    fn default() -> Self {
+        Self {
+            width_step: SizeChange::SetProportion(10.),
+            height_step: SizeChange::SetProportion(10.),
+        }
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
+pub struct ResizeModePart {
+    #[knuffel(child, unwrap(argument, str))]
+    pub width_step: Option<SizeChange>,
+    #[knuffel(child, unwrap(argument, str))]
+    pub height_step: Option<SizeChange>,
+}
+
+impl MergeWith<ResizeModePart> for ResizeMode {
+    fn merge_with(&mut self, part: &ResizeModePart) {
+        merge_clone!((self, part), width_step, height_step);
+    }
+}
+
+/// Commands to run on compositor events.
+#[derive(knuffel::Decode, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Hooks {
+    #[knuffel(child)]
+    pub on_window_open: Option<Hook>,
+    #[knuffel(child)]
+    pub on_window_close: Option<Hook>,
+    #[knuffel(child)]
+    pub on_workspace_focus: Option<Hook>,
+    #[knuffel(child)]
+    pub on_output_connect: Option<Hook>,
+    #[knuffel(child)]
+    pub on_output_disconnect: Option<Hook>,
+}
+
+#[derive(knuffel::Decode, Debug, Clone, PartialEq, Eq)]
+pub struct Hook {
+    #[knuffel(arguments)]
+    pub command: Vec<String>,
+}
+
+impl MergeWith<Self> for Hooks {
+    fn merge_with(&mut self, part: &Self) {
+        merge_clone_opt!(
+            (self, part),
+            on_window_open,
+            on_window_close,
+            on_workspace_focus,
+            on_output_connect,
+            on_output_disconnect,
+        );
+    }
+}
+
 #[derive(knuffel::Decode, Debug, Default, Clone, PartialEq, Eq)]
 pub struct Environment(#[knuffel(children)] pub Vec<EnvironmentVariable>);
 
@@ -151,6 +151,7 @@ impl From<MruBind> for Bind {
             action: Action::from(x.action),
             repeat: true,
             cooldown: None,
+            double_press: None,
             allow_when_locked: false,
             allow_inhibiting: x.allow_inhibiting,
             hotkey_overlay_title: x.hotkey_overlay_title,
@@ -1,7 +1,8 @@
 use niri_ipc::ColumnDisplay;
 
 use crate::appearance::{BlockOutFrom, BorderRule, CornerRadius, ShadowRule, TabIndicatorRule};
-use crate::layout::DefaultPresetSize;
+use crate::gestures::ThreeFingerSwipe;
+use crate::layout::{DefaultPresetSize, NewWindowPlacement};
 use crate::utils::RegexEq;
 use crate::FloatOrInt;
 
@@ -31,6 +32,8 @@ pub struct WindowRule {
     pub open_floating: Option<bool>,
     #[knuffel(child, unwrap(argument))]
     pub open_focused: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub new_window_placement: Option<NewWindowPlacement>,
 
     // Rules applied dynamically.
     #[knuffel(child, unwrap(argument))]
@@ -72,6 +75,22 @@ pub struct WindowRule {
     pub scroll_factor: Option<FloatOrInt<0, 100>>,
     #[knuffel(child, unwrap(argument))]
     pub tiled_state: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub forward_pinch_gestures: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub allow_keyboard_shortcuts_inhibit: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub disable_pointer_gestures: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub allow_swallowing: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub always_on_top: Option<bool>,
+    #[knuffel(child, unwrap(argument))]
+    pub exclude_from_gesture_actions: Option<bool>,
+    #[knuffel(child, unwrap(arguments))]
+    pub tags: Option<Vec<String>>,
+    #[knuffel(child, default)]
+    pub three_finger_swipe: ThreeFingerSwipe,
 }
 
 #[derive(knuffel::Decode, Debug, Default, Clone, PartialEq)]
@@ -94,6 +113,8 @@ pub struct Match {
     pub is_urgent: Option<bool>,
     #[knuffel(property)]
     pub at_startup: Option<bool>,
+    #[knuffel(property)]
+    pub profile: Option<String>,
 }
 
 #[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
@@ -6,7 +6,8 @@
 //!
 //! If you send a [`Request::EventStream`], niri will *stop* reading subsequent [`Request`]s, and
 //! will start continuously writing compositor [`Event`]s to the socket. If you'd like to read an
-//! event stream and write more requests at the same time, you need to use two IPC sockets.
+//! event stream and write more requests at the same time, you need to use two IPC sockets. The
+//! same goes for [`Request::Watch`], which writes [`WatchMessage`]s instead.
 //!
 //! <div class="warning">
 //!
@@ -75,6 +76,8 @@ pub enum Request {
     Windows,
     /// Request information about layer-shell surfaces.
     Layers,
+    /// Request the marks set on windows.
+    Marks,
     /// Request information about the configured keyboard layouts.
     KeyboardLayouts,
     /// Request information about the focused output.
@@ -87,6 +90,12 @@ pub enum Request {
     PickColor,
     /// Perform an action.
     Action(Action),
+    /// Perform several actions together.
+    ///
+    /// The actions run in order, all at once between two frames, so nothing is drawn in the
+    /// middle of the sequence, and no other request can run in-between. If any of the actions is
+    /// invalid, none of them run.
+    Actions(Vec<Action>),
     /// Change output configuration temporarily.
     ///
     /// The configuration is changed temporarily and not saved into the config file. If the output
@@ -113,10 +122,45 @@ pub enum Request {
     /// been removed. This can happen if the corresponding [`Event::WorkspacesChanged`] arrives
     /// before the corresponding [`Event::WindowOpenedOrChanged`].
     EventStream,
+    /// Start watching some resources, with sequence numbers.
+    ///
+    /// The compositor should reply with `Reply::Ok(Response::Handled)`, then continuously send
+    /// [`WatchMessage`]s, one per line.
+    ///
+    /// The first message is always a [`WatchMessage::Snapshot`] with the full current state of
+    /// the watched resources, followed by a [`WatchMessage::Diff`] for every change to them. Only
+    /// the events for the watched resources are sent.
+    Watch {
+        /// Resources to watch.
+        ///
+        /// If empty, all resources are watched.
+        resources: Vec<WatchResource>,
+    },
     /// Respond with an error (for testing error handling).
     ReturnError,
     /// Request information about the overview.
     OverviewState,
+    /// Request the input latency statistics.
+    ///
+    /// niri only collects them with the `trace-input-latency` debug flag.
+    InputLatency,
+    /// Request the gesture binds, including the changes made over IPC.
+    GestureBinds,
+    /// Change a gesture bind temporarily.
+    ///
+    /// The change is not saved into the config file. It takes precedence over the gesture binds
+    /// from the config, including the ones in profiles, and stays through config reloads until
+    /// it is reset.
+    GestureBind {
+        /// Gesture to change.
+        trigger: GestureTrigger,
+        /// Change to make.
+        change: GestureBindChange,
+    },
+    /// Undo all gesture bind changes made over IPC.
+    ResetGestureBinds,
+    /// Request a dump of the compositor state to attach to bug reports.
+    DumpState,
 }
 
 /// Reply from niri to client.
@@ -147,6 +191,8 @@ pub enum Response {
     Windows(Vec<Window>),
     /// Information about layer-shell surfaces.
     Layers(Vec<LayerSurface>),
+    /// Marks set on windows.
+    Marks(Vec<WindowMark>),
     /// Information about the keyboard layout.
     KeyboardLayouts(KeyboardLayouts),
     /// Information about the focused output.
@@ -161,6 +207,12 @@ pub enum Response {
     OutputConfigChanged(OutputConfigChanged),
     /// Information about the overview.
     OverviewState(Overview),
+    /// Input latency statistics.
+    InputLatency(InputLatency),
+    /// Information about the gesture binds.
+    GestureBinds(Vec<GestureBind>),
+    /// Dump of the compositor state.
+    StateDump(StateDump),
 }
 
 /// Overview information.
@@ -171,6 +223,115 @@ pub struct Overview {
     pub is_open: bool,
 }
 
+/// Dump of the compositor state for bug reports.
+///
+/// Window titles are left out, since they can contain private information.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct StateDump {
+    /// Version of the running niri instance.
+    pub version: String,
+    /// Error from the last config load, if it failed.
+    pub config_error: Option<String>,
+    /// Connected outputs.
+    pub outputs: Vec<Output>,
+    /// Workspaces.
+    pub workspaces: Vec<Workspace>,
+    /// Open windows, without their titles.
+    pub windows: Vec<Window>,
+    /// Connected input devices.
+    pub input_devices: Vec<InputDevice>,
+    /// Internal state of the ongoing gestures, in a human-readable form.
+    pub gestures: String,
+}
+
+/// Input device.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct InputDevice {
+    /// Name of the device.
+    pub name: String,
+    /// What the device can do.
+    pub capabilities: Vec<InputDeviceCapability>,
+}
+
+/// Capability of an input device.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum InputDeviceCapability {
+    /// Keyboard keys.
+    Keyboard,
+    /// Pointer movement and buttons.
+    Pointer,
+    /// Touchscreen.
+    Touch,
+    /// Tablet tool, like a pen.
+    TabletTool,
+    /// Tablet pad buttons, rings and strips.
+    TabletPad,
+    /// Touchpad gestures.
+    Gesture,
+    /// Switches, like the laptop lid.
+    Switch,
+}
+
+/// Input latency statistics.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct InputLatency {
+    /// Whether niri is currently collecting the statistics.
+    pub enabled: bool,
+    /// Latency of each stage that the input events go through, in order.
+    pub stages: Vec<InputLatencyStage>,
+}
+
+/// Latency statistics of one stage of input event processing.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct InputLatencyStage {
+    /// The stage.
+    pub stage: InputStage,
+    /// Number of events that went through this stage.
+    pub count: u64,
+    /// Mean latency in microseconds.
+    pub mean_us: u64,
+    /// Maximum latency in microseconds.
+    pub max_us: u64,
+    /// Latency histogram.
+    ///
+    /// Buckets are in increasing order, and each one counts the events that are not in the
+    /// previous buckets.
+    pub buckets: Vec<LatencyBucket>,
+}
+
+/// Stage of input event processing.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum InputStage {
+    /// From the kernel timestamp of the event to niri starting to process it.
+    Receive,
+    /// From niri starting to process the event to it going through the binds and gestures and
+    /// into the client queues.
+    Dispatch,
+    /// From the end of the dispatch to niri sending the queued events to the clients.
+    Flush,
+    /// From the kernel timestamp of the event to the presentation of the first frame rendered
+    /// after it.
+    Present,
+}
+
+/// Histogram bucket of latencies.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct LatencyBucket {
+    /// Upper bound of the latencies in this bucket, in microseconds.
+    ///
+    /// `None` for the last bucket, which counts all latencies above the previous one.
+    pub max_us: Option<u64>,
+    /// Number of events in this bucket.
+    pub count: u64,
+}
+
 /// Color picked from the screen.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -194,6 +355,8 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(short, long))]
         skip_confirmation: bool,
     },
+    /// Suspend the system.
+    Suspend {},
     /// Power off all monitors via DPMS.
     PowerOffMonitors {},
     /// Power on all monitors via DPMS.
@@ -210,6 +373,35 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(last = true, required = true))]
         command: String,
     },
+    /// Send key combos to a window.
+    ///
+    /// The combos are typed one after another through the compositor keyboard, bypassing the
+    /// niri binds.
+    SendKeys {
+        /// Id of the window to send the keys to.
+        ///
+        /// If `None`, uses the focused window. Otherwise, the keyboard focus moves to the window
+        /// only while the keys are sent, and the focused window doesn't change.
+        #[cfg_attr(feature = "clap", arg(long))]
+        window: Option<u64>,
+        /// Key combos to send, for example `Ctrl+W`.
+        #[cfg_attr(feature = "clap", arg(required = true))]
+        keys: Vec<String>,
+    },
+    /// Switch to a bind mode.
+    ///
+    /// While a mode is active, its binds replace the regular binds. Switch to `default` to go
+    /// back to the regular binds.
+    SwitchBindMode {
+        /// Name of the mode.
+        #[cfg_attr(feature = "clap", arg())]
+        name: String,
+    },
+    /// Start resizing the focused window with the keyboard.
+    ///
+    /// The arrow keys and hjkl resize the window, Enter keeps the new size, and Escape reverts
+    /// it.
+    EnterResizeMode {},
     /// Do a screen transition.
     DoScreenTransition {
         /// Delay in milliseconds for the screen to freeze before starting the transition.
@@ -232,6 +424,39 @@ pub enum Action {
     },
     /// Screenshot the focused screen.
     ScreenshotScreen {
+        /// Name of the output to screenshot.
+        ///
+        /// If `None`, uses the focused output.
+        #[cfg_attr(feature = "clap", arg(long))]
+        output: Option<String>,
+
+        /// Write the screenshot to disk in addition to putting it in your clipboard.
+        ///
+        /// The screenshot is saved according to the `screenshot-path` config setting.
+        #[cfg_attr(feature = "clap", arg(short = 'd', long, action = clap::ArgAction::Set, default_value_t = true))]
+        write_to_disk: bool,
+
+        /// Whether to include the mouse pointer in the screenshot.
+        #[cfg_attr(feature = "clap", arg(short = 'p', long, action = clap::ArgAction::Set, default_value_t = true))]
+        show_pointer: bool,
+
+        /// Path to save the screenshot to.
+        ///
+        /// The path must be absolute, otherwise an error is returned.
+        ///
+        /// If `None`, the screenshot is saved according to the `screenshot-path` config setting.
+        #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set))]
+        path: Option<String>,
+    },
+    /// Screenshot a region of the screen.
+    ScreenshotRegion {
+        /// Region to screenshot, as `x,y,width,height` in logical coordinates.
+        ///
+        /// The region is taken from the output containing its top-left corner, and is cut to the
+        /// edges of that output.
+        #[cfg_attr(feature = "clap", arg(allow_hyphen_values = true))]
+        region: Region,
+
         /// Write the screenshot to disk in addition to putting it in your clipboard.
         ///
         /// The screenshot is saved according to the `screenshot-path` config setting.
@@ -279,6 +504,10 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long, action = clap::ArgAction::Set))]
         path: Option<String>,
     },
+    /// Pick a color from the screen with the mouse and copy it to the clipboard.
+    ///
+    /// The color is copied in the hex format, like `#1a2b3c`.
+    PickColor {},
     /// Enable or disable the keyboard shortcuts inhibitor (if any) for the focused surface.
     ToggleKeyboardShortcutsInhibit {},
     /// Close a window.
@@ -290,6 +519,45 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         id: Option<u64>,
     },
+    /// Kill the process of a window that doesn't respond to closing, after a confirmation.
+    #[cfg_attr(
+        feature = "clap",
+        clap(about = "Kill the process of the focused window after a confirmation")
+    )]
+    ForceCloseWindow {
+        /// Id of the window to force close.
+        ///
+        /// If `None`, uses the focused window, or the window under the cursor with
+        /// `under_cursor`.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+        /// Use the window under the cursor instead of the focused window.
+        #[cfg_attr(feature = "clap", arg(long, conflicts_with = "id"))]
+        under_cursor: bool,
+    },
+    /// Minimize a window.
+    ///
+    /// The window is hidden from the layout until restored, for example from a taskbar.
+    #[cfg_attr(feature = "clap", clap(about = "Minimize the focused window"))]
+    MinimizeWindow {
+        /// Id of the window to minimize.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Put a minimized window back where it was and focus it.
+    #[cfg_attr(
+        feature = "clap",
+        clap(about = "Restore the most recently minimized window")
+    )]
+    UnminimizeWindow {
+        /// Id of the window to restore.
+        ///
+        /// If `None`, uses the most recently minimized window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
     /// Toggle fullscreen on a window.
     #[cfg_attr(
         feature = "clap",
@@ -444,6 +712,8 @@ pub enum Action {
     SwapWindowLeft {},
     /// Toggle the focused column between normal and tabbed display.
     ToggleColumnTabbedDisplay {},
+    /// Toggle the focused column between normal and accordion display.
+    ToggleColumnAccordionDisplay {},
     /// Set the display mode of the focused column.
     SetColumnDisplay {
         /// Display mode to set.
@@ -761,6 +1031,13 @@ pub enum Action {
     },
     /// Expand the focused column to space not taken up by other fully visible columns.
     ExpandColumnToAvailableWidth {},
+    /// Set all columns on the focused workspace to equal widths.
+    EqualizeColumnWidths {},
+    /// Set the widths of all columns on the focused workspace proportionally to their number of
+    /// windows.
+    DistributeColumnWidths {},
+    /// Toggle the monocle mode on the focused workspace, where every column takes the full width.
+    ToggleMonocle {},
     /// Switch between keyboard layouts.
     SwitchLayout {
         /// Layout to switch to.
@@ -803,6 +1080,49 @@ pub enum Action {
     DebugToggleOpaqueRegions {},
     /// Toggle visualization of output damage.
     DebugToggleDamage {},
+    /// Toggle the overlay showing the state of the gesture recognizers.
+    DebugToggleGestureOverlay {},
+    /// Zoom the magnifier back out.
+    ResetMagnifier {},
+    /// Feed a scripted touchpad gesture to the gesture binds.
+    ///
+    /// The gesture runs through the same recognizers as a real one, with synthetic timestamps
+    /// spread over the duration, so it triggers the same binds and IPC gesture events.
+    SimulateGesture {
+        /// Kind of the gesture: `swipe`, `pinch` or `hold`.
+        #[cfg_attr(feature = "clap", arg())]
+        kind: GestureKind,
+        /// Number of fingers.
+        #[cfg_attr(feature = "clap", arg())]
+        fingers: u8,
+        /// Total horizontal movement of a swipe, in touchpad units.
+        #[cfg_attr(
+            feature = "clap",
+            arg(long, default_value_t = 0., allow_hyphen_values = true)
+        )]
+        dx: f64,
+        /// Total vertical movement of a swipe, in touchpad units.
+        #[cfg_attr(
+            feature = "clap",
+            arg(long, default_value_t = 0., allow_hyphen_values = true)
+        )]
+        dy: f64,
+        /// Final scale of a pinch.
+        #[cfg_attr(feature = "clap", arg(long, default_value_t = 1.))]
+        scale: f64,
+        /// Total rotation of a pinch in degrees, clockwise.
+        #[cfg_attr(
+            feature = "clap",
+            arg(long, default_value_t = 0., allow_hyphen_values = true)
+        )]
+        rotation: f64,
+        /// Duration of the gesture in milliseconds.
+        #[cfg_attr(feature = "clap", arg(long, default_value_t = 100))]
+        duration_ms: u32,
+        /// End the gesture as cancelled, like lifting the fingers early.
+        #[cfg_attr(feature = "clap", arg(long))]
+        cancelled: bool,
+    },
     /// Move the focused window between the floating and the tiling layout.
     ToggleWindowFloating {
         /// Id of the window to move.
@@ -827,6 +1147,26 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         id: Option<u64>,
     },
+    /// Toggle whether the focused window follows the workspace switches on its monitor.
+    ///
+    /// Pinning a tiled window also moves it to the floating layout.
+    ToggleWindowPinned {
+        /// Id of the window to pin or unpin.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Toggle whether the focused window stays above the other floating windows.
+    ///
+    /// Making a tiled window always-on-top also moves it to the floating layout.
+    ToggleWindowAlwaysOnTop {
+        /// Id of the window to toggle.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
     /// Switches focus to the floating layout.
     FocusFloating {},
     /// Switches focus to the tiling layout.
@@ -868,6 +1208,69 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg(long))]
         id: Option<u64>,
     },
+    /// Toggle a tag on a window.
+    #[cfg_attr(feature = "clap", clap(about = "Toggle a tag on the focused window"))]
+    ToggleWindowTag {
+        /// Tag to toggle.
+        #[cfg_attr(feature = "clap", arg())]
+        tag: String,
+
+        /// Id of the window.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Focus the next window with a tag.
+    FocusWindowWithTag {
+        /// Tag to look for.
+        #[cfg_attr(feature = "clap", arg())]
+        tag: String,
+    },
+    /// Move all windows with a tag to the focused workspace.
+    BringWindowsWithTag {
+        /// Tag to look for.
+        #[cfg_attr(feature = "clap", arg())]
+        tag: String,
+    },
+    /// Set a mark on a window.
+    ///
+    /// A mark is on at most one window at a time, so setting it moves it from the window that
+    /// had it before.
+    #[cfg_attr(feature = "clap", clap(about = "Set a mark on the focused window"))]
+    SetMark {
+        /// Mark to set, a single character.
+        #[cfg_attr(feature = "clap", arg())]
+        mark: char,
+
+        /// Id of the window.
+        ///
+        /// If `None`, uses the focused window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: Option<u64>,
+    },
+    /// Focus the window with a mark.
+    GotoMark {
+        /// Mark to look for.
+        #[cfg_attr(feature = "clap", arg())]
+        mark: char,
+    },
+    /// Set the active window rule profile.
+    ///
+    /// Window rules with `match profile="..."` only apply while their profile is active.
+    SetWindowRuleProfile {
+        /// Name of the profile.
+        ///
+        /// If `None`, no profile is active.
+        #[cfg_attr(feature = "clap", arg())]
+        profile: Option<String>,
+    },
+    /// Activate a window rule profile, or deactivate it if it is already active.
+    ToggleWindowRuleProfile {
+        /// Name of the profile.
+        #[cfg_attr(feature = "clap", arg())]
+        profile: String,
+    },
     /// Set the dynamic cast target to a window.
     #[cfg_attr(
         feature = "clap",
@@ -985,6 +1388,8 @@ pub enum ColumnDisplay {
     Normal,
     /// Windows are in tabs.
     Tabbed,
+    /// The active window takes most of the height, and the other windows are collapsed.
+    Accordion,
 }
 
 /// Output actions that niri can perform.
@@ -1101,6 +1506,20 @@ pub struct ConfiguredMode {
     pub refresh: Option<f64>,
 }
 
+/// Rectangle in the global logical coordinate space.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct Region {
+    /// X coordinate of the top-left corner.
+    pub x: i32,
+    /// Y coordinate of the top-left corner.
+    pub y: i32,
+    /// Width in logical pixels.
+    pub width: u32,
+    /// Height in logical pixels.
+    pub height: u32,
+}
+
 /// Modeline horizontal syncing polarity.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -1179,7 +1598,7 @@ pub struct VrrToSet {
 }
 
 /// Connected output.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Output {
     /// Name of the output.
@@ -1294,6 +1713,11 @@ pub struct Window {
     pub pid: Option<i32>,
     /// Id of the workspace this window is on, if any.
     pub workspace_id: Option<u64>,
+    /// Name of the output this window is on, if any.
+    ///
+    /// Unlike [`Self::workspace_id`], this is also set for a window that is being moved with
+    /// the mouse.
+    pub output: Option<String>,
     /// Whether this window is currently focused.
     ///
     /// There can be either one focused window or zero (e.g. when a layer-shell surface has focus).
@@ -1302,8 +1726,14 @@ pub struct Window {
     ///
     /// If the window isn't floating then it is in the tiling layout.
     pub is_floating: bool,
+    /// Whether this window is currently fullscreen.
+    pub is_fullscreen: bool,
     /// Whether this window requests your attention.
     pub is_urgent: bool,
+    /// Whether this window is minimized.
+    ///
+    /// Minimized windows are out of the layout, so they have no workspace or output.
+    pub is_minimized: bool,
     /// Position- and size-related properties of the window.
     pub layout: WindowLayout,
     /// Timestamp when the window was most recently focused.
@@ -1473,6 +1903,174 @@ pub struct LayerSurface {
     pub keyboard_interactivity: LayerSurfaceKeyboardInteractivity,
 }
 
+/// Resource that can be watched with [`Request::Watch`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum WatchResource {
+    /// Open windows.
+    Windows,
+    /// Workspaces.
+    Workspaces,
+    /// Connected outputs.
+    Outputs,
+}
+
+impl WatchResource {
+    /// Returns the resource that this event changes, if any.
+    pub fn for_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::WorkspacesChanged { .. }
+            | Event::WorkspaceUrgencyChanged { .. }
+            | Event::WorkspaceActivated { .. }
+            | Event::WorkspaceActiveWindowChanged { .. } => Some(Self::Workspaces),
+            Event::WindowsChanged { .. }
+            | Event::WindowOpenedOrChanged { .. }
+            | Event::WindowClosed { .. }
+            | Event::WindowFocusChanged { .. }
+            | Event::WindowFocusTimestampChanged { .. }
+            | Event::WindowUrgencyChanged { .. }
+            | Event::WindowLayoutsChanged { .. } => Some(Self::Windows),
+            Event::OutputsChanged { .. } => Some(Self::Outputs),
+            Event::KeyboardLayoutsChanged { .. }
+            | Event::KeyboardLayoutSwitched { .. }
+            | Event::OverviewOpenedOrClosed { .. }
+            | Event::TabletModeChanged { .. }
+            | Event::ConfigLoaded { .. }
+            | Event::ScreenshotCaptured { .. }
+            | Event::GestureBegin { .. }
+            | Event::GestureUpdate { .. }
+            | Event::GestureEnd { .. } => None,
+        }
+    }
+}
+
+/// Message sent to clients watching resources with [`Request::Watch`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum WatchMessage {
+    /// Full current state of the watched resources.
+    ///
+    /// Applying these events to default-initialized state replicates the current state.
+    Snapshot {
+        /// Sequence number of the last change included in this snapshot.
+        seq: u64,
+        /// Events that replicate the state.
+        events: Vec<Event>,
+    },
+    /// A change to one of the watched resources.
+    Diff {
+        /// Sequence number of this change.
+        ///
+        /// Sequence numbers are shared between all resources and all clients, and grow by one
+        /// with every event, so they skip the changes to resources that aren't watched.
+        seq: u64,
+        /// The change.
+        event: Event,
+    },
+}
+
+/// A mark set on a window.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct WindowMark {
+    /// The mark.
+    pub mark: char,
+    /// Id of the window with this mark.
+    pub window_id: u64,
+}
+
+/// Kind of a touchpad or touchscreen gesture.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum GestureKind {
+    /// Fingers moving together in one direction.
+    Swipe,
+    /// Fingers moving towards or away from each other, or rotating.
+    Pinch,
+    /// Fingers resting without moving.
+    Hold,
+}
+
+/// Gesture that a gesture bind reacts to.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct GestureTrigger {
+    /// Kind of the gesture.
+    pub kind: GestureKind,
+    /// Number of fingers.
+    pub fingers: u8,
+    /// Direction of the gesture; `None` for hold gestures.
+    pub direction: Option<GestureDirection>,
+    /// Whether the swipe must end with a fling.
+    pub fling: bool,
+}
+
+/// A global gesture bind.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct GestureBind {
+    /// Gesture that the bind reacts to.
+    pub trigger: GestureTrigger,
+    /// Description of the action that the gesture triggers.
+    ///
+    /// `None` if the gesture was disabled over IPC.
+    pub action: Option<String>,
+    /// Whether the bind was changed over IPC.
+    pub is_overridden: bool,
+}
+
+/// Temporary change to a gesture bind.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "clap", derive(clap::Parser))]
+#[cfg_attr(feature = "clap", command(subcommand_value_name = "CHANGE"))]
+#[cfg_attr(feature = "clap", command(subcommand_help_heading = "Changes"))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum GestureBindChange {
+    /// Make the gesture trigger nothing.
+    Disable,
+    /// Undo disabling the gesture.
+    Enable,
+    /// Make the gesture trigger a different action.
+    Rebind {
+        /// Action to trigger.
+        #[cfg_attr(feature = "clap", command(subcommand))]
+        action: Action,
+    },
+    /// Put back the gesture bind from the config.
+    Reset,
+}
+
+/// Direction of a touchpad or touchscreen gesture.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub enum GestureDirection {
+    /// Swipe to the left.
+    Left,
+    /// Swipe to the right.
+    Right,
+    /// Swipe up.
+    Up,
+    /// Swipe down.
+    Down,
+    /// Swipe diagonally up and to the left.
+    UpLeft,
+    /// Swipe diagonally up and to the right.
+    UpRight,
+    /// Swipe diagonally down and to the left.
+    DownLeft,
+    /// Swipe diagonally down and to the right.
+    DownRight,
+    /// Pinch with the fingers moving towards each other.
+    In,
+    /// Pinch with the fingers moving away from each other.
+    Out,
+    /// Pinch with the fingers rotating clockwise.
+    RotateCw,
+    /// Pinch with the fingers rotating counter-clockwise.
+    RotateCcw,
+}
+
 /// A compositor event.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -1562,6 +2160,16 @@ pub enum Event {
         /// Pairs consisting of a window id and new layout information for the window.
         changes: Vec<(u64, WindowLayout)>,
     },
+    /// The output configuration has changed.
+    ///
+    /// Sent when an output is connected, disconnected, or reconfigured.
+    OutputsChanged {
+        /// The new output configuration.
+        ///
+        /// This configuration completely replaces the previous configuration. I.e. if any outputs
+        /// are missing from here, then they were disconnected.
+        outputs: Vec<Output>,
+    },
     /// The configured keyboard layouts have changed.
     KeyboardLayoutsChanged {
         /// The new keyboard layout configuration.
@@ -1577,6 +2185,14 @@ pub enum Event {
         /// The new state of the overview.
         is_open: bool,
     },
+    /// A convertible laptop went into or out of tablet mode.
+    ///
+    /// You will always receive this event when connecting to the event stream, indicating the
+    /// current tablet mode.
+    TabletModeChanged {
+        /// Whether the laptop is now in tablet mode.
+        is_tablet_mode: bool,
+    },
     /// The configuration was reloaded.
     ///
     /// You will always receive this event when connecting to the event stream, indicating the last
@@ -1595,6 +2211,34 @@ pub enum Event {
         /// be converted to a `String` (e.g. contained invalid UTF-8 bytes).
         path: Option<String>,
     },
+    /// A gesture handled by the gesture binds started.
+    GestureBegin {
+        /// Kind of the gesture.
+        kind: GestureKind,
+        /// Number of fingers.
+        fingers: u8,
+    },
+    /// A gesture handled by the gesture binds progressed.
+    ///
+    /// The progress is reported in steps of 0.05, and an update is only sent when the direction or
+    /// the step changes. Hold gestures don't have updates.
+    GestureUpdate {
+        /// Direction that the gesture is currently closest to triggering in.
+        direction: GestureDirection,
+        /// Progress towards triggering in this direction, from 0 to 1.
+        ///
+        /// The gesture triggers its bind, if any, when the progress reaches 1. After that, the
+        /// gesture doesn't send any more updates, unless a swipe changes its direction with the
+        /// `swipe-hysteresis` threshold, which sends another update with progress 1.
+        progress: f64,
+    },
+    /// A gesture handled by the gesture binds ended.
+    GestureEnd {
+        /// Whether the gesture was cancelled.
+        ///
+        /// A hold gesture triggers its bind when it ends without being cancelled.
+        cancelled: bool,
+    },
 }
 
 impl From<Duration> for Timestamp {
@@ -1630,6 +2274,41 @@ impl FromStr for WorkspaceReferenceArg {
     }
 }
 
+impl FromStr for GestureKind {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "swipe" => Ok(Self::Swipe),
+            "pinch" => Ok(Self::Pinch),
+            "hold" => Ok(Self::Hold),
+            _ => Err(r#"invalid gesture kind, can be "swipe", "pinch" or "hold""#),
+        }
+    }
+}
+
+impl FromStr for GestureDirection {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Self::Left),
+            "right" => Ok(Self::Right),
+            "up" => Ok(Self::Up),
+            "down" => Ok(Self::Down),
+            "up-left" => Ok(Self::UpLeft),
+            "up-right" => Ok(Self::UpRight),
+            "down-left" => Ok(Self::DownLeft),
+            "down-right" => Ok(Self::DownRight),
+            "in" => Ok(Self::In),
+            "out" => Ok(Self::Out),
+            "rotate-cw" => Ok(Self::RotateCw),
+            "rotate-ccw" => Ok(Self::RotateCcw),
+            _ => Err("invalid gesture direction"),
+        }
+    }
+}
+
 impl FromStr for SizeChange {
     type Err = &'static str;
 
@@ -1732,7 +2411,8 @@ impl FromStr for ColumnDisplay {
         match s {
             "normal" => Ok(Self::Normal),
             "tabbed" => Ok(Self::Tabbed),
-            _ => Err(r#"invalid column display, can be "normal" or "tabbed""#),
+            "accordion" => Ok(Self::Accordion),
+            _ => Err(r#"invalid column display, can be "normal", "tabbed" or "accordion""#),
         }
     }
 }
@@ -1799,6 +2479,35 @@ impl FromStr for ConfiguredMode {
     }
 }
 
+impl FromStr for Region {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',').map(str::trim);
+        let (Some(x), Some(y), Some(width), Some(height), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return Err("region must be written as x,y,width,height");
+        };
+
+        let x = x.parse().map_err(|_| "error parsing x")?;
+        let y = y.parse().map_err(|_| "error parsing y")?;
+        let width = width.parse().map_err(|_| "error parsing width")?;
+        let height = height.parse().map_err(|_| "error parsing height")?;
+
+        Ok(Self {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
 impl FromStr for HSyncPolarity {
     type Err = &'static str;
 
@@ -1982,4 +2691,21 @@ mod tests {
         assert!("-".parse::<PositionChange>().is_err());
         assert!("10% ".parse::<PositionChange>().is_err());
     }
+
+    #[test]
+    fn parse_region() {
+        assert_eq!(
+            "-100, 20,300,400".parse::<Region>().unwrap(),
+            Region {
+                x: -100,
+                y: 20,
+                width: 300,
+                height: 400,
+            },
+        );
+
+        assert!("0,0,300".parse::<Region>().is_err());
+        assert!("0,0,300,400,5".parse::<Region>().is_err());
+        assert!("0,0,-300,400".parse::<Region>().is_err());
+    }
 }
@@ -6,7 +6,7 @@ use std::net::Shutdown;
 use std::os::unix::net::UnixStream;
 use std::path::Path;
 
-use crate::{Event, Reply, Request};
+use crate::{Event, Reply, Request, WatchMessage};
 
 /// Name of the environment variable containing the niri IPC socket path.
 pub const SOCKET_PATH_ENV: &str = "NIRI_SOCKET";
@@ -98,4 +98,22 @@ impl Socket {
             Ok(event)
         }
     }
+
+    /// Starts reading [`WatchMessage`]s from the socket.
+    ///
+    /// The returned function will block until the next [`WatchMessage`] arrives, then return it.
+    ///
+    /// Use this only after requesting a [`Watch`][Request::Watch].
+    pub fn read_watch_messages(self) -> impl FnMut() -> io::Result<WatchMessage> {
+        let Self { mut stream } = self;
+        let _ = stream.get_mut().shutdown(Shutdown::Write);
+
+        let mut buf = String::new();
+        move || {
+            buf.clear();
+            stream.read_line(&mut buf)?;
+            let message = serde_json::from_str(&buf)?;
+            Ok(message)
+        }
+    }
 }
@@ -9,7 +9,7 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
-use crate::{Event, KeyboardLayouts, Window, Workspace};
+use crate::{Event, KeyboardLayouts, Output, Window, Workspace};
 
 /// Part of the state communicated via the event stream.
 pub trait EventStreamStatePart {
@@ -38,12 +38,18 @@ pub struct EventStreamState {
     /// State of workspaces.
     pub windows: WindowsState,
 
+    /// State of outputs.
+    pub outputs: OutputsState,
+
     /// State of the keyboard layouts.
     pub keyboard_layouts: KeyboardLayoutsState,
 
     /// State of the overview.
     pub overview: OverviewState,
 
+    /// State of the tablet mode switch.
+    pub tablet_mode: TabletModeState,
+
     /// State of the config.
     pub config: ConfigState,
 }
@@ -62,6 +68,13 @@ pub struct WindowsState {
     pub windows: HashMap<u64, Window>,
 }
 
+/// The outputs state communicated over the event stream.
+#[derive(Debug, Default)]
+pub struct OutputsState {
+    /// Map from an output name to the output.
+    pub outputs: HashMap<String, Output>,
+}
+
 /// The keyboard layout state communicated over the event stream.
 #[derive(Debug, Default)]
 pub struct KeyboardLayoutsState {
@@ -76,6 +89,13 @@ pub struct OverviewState {
     pub is_open: bool,
 }
 
+/// The tablet mode state communicated over the event stream.
+#[derive(Debug, Default)]
+pub struct TabletModeState {
+    /// Whether the laptop is in tablet mode.
+    pub is_tablet_mode: bool,
+}
+
 /// The config state communicated over the event stream.
 #[derive(Debug, Default)]
 pub struct ConfigState {
@@ -88,8 +108,10 @@ impl EventStreamStatePart for EventStreamState {
         let mut events = Vec::new();
         events.extend(self.workspaces.replicate());
         events.extend(self.windows.replicate());
+        events.extend(self.outputs.replicate());
         events.extend(self.keyboard_layouts.replicate());
         events.extend(self.overview.replicate());
+        events.extend(self.tablet_mode.replicate());
         events.extend(self.config.replicate());
         events
     }
@@ -97,8 +119,10 @@ impl EventStreamStatePart for EventStreamState {
     fn apply(&mut self, event: Event) -> Option<Event> {
         let event = self.workspaces.apply(event)?;
         let event = self.windows.apply(event)?;
+        let event = self.outputs.apply(event)?;
         let event = self.keyboard_layouts.apply(event)?;
         let event = self.overview.apply(event)?;
+        let event = self.tablet_mode.apply(event)?;
         let event = self.config.apply(event)?;
         Some(event)
     }
@@ -225,6 +249,26 @@ impl EventStreamStatePart for WindowsState {
     }
 }
 
+impl EventStreamStatePart for OutputsState {
+    fn replicate(&self) -> Vec<Event> {
+        let outputs = self.outputs.values().cloned().collect();
+        vec![Event::OutputsChanged { outputs }]
+    }
+
+    fn apply(&mut self, event: Event) -> Option<Event> {
+        match event {
+            Event::OutputsChanged { outputs } => {
+                self.outputs = outputs
+                    .into_iter()
+                    .map(|output| (output.name.clone(), output))
+                    .collect();
+            }
+            event => return Some(event),
+        }
+        None
+    }
+}
+
 impl EventStreamStatePart for KeyboardLayoutsState {
     fn replicate(&self) -> Vec<Event> {
         if let Some(keyboard_layouts) = self.keyboard_layouts.clone() {
@@ -268,6 +312,24 @@ impl EventStreamStatePart for OverviewState {
     }
 }
 
+impl EventStreamStatePart for TabletModeState {
+    fn replicate(&self) -> Vec<Event> {
+        vec![Event::TabletModeChanged {
+            is_tablet_mode: self.is_tablet_mode,
+        }]
+    }
+
+    fn apply(&mut self, event: Event) -> Option<Event> {
+        match event {
+            Event::TabletModeChanged { is_tablet_mode } => {
+                self.is_tablet_mode = is_tablet_mode;
+            }
+            event => return Some(event),
+        }
+        None
+    }
+}
+
 impl EventStreamStatePart for ConfigState {
     fn replicate(&self) -> Vec<Event> {
         vec![Event::ConfigLoaded {
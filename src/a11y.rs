@@ -180,6 +180,13 @@ impl Niri {
             nodes.push((ID_ANNOUNCEMENT, node));
         }
 
+        // The dialog text depends on what it confirms.
+        if update_focus && focus == ID_EXIT_CONFIRM_DIALOG {
+            let confirmation = self.exit_confirm_dialog.confirmation();
+            let node = crate::ui::exit_confirm_dialog::a11y_node(confirmation);
+            nodes.push((ID_EXIT_CONFIRM_DIALOG, node));
+        }
+
         if focus == ID_MRU {
             // Ideally MRU would be a Group with a child Button for a window, but I've no idea how
             // to make it work reliably. When I did it that way, there were two issues:
@@ -301,7 +308,8 @@ impl Niri {
         let mut screenshot_ui = Node::new(Role::Group);
         screenshot_ui.set_label("Screenshot UI");
 
-        let exit_confirm_dialog = crate::ui::exit_confirm_dialog::a11y_node();
+        let confirmation = self.exit_confirm_dialog.confirmation();
+        let exit_confirm_dialog = crate::ui::exit_confirm_dialog::a11y_node(confirmation);
 
         let mut overview = Node::new(Role::Group);
         overview.set_label("Overview");
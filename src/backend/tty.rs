@@ -445,7 +445,7 @@ impl Tty {
         event_loop
             .insert_source(input_backend, |mut event, _, state| {
                 state.process_libinput_event(&mut event);
-                state.process_input_event(event);
+                state.process_traced_input_event(event);
             })
             .unwrap();
 
@@ -1711,6 +1711,7 @@ impl Tty {
                 }
 
                 feedback.presented::<_, smithay::utils::Monotonic>(time, refresh, seq, flags);
+                niri.input_latency.presented(&output, time);
 
                 if !presentation_time.is_zero() {
                     let misprediction_s =
@@ -3,7 +3,9 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 use clap_complete::Shell;
-use niri_ipc::{Action, OutputAction};
+use niri_ipc::{
+    Action, GestureBindChange, GestureDirection, GestureKind, OutputAction, WatchResource,
+};
 
 use crate::utils::version;
 
@@ -62,19 +64,34 @@ pub enum Sub {
 #[derive(Subcommand)]
 pub enum Msg {
     /// List connected outputs.
-    Outputs,
+    Outputs {
+        /// Print the current output settings as config sections instead.
+        ///
+        /// Copy them into the config file to keep the changes made with `niri msg output`.
+        #[arg(long)]
+        kdl: bool,
+    },
     /// List workspaces.
     Workspaces,
     /// List open windows.
     Windows,
     /// List open layer-shell surfaces.
     Layers,
+    /// List the marks set on windows.
+    Marks,
     /// Get the configured keyboard layouts.
     KeyboardLayouts,
     /// Print information about the focused output.
     FocusedOutput,
     /// Print information about the focused window.
-    FocusedWindow,
+    FocusedWindow {
+        /// Keep running and print the focused window again every time it changes.
+        ///
+        /// A change is a different window getting focused, or the focused window changing its
+        /// title or app ID.
+        #[arg(long)]
+        watch: bool,
+    },
     /// Pick a window with the mouse and print information about it.
     PickWindow,
     /// Pick a color from the screen with the mouse.
@@ -84,6 +101,14 @@ pub enum Msg {
         #[command(subcommand)]
         action: Action,
     },
+    /// Perform several actions together, between two frames.
+    ///
+    /// If any of the actions is invalid, none of them run.
+    Actions {
+        /// JSON array of actions in the IPC format, for example `[{"FocusWorkspaceDown":{}}]`.
+        #[arg()]
+        actions: String,
+    },
     /// Change output configuration temporarily.
     ///
     /// The configuration is changed temporarily and not saved into the config file. If the output
@@ -99,14 +124,86 @@ pub enum Msg {
         #[command(subcommand)]
         action: OutputAction,
     },
+    /// Rename or reorder a workspace by its id.
+    ///
+    /// Run `niri msg workspaces --json` to see the workspace ids.
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceMsg,
+    },
     /// Start continuously receiving events from the compositor.
     EventStream,
+    /// Print the current state of some resources, then every change to them.
+    ///
+    /// Every change has a sequence number, and the state has the sequence number of the last
+    /// change it includes.
+    Watch {
+        /// Resources to watch, or all of them if empty.
+        #[arg(value_enum)]
+        resources: Vec<WatchResource>,
+    },
     /// Print the version of the running niri instance.
     Version,
     /// Request an error from the running niri instance.
     RequestError,
     /// Print the overview state.
     OverviewState,
+    /// Print the input latency statistics.
+    ///
+    /// Requires the `trace-input-latency` debug flag.
+    InputLatency,
+    /// List the gesture binds, including the changes made with `niri msg gesture-bind`.
+    GestureBinds,
+    /// Change a gesture bind temporarily.
+    ///
+    /// The change is not saved into the config file. It takes precedence over the gesture binds
+    /// from the config, including the ones in profiles, and stays through config reloads until
+    /// it is reset.
+    GestureBind {
+        /// Kind of the gesture: `swipe`, `pinch` or `hold`.
+        #[arg()]
+        kind: GestureKind,
+        /// Number of fingers.
+        #[arg()]
+        fingers: u8,
+        /// Direction of the gesture, for example `down` or `in`.
+        #[arg(long)]
+        direction: Option<GestureDirection>,
+        /// Whether the swipe must end with a fling.
+        #[arg(long)]
+        fling: bool,
+        /// Change to make.
+        #[command(subcommand)]
+        change: GestureBindChange,
+    },
+    /// Undo all changes made with `niri msg gesture-bind`.
+    ResetGestureBinds,
+    /// Print the compositor state to attach to bug reports.
+    ///
+    /// Window titles are left out.
+    DumpState,
+}
+
+#[derive(Subcommand)]
+pub enum WorkspaceMsg {
+    /// Set the name of a workspace.
+    Rename {
+        /// Id of the workspace.
+        #[arg()]
+        id: u64,
+        /// New name for the workspace.
+        #[arg()]
+        name: String,
+    },
+    /// Move a workspace to a new index on its monitor.
+    Move {
+        /// Id of the workspace.
+        #[arg()]
+        id: u64,
+        /// New index for the workspace, counting from 1.
+        #[arg()]
+        index: usize,
+    },
 }
 
 #[derive(Clone, Debug, clap::ValueEnum)]
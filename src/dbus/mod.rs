@@ -1,3 +1,5 @@
+use std::sync::mpsc;
+
 use zbus::blocking::Connection;
 use zbus::object_server::Interface;
 
@@ -11,6 +13,7 @@ pub mod gnome_shell_introspect;
 pub mod gnome_shell_screenshot;
 pub mod mutter_display_config;
 pub mod mutter_service_channel;
+pub mod niri_compositor;
 
 #[cfg(feature = "xdp-gnome-screencast")]
 pub mod mutter_screen_cast;
@@ -22,6 +25,7 @@ use self::freedesktop_screensaver::ScreenSaver;
 use self::gnome_shell_introspect::Introspect;
 use self::mutter_display_config::DisplayConfig;
 use self::mutter_service_channel::ServiceChannel;
+use self::niri_compositor::{Compositor, CompositorChange};
 
 trait Start: Interface {
     fn start(self) -> anyhow::Result<zbus::blocking::Connection>;
@@ -39,6 +43,8 @@ pub struct DBusServers {
     pub conn_login1: Option<Connection>,
     pub conn_locale1: Option<Connection>,
     pub conn_keyboard_monitor: Option<Connection>,
+    pub conn_compositor: Option<Connection>,
+    pub compositor_signals: Option<mpsc::Sender<CompositorChange>>,
 }
 
 impl DBusServers {
@@ -131,6 +137,22 @@ impl DBusServers {
                 dbus.conn_screen_cast = try_start(screen_cast);
             }
 
+            let (to_niri, from_compositor) = calloop::channel::channel();
+            niri.event_loop
+                .insert_source(from_compositor, move |event, _, state| match event {
+                    calloop::channel::Event::Msg(msg) => state.on_compositor_msg(msg),
+                    calloop::channel::Event::Closed => (),
+                })
+                .unwrap();
+            let compositor = Compositor::new(to_niri);
+            dbus.conn_compositor = try_start(compositor);
+            if let Some(conn) = &dbus.conn_compositor {
+                match niri_compositor::start_signal_emitter(conn.clone()) {
+                    Ok(signals) => dbus.compositor_signals = Some(signals),
+                    Err(err) => warn!("error starting the compositor signal emitter: {err:?}"),
+                }
+            }
+
             let keyboard_monitor = KeyboardMonitor::new();
             if let Some(x) = try_start(keyboard_monitor.clone()) {
                 dbus.conn_keyboard_monitor = Some(x);
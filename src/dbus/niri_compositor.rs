@@ -0,0 +1,191 @@
+use std::sync::mpsc;
+use std::{iter, thread};
+
+use zbus::fdo::{self, RequestNameFlags};
+use zbus::interface;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::{SerializeDict, Type, Value};
+
+use super::Start;
+
+/// niri's own D-Bus interface for tools that don't speak the niri IPC socket.
+pub struct Compositor {
+    to_niri: calloop::channel::Sender<CompositorToNiri>,
+}
+
+pub enum CompositorToNiri {
+    GetFocusedWindow(async_channel::Sender<WindowProperties>),
+    GetWorkspaces(async_channel::Sender<Vec<WorkspaceProperties>>),
+    DoAction(niri_ipc::Action),
+}
+
+/// State change announced with a signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositorChange {
+    FocusedWindow,
+    Workspaces,
+}
+
+/// What the signals announced on the previous refresh.
+#[derive(Debug, Default)]
+pub struct CompositorSignalState {
+    pub focused_window: Option<WindowProperties>,
+    pub workspaces: Vec<WorkspaceProperties>,
+}
+
+#[derive(Debug, Default, SerializeDict, Type, Value)]
+#[zvariant(signature = "dict")]
+pub struct WindowProperties {
+    /// Window id, same as in the niri IPC.
+    pub id: Option<u64>,
+    pub title: Option<String>,
+    #[zvariant(rename = "app-id")]
+    pub app_id: Option<String>,
+    #[zvariant(rename = "workspace-id")]
+    pub workspace_id: Option<u64>,
+}
+
+#[derive(Debug, SerializeDict, Type, Value)]
+#[zvariant(signature = "dict")]
+pub struct WorkspaceProperties {
+    /// Workspace id, same as in the niri IPC.
+    pub id: u64,
+    /// Index of the workspace on its output, starting from 1.
+    pub idx: u8,
+    pub name: Option<String>,
+    pub output: Option<String>,
+    #[zvariant(rename = "is-active")]
+    pub is_active: bool,
+    #[zvariant(rename = "is-focused")]
+    pub is_focused: bool,
+}
+
+#[interface(name = "org.niri.Compositor")]
+impl Compositor {
+    /// Returns the focused window, or an empty dict if no window is focused.
+    async fn get_focused_window(&self) -> fdo::Result<WindowProperties> {
+        self.request(CompositorToNiri::GetFocusedWindow).await
+    }
+
+    async fn get_workspaces(&self) -> fdo::Result<Vec<WorkspaceProperties>> {
+        self.request(CompositorToNiri::GetWorkspaces).await
+    }
+
+    /// Performs an action, written as JSON in the same format as the niri IPC.
+    ///
+    /// For example, `{"FocusColumnRight":{}}`.
+    async fn do_action(&self, action: &str) -> fdo::Result<()> {
+        let action: niri_ipc::Action = serde_json::from_str(action)
+            .map_err(|err| fdo::Error::InvalidArgs(format!("error parsing action: {err}")))?;
+        crate::ipc::server::validate_action(&action).map_err(fdo::Error::InvalidArgs)?;
+
+        if let Err(err) = self.to_niri.send(CompositorToNiri::DoAction(action)) {
+            warn!("error sending message to niri: {err:?}");
+            return Err(fdo::Error::Failed("internal error".to_owned()));
+        }
+
+        Ok(())
+    }
+
+    /// The focused window, or its title or app id, changed.
+    #[zbus(signal)]
+    pub async fn focused_window_changed(ctxt: &SignalEmitter<'_>) -> zbus::Result<()>;
+
+    /// Workspaces were added, removed, or changed.
+    #[zbus(signal)]
+    pub async fn workspaces_changed(ctxt: &SignalEmitter<'_>) -> zbus::Result<()>;
+}
+
+impl Compositor {
+    pub fn new(to_niri: calloop::channel::Sender<CompositorToNiri>) -> Self {
+        Self { to_niri }
+    }
+
+    /// Sends a request to niri and waits for the reply.
+    ///
+    /// Every request carries its own reply channel, so concurrent calls can't receive each
+    /// other's replies.
+    async fn request<T>(
+        &self,
+        msg: impl FnOnce(async_channel::Sender<T>) -> CompositorToNiri,
+    ) -> fdo::Result<T> {
+        let (tx, rx) = async_channel::bounded(1);
+        if let Err(err) = self.to_niri.send(msg(tx)) {
+            warn!("error sending message to niri: {err:?}");
+            return Err(fdo::Error::Failed("internal error".to_owned()));
+        }
+
+        rx.recv().await.map_err(|err| {
+            warn!("error receiving message from niri: {err:?}");
+            fdo::Error::Failed("internal error".to_owned())
+        })
+    }
+}
+
+impl Start for Compositor {
+    fn start(self) -> anyhow::Result<zbus::blocking::Connection> {
+        let conn = zbus::blocking::Connection::session()?;
+        let flags = RequestNameFlags::AllowReplacement
+            | RequestNameFlags::ReplaceExisting
+            | RequestNameFlags::DoNotQueue;
+
+        conn.object_server().at("/org/niri/Compositor", self)?;
+        conn.request_name_with_flags("org.niri.Compositor", flags)?;
+
+        Ok(conn)
+    }
+}
+
+/// Starts the thread that emits the change signals.
+///
+/// The thread emits the signals in the order it receives the changes. Changes that pile up while
+/// it is busy are coalesced into one signal each.
+pub fn start_signal_emitter(
+    conn: zbus::blocking::Connection,
+) -> anyhow::Result<mpsc::Sender<CompositorChange>> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::Builder::new()
+        .name("Compositor Signal Emitter".to_owned())
+        .spawn(move || {
+            let iface = match conn
+                .object_server()
+                .interface::<_, Compositor>("/org/niri/Compositor")
+            {
+                Ok(iface) => iface,
+                Err(err) => {
+                    warn!("error getting Compositor interface: {err:?}");
+                    return;
+                }
+            };
+
+            while let Ok(change) = rx.recv() {
+                let mut changes = Vec::new();
+                for change in iter::once(change).chain(rx.try_iter()) {
+                    if !changes.contains(&change) {
+                        changes.push(change);
+                    }
+                }
+
+                let _span = tracy_client::span!("CompositorChanged");
+                async_io::block_on(async {
+                    let emitter = iface.signal_emitter();
+                    for change in changes {
+                        let res = match change {
+                            CompositorChange::FocusedWindow => {
+                                Compositor::focused_window_changed(emitter).await
+                            }
+                            CompositorChange::Workspaces => {
+                                Compositor::workspaces_changed(emitter).await
+                            }
+                        };
+                        if let Err(err) = res {
+                            warn!("error emitting {change:?} change: {err:?}");
+                        }
+                    }
+                });
+            }
+        })?;
+
+    Ok(tx)
+}
@@ -198,9 +198,23 @@ impl CompositorHandler for State {
                     let mapped = Mapped::new(window, rules, hook);
                     let window = mapped.window.clone();
 
+                    // Windows opened on a specific output or workspace don't swallow anything.
+                    let swallowed = if parent.is_none()
+                        && !is_floating
+                        && output.is_none()
+                        && workspace_id.is_none()
+                    {
+                        self.window_to_swallow(&mapped)
+                    } else {
+                        None
+                    };
+
                     let target = if let Some(p) = &parent {
                         // Open dialogs next to their parent window.
                         AddWindowTarget::NextTo(p)
+                    } else if let Some(swallowed) = &swallowed {
+                        // Take the place of the swallowed window.
+                        AddWindowTarget::NextTo(swallowed)
                     } else if let Some(id) = workspace_id {
                         AddWindowTarget::Workspace(id)
                     } else if let Some(output) = &output {
@@ -219,6 +233,10 @@ impl CompositorHandler for State {
                     );
                     let output = output.cloned();
 
+                    if let Some(swallowed) = swallowed {
+                        self.swallow_window(&swallowed, &window);
+                    }
+
                     // The window state cannot contain Fullscreen and Maximized at once. Therefore,
                     // if the window ended up fullscreen, then we only know that it is also
                     // maximized from the is_pending_maximized variable. Tell the layout about it
@@ -256,6 +274,14 @@ impl CompositorHandler for State {
                 return;
             }
 
+            if self.swallowed_window_commit(surface) {
+                return;
+            }
+
+            if self.minimized_window_commit(surface) {
+                return;
+            }
+
             // This is a commit of a previously-mapped root or a non-toplevel root.
             if let Some((mapped, output)) = self.niri.layout.find_window_and_output(surface) {
                 let window = mapped.window.clone();
@@ -292,6 +318,7 @@ impl CompositorHandler for State {
                         .stop_casts_for_target(CastTarget::Window { id: id.get() });
 
                     self.niri.window_mru_ui.remove_window(id);
+                    self.restore_swallowed_window(&window);
                     self.niri.layout.remove_window(&window, transaction.clone());
                     self.add_default_dmabuf_pre_commit_hook(surface);
 
@@ -264,7 +264,12 @@ impl KeyboardShortcutsInhibitHandler for State {
 
     fn new_inhibitor(&mut self, inhibitor: KeyboardShortcutsInhibitor) {
         // FIXME: show a confirmation dialog with a "remember for this application" kind of toggle.
-        inhibitor.activate();
+        if self
+            .niri
+            .allows_keyboard_shortcuts_inhibit(inhibitor.wl_surface())
+        {
+            inhibitor.activate();
+        }
         self.niri
             .keyboard_shortcuts_inhibiting_surfaces
             .insert(inhibitor.wl_surface().clone(), inhibitor);
@@ -526,7 +531,9 @@ impl ForeignToplevelHandler for State {
     }
 
     fn activate(&mut self, wl_surface: WlSurface) {
-        if let Some((mapped, _)) = self.niri.layout.find_window_and_output(&wl_surface) {
+        if let Some(window) = self.find_minimized_window(&wl_surface) {
+            self.unminimize_window(&window);
+        } else if let Some((mapped, _)) = self.niri.layout.find_window_and_output(&wl_surface) {
             let window = mapped.window.clone();
             self.niri.layout.activate_window(&window);
             self.niri.layer_shell_on_demand_focus = None;
@@ -535,7 +542,9 @@ impl ForeignToplevelHandler for State {
     }
 
     fn close(&mut self, wl_surface: WlSurface) {
-        if let Some((mapped, _)) = self.niri.layout.find_window_and_output(&wl_surface) {
+        if let Some(window) = self.find_minimized_window(&wl_surface) {
+            window.toplevel().expect("no X11 support").send_close();
+        } else if let Some((mapped, _)) = self.niri.layout.find_window_and_output(&wl_surface) {
             mapped.toplevel().send_close();
         }
     }
@@ -580,6 +589,19 @@ impl ForeignToplevelHandler for State {
             self.niri.layout.set_maximized(&window, false);
         }
     }
+
+    fn set_minimized(&mut self, wl_surface: WlSurface) {
+        if let Some((mapped, _)) = self.niri.layout.find_window_and_output(&wl_surface) {
+            let window = mapped.window.clone();
+            self.minimize_window(&window);
+        }
+    }
+
+    fn unset_minimized(&mut self, wl_surface: WlSurface) {
+        if let Some(window) = self.find_minimized_window(&wl_surface) {
+            self.unminimize_window(&window);
+        }
+    }
 }
 delegate_foreign_toplevel!(State);
 
@@ -817,6 +817,17 @@ impl XdgShellHandler for State {
         }
     }
 
+    fn minimize_request(&mut self, toplevel: ToplevelSurface) {
+        if let Some((mapped, _)) = self
+            .niri
+            .layout
+            .find_window_and_output(toplevel.wl_surface())
+        {
+            let window = mapped.window.clone();
+            self.minimize_window(&window);
+        }
+    }
+
     fn toplevel_destroyed(&mut self, surface: ToplevelSurface) {
         if self
             .niri
@@ -828,6 +839,14 @@ impl XdgShellHandler for State {
             return;
         }
 
+        if self.swallowed_window_destroyed(surface.wl_surface()) {
+            return;
+        }
+
+        if self.minimized_window_destroyed(surface.wl_surface()) {
+            return;
+        }
+
         let win_out = self
             .niri
             .layout
@@ -862,6 +881,7 @@ impl XdgShellHandler for State {
         let was_active = active_window == Some(&window);
 
         self.niri.window_mru_ui.remove_window(id);
+        self.restore_swallowed_window(&window);
         self.niri.layout.remove_window(&window, transaction.clone());
         self.add_default_dmabuf_pre_commit_hook(surface.wl_surface());
 
@@ -1031,6 +1051,7 @@ impl State {
             &config.window_rules,
             WindowRef::Unmapped(unmapped),
             self.niri.is_at_startup,
+            self.niri.window_rule_profile.as_deref(),
         );
 
         let Unmapped { window, state, .. } = unmapped;
@@ -1367,6 +1388,7 @@ impl State {
                 window_rules,
                 WindowRef::Unmapped(unmapped),
                 self.niri.is_at_startup,
+                self.niri.window_rule_profile.as_deref(),
             );
             if let InitialConfigureState::Configured { rules, .. } = &mut unmapped.state {
                 *rules = new_rules;
@@ -1376,7 +1398,8 @@ impl State {
             .layout
             .find_window_and_output_mut(toplevel.wl_surface())
         {
-            if mapped.recompute_window_rules(window_rules, self.niri.is_at_startup) {
+            let profile = self.niri.window_rule_profile.as_deref();
+            if mapped.recompute_window_rules(window_rules, self.niri.is_at_startup, profile) {
                 drop(config);
                 let output = output.cloned();
                 let window = mapped.window.clone();
@@ -0,0 +1,228 @@
+//! Sticky keys and slow keys.
+//!
+//! Both sit in front of the regular keyboard handling, so the binds and the clients see the keys
+//! as they come out of here. Slow keys hold back a press until the key has been down long enough,
+//! and drop the key entirely if it's released sooner. Sticky keys latch a tapped modifier and
+//! press it around the next key.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use calloop::timer::{TimeoutAction, Timer};
+use calloop::RegistrationToken;
+use smithay::backend::input::{KeyState, Keycode};
+
+use super::modifier_tap::is_modifier_keysym;
+use crate::niri::State;
+use crate::utils::get_monotonic_time;
+
+/// State of the keyboard accessibility options.
+#[derive(Debug, Default)]
+pub struct AccessibilityKeys {
+    sticky: StickyKeys,
+    /// Keys held down for less than the slow keys delay, with their acceptance timers.
+    slow_pending: HashMap<Keycode, RegistrationToken>,
+    /// Keys whose press was accepted by slow keys and is waiting for the release.
+    slow_accepted: HashSet<Keycode>,
+}
+
+/// Tracks tapped modifiers for sticky keys.
+#[derive(Debug, Default)]
+struct StickyKeys {
+    /// Modifiers pressed with no other key since; they latch when released.
+    tapping: Vec<Keycode>,
+    /// Latched modifiers to press around the next key.
+    latched: Vec<Keycode>,
+}
+
+impl StickyKeys {
+    fn modifier_pressed(&mut self, key_code: Keycode) {
+        if !self.tapping.contains(&key_code) {
+            self.tapping.push(key_code);
+        }
+    }
+
+    /// Handles a modifier release; returns whether it completed a tap.
+    fn modifier_released(&mut self, key_code: Keycode) -> bool {
+        let Some(idx) = self.tapping.iter().position(|k| *k == key_code) else {
+            return false;
+        };
+        self.tapping.remove(idx);
+
+        // Tapping a latched modifier again unlatches it.
+        if let Some(idx) = self.latched.iter().position(|k| *k == key_code) {
+            self.latched.remove(idx);
+        } else {
+            self.latched.push(key_code);
+        }
+
+        true
+    }
+
+    /// Handles a non-modifier key press; returns the latched modifiers to apply to it.
+    fn key_pressed(&mut self) -> Vec<Keycode> {
+        // The modifiers held down now are used in a chord, so they aren't taps.
+        self.tapping.clear();
+        std::mem::take(&mut self.latched)
+    }
+}
+
+impl State {
+    pub(super) fn accessibility_keys_input(
+        &mut self,
+        key_code: Keycode,
+        state: KeyState,
+        time: u32,
+        consumed_by_a11y: &mut bool,
+    ) {
+        let slow_keys_ms = self
+            .niri
+            .config
+            .borrow()
+            .input
+            .keyboard
+            .accessibility
+            .slow_keys_ms;
+
+        let keys = &mut self.niri.accessibility_keys;
+        match state {
+            KeyState::Pressed => {
+                if let Some(delay) = slow_keys_ms.filter(|ms| *ms > 0) {
+                    let timer = Timer::from_duration(Duration::from_millis(u64::from(delay)));
+                    let token = self
+                        .niri
+                        .event_loop
+                        .insert_source(timer, move |_, _, state| {
+                            let keys = &mut state.niri.accessibility_keys;
+                            keys.slow_pending.remove(&key_code);
+                            keys.slow_accepted.insert(key_code);
+
+                            let time = get_monotonic_time().as_millis() as u32;
+                            state.sticky_keys_input(key_code, KeyState::Pressed, time, &mut false);
+                            TimeoutAction::Drop
+                        })
+                        .unwrap();
+
+                    let keys = &mut self.niri.accessibility_keys;
+                    if let Some(token) = keys.slow_pending.insert(key_code, token) {
+                        self.niri.event_loop.remove(token);
+                    }
+                    return;
+                }
+            }
+            KeyState::Released => {
+                // Released too soon, drop the key.
+                if let Some(token) = keys.slow_pending.remove(&key_code) {
+                    self.niri.event_loop.remove(token);
+                    return;
+                }
+                keys.slow_accepted.remove(&key_code);
+            }
+        }
+
+        self.sticky_keys_input(key_code, state, time, consumed_by_a11y);
+    }
+
+    fn sticky_keys_input(
+        &mut self,
+        key_code: Keycode,
+        state: KeyState,
+        time: u32,
+        consumed_by_a11y: &mut bool,
+    ) {
+        let config = self.niri.config.borrow();
+        let sticky_keys = config.input.keyboard.accessibility.sticky_keys;
+        drop(config);
+
+        if !sticky_keys {
+            self.niri.accessibility_keys.sticky = StickyKeys::default();
+            self.on_key(key_code, state, time, consumed_by_a11y);
+            return;
+        }
+
+        if self.is_sticky_modifier(key_code) {
+            // Deliver modifiers as usual, so holding them down still works.
+            let sticky = &mut self.niri.accessibility_keys.sticky;
+            match state {
+                KeyState::Pressed => sticky.modifier_pressed(key_code),
+                KeyState::Released => {
+                    sticky.modifier_released(key_code);
+                }
+            }
+            self.on_key(key_code, state, time, consumed_by_a11y);
+            return;
+        }
+
+        if state == KeyState::Released {
+            self.on_key(key_code, state, time, consumed_by_a11y);
+            return;
+        }
+
+        let latched = self.niri.accessibility_keys.sticky.key_pressed();
+        for &modifier in &latched {
+            self.inject_key(modifier, KeyState::Pressed);
+        }
+        self.on_key(key_code, state, time, consumed_by_a11y);
+        for &modifier in latched.iter().rev() {
+            self.inject_key(modifier, KeyState::Released);
+        }
+    }
+
+    /// Returns whether the key is a modifier that sticky keys can latch.
+    fn is_sticky_modifier(&mut self, key_code: Keycode) -> bool {
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        keyboard.with_xkb_state(self, |context| {
+            let xkb = context.xkb().lock().unwrap();
+            let layout = xkb.active_layout();
+            // SAFETY: we're not changing the ref count.
+            let keymap = unsafe { xkb.keymap() };
+
+            // Lock keys like Caps Lock toggle on their own, so they don't latch.
+            keymap
+                .key_get_syms_by_level(key_code, layout.0, 0)
+                .iter()
+                .any(|sym| is_modifier_keysym(*sym))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sticky_modifier_tap_latches() {
+        let shift = Keycode::from(50u32);
+
+        let mut sticky = StickyKeys::default();
+        sticky.modifier_pressed(shift);
+        assert!(sticky.modifier_released(shift));
+        assert_eq!(sticky.key_pressed(), [shift]);
+
+        // The latch applies to one key only.
+        assert!(sticky.key_pressed().is_empty());
+    }
+
+    #[test]
+    fn sticky_modifier_chord_doesnt_latch() {
+        let shift = Keycode::from(50u32);
+
+        let mut sticky = StickyKeys::default();
+        sticky.modifier_pressed(shift);
+        assert!(sticky.key_pressed().is_empty());
+        assert!(!sticky.modifier_released(shift));
+        assert!(sticky.key_pressed().is_empty());
+    }
+
+    #[test]
+    fn sticky_modifier_second_tap_unlatches() {
+        let shift = Keycode::from(50u32);
+
+        let mut sticky = StickyKeys::default();
+        for _ in 0..2 {
+            sticky.modifier_pressed(shift);
+            sticky.modifier_released(shift);
+        }
+        assert!(sticky.key_pressed().is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! Double-press binds.
+//!
+//! A double-press bind triggers on the second press of its key within the bind's time window.
+//! When the key also has a regular single-press bind, the first press holds it back until the
+//! window runs out, so that a double press doesn't trigger both binds.
+
+use std::time::Duration;
+
+use calloop::timer::{TimeoutAction, Timer};
+use calloop::RegistrationToken;
+use niri_config::{Bind, Key, Trigger};
+use smithay::input::keyboard::ModifiersState;
+
+use super::{find_double_press_bind, make_binds_iter, modifiers_from_state};
+use crate::niri::State;
+
+#[derive(Debug, Default)]
+pub struct DoublePressTracker {
+    /// Key of the double-press bind that was pressed once, with the time of the press.
+    first_press: Option<(Key, u32)>,
+    /// Single-press bind held back by the first press, with its timer.
+    held_back: Option<(Bind, RegistrationToken)>,
+}
+
+/// What a press means for the double-press binds.
+pub(super) enum DoublePress {
+    /// The key has no double-press bind.
+    None,
+    /// The press completes a double press of this bind.
+    Double(Bind),
+    /// The press is the first one; a single-press bind on the key should wait this long.
+    First(Duration),
+}
+
+impl DoublePressTracker {
+    /// Feeds a press of a double-press bind key.
+    ///
+    /// Returns whether it completes a double press; otherwise, it counts as the first press.
+    pub fn press(&mut self, key: Key, window: Duration, time: u32) -> bool {
+        if let Some((first_key, first_time)) = self.first_press.take() {
+            let elapsed = Duration::from_millis(u64::from(time.wrapping_sub(first_time)));
+            if first_key == key && elapsed <= window {
+                return true;
+            }
+        }
+
+        self.first_press = Some((key, time));
+        false
+    }
+
+    /// Forgets the first press, for example when another key is pressed.
+    pub fn reset(&mut self) {
+        self.first_press = None;
+    }
+}
+
+impl State {
+    /// Feeds a press to the double-press binds.
+    pub(super) fn double_press_input(
+        &mut self,
+        trigger: Trigger,
+        mods: ModifiersState,
+        time: u32,
+    ) -> DoublePress {
+        let config = self.niri.config.borrow();
+        let mod_key = self.backend.mod_key(&config);
+        let bindings = make_binds_iter(
+            &config,
+            &mut self.niri.window_mru_ui,
+            self.niri.bind_mode.as_deref(),
+            modifiers_from_state(mods),
+        );
+        let bind = find_double_press_bind(bindings, mod_key, trigger, mods);
+        drop(config);
+
+        let Some(mut bind) = bind else {
+            self.niri.double_press.reset();
+            self.flush_held_back_bind();
+            return DoublePress::None;
+        };
+
+        let window = bind.double_press.unwrap();
+        if self.niri.double_press.press(bind.key, window, time) {
+            self.drop_held_back_bind();
+            // Holding down the second press shouldn't repeat the bind.
+            bind.repeat = false;
+            DoublePress::Double(bind)
+        } else {
+            self.flush_held_back_bind();
+            DoublePress::First(window)
+        }
+    }
+
+    /// Holds back a single-press bind until the double press window runs out.
+    pub(super) fn hold_back_bind(&mut self, bind: Bind, window: Duration) {
+        self.flush_held_back_bind();
+
+        let timer = Timer::from_duration(window);
+        let token = self
+            .niri
+            .event_loop
+            .insert_source(timer, |_, _, state| {
+                if let Some((bind, _)) = state.niri.double_press.held_back.take() {
+                    state.handle_bind(bind);
+                }
+                TimeoutAction::Drop
+            })
+            .unwrap();
+        self.niri.double_press.held_back = Some((bind, token));
+    }
+
+    /// Triggers the held back single-press bind right away, if any.
+    pub(super) fn flush_held_back_bind(&mut self) {
+        if let Some((bind, token)) = self.niri.double_press.held_back.take() {
+            self.niri.event_loop.remove(token);
+            self.handle_bind(bind);
+        }
+    }
+
+    /// Drops the held back single-press bind when the double press completes.
+    pub(super) fn drop_held_back_bind(&mut self) {
+        if let Some((_, token)) = self.niri.double_press.held_back.take() {
+            self.niri.event_loop.remove(token);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use niri_config::Modifiers;
+    use smithay::input::keyboard::Keysym;
+
+    use super::*;
+
+    const WINDOW: Duration = Duration::from_millis(300);
+
+    fn key(keysym: Keysym) -> Key {
+        Key {
+            trigger: Trigger::Keysym(keysym),
+            modifiers: Modifiers::COMPOSITOR,
+        }
+    }
+
+    #[test]
+    fn double_press_within_window() {
+        let mut tracker = DoublePressTracker::default();
+        assert!(!tracker.press(key(Keysym::a), WINDOW, 0));
+        assert!(tracker.press(key(Keysym::a), WINDOW, 200));
+
+        // The double press starts over.
+        assert!(!tracker.press(key(Keysym::a), WINDOW, 250));
+    }
+
+    #[test]
+    fn slow_second_press_is_first_press() {
+        let mut tracker = DoublePressTracker::default();
+        assert!(!tracker.press(key(Keysym::a), WINDOW, 0));
+        assert!(!tracker.press(key(Keysym::a), WINDOW, 500));
+        assert!(tracker.press(key(Keysym::a), WINDOW, 600));
+    }
+
+    #[test]
+    fn other_key_resets() {
+        let mut tracker = DoublePressTracker::default();
+        assert!(!tracker.press(key(Keysym::a), WINDOW, 0));
+        assert!(!tracker.press(key(Keysym::b), WINDOW, 100));
+        assert!(!tracker.press(key(Keysym::a), WINDOW, 200));
+
+        tracker.reset();
+        assert!(!tracker.press(key(Keysym::a), WINDOW, 250));
+    }
+}
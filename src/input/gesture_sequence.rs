@@ -0,0 +1,224 @@
+//! Multi-stage gesture sequences for the config gesture binds.
+//!
+//! Layered on top of the discrete touchpad and touchscreen gestures: every gesture that triggers
+//! is fed in as the next step, and a sequence triggers once all of its steps arrive in order. The
+//! caller abandons the sequence in progress when the next step doesn't arrive in time.
+
+use niri_config::gestures::{GestureSequence, GestureTrigger};
+use niri_config::Action;
+
+#[derive(Debug, Default)]
+pub struct GestureSequenceTracker {
+    /// Gestures of the sequence in progress, as they triggered.
+    steps: Vec<GestureTrigger>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SequenceTrigger {
+    /// Trigger the regular bind for this gesture.
+    Bind(GestureTrigger),
+    /// Trigger the action of a completed sequence.
+    Sequence(Action),
+}
+
+impl GestureSequenceTracker {
+    /// Feeds a gesture that triggered.
+    ///
+    /// Returns what should trigger right away, in order. The first gesture of a sequence holds
+    /// back its own bind until the sequence is abandoned.
+    pub fn step(
+        &mut self,
+        sequences: &[GestureSequence],
+        trigger: GestureTrigger,
+    ) -> Vec<SequenceTrigger> {
+        let mut triggers = Vec::new();
+
+        self.steps.push(trigger);
+        if !self.leads_to_sequence(sequences) {
+            // The sequence in progress broke off; see if this gesture starts a new one.
+            self.steps.pop();
+            triggers.extend(self.abandon());
+
+            self.steps.push(trigger);
+            if !self.leads_to_sequence(sequences) {
+                self.steps.clear();
+                triggers.push(SequenceTrigger::Bind(trigger));
+                return triggers;
+            }
+        }
+
+        if let Some(sequence) = sequences
+            .iter()
+            .find(|sequence| matches_steps(&sequence.steps, &self.steps))
+        {
+            self.steps.clear();
+            triggers.push(SequenceTrigger::Sequence(sequence.action.clone()));
+        }
+
+        triggers
+    }
+
+    /// Abandons the sequence in progress.
+    ///
+    /// Returns the held back bind if the sequence didn't get past its first gesture. Gestures of
+    /// a sequence abandoned later on don't trigger anything.
+    pub fn abandon(&mut self) -> Option<SequenceTrigger> {
+        let held = match &self.steps[..] {
+            [first] => Some(SequenceTrigger::Bind(*first)),
+            _ => None,
+        };
+        self.steps.clear();
+        held
+    }
+
+    /// Returns whether a sequence is in progress and waiting for the next gesture.
+    pub fn is_pending(&self) -> bool {
+        !self.steps.is_empty()
+    }
+
+    /// Describes the sequence in progress for the gesture debug overlay.
+    pub fn debug_text(&self) -> Option<String> {
+        if self.steps.is_empty() {
+            return None;
+        }
+
+        let steps: Vec<_> = self
+            .steps
+            .iter()
+            .map(|step| match step.direction {
+                Some(direction) => format!("{:?}-{} {direction:?}", step.kind, step.fingers),
+                None => format!("{:?}-{}", step.kind, step.fingers),
+            })
+            .collect();
+        Some(steps.join(", "))
+    }
+
+    fn leads_to_sequence(&self, sequences: &[GestureSequence]) -> bool {
+        sequences.iter().any(|sequence| {
+            sequence.steps.len() >= self.steps.len()
+                && matches_steps(&sequence.steps[..self.steps.len()], &self.steps)
+        })
+    }
+}
+
+/// Returns whether the gestures match the sequence steps.
+///
+/// Sequence steps never require a fling, so any swipe in the right direction matches.
+fn matches_steps(steps: &[GestureTrigger], gestures: &[GestureTrigger]) -> bool {
+    steps.len() == gestures.len()
+        && steps.iter().zip(gestures).all(|(step, gesture)| {
+            step.kind == gesture.kind
+                && step.fingers == gesture.fingers
+                && step.direction == gesture.direction
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use niri_config::{GestureDirection, GestureKind};
+
+    use super::*;
+
+    fn swipe(direction: GestureDirection) -> GestureTrigger {
+        GestureTrigger {
+            kind: GestureKind::Swipe,
+            fingers: 3,
+            direction: Some(direction),
+            fling: false,
+        }
+    }
+
+    fn hold() -> GestureTrigger {
+        GestureTrigger {
+            kind: GestureKind::Hold,
+            fingers: 3,
+            direction: None,
+            fling: false,
+        }
+    }
+
+    fn sequences() -> Vec<GestureSequence> {
+        vec![GestureSequence {
+            steps: vec![swipe(GestureDirection::Down), hold()],
+            action: Action::ToggleOverview,
+        }]
+    }
+
+    #[test]
+    fn sequence_triggers() {
+        let sequences = sequences();
+        let mut tracker = GestureSequenceTracker::default();
+
+        assert!(tracker
+            .step(&sequences, swipe(GestureDirection::Down))
+            .is_empty());
+        assert!(tracker.is_pending());
+        assert_eq!(
+            tracker.step(&sequences, hold()),
+            [SequenceTrigger::Sequence(Action::ToggleOverview)]
+        );
+        assert!(!tracker.is_pending());
+    }
+
+    #[test]
+    fn unrelated_gesture_triggers_right_away() {
+        let sequences = sequences();
+        let mut tracker = GestureSequenceTracker::default();
+
+        let left = swipe(GestureDirection::Left);
+        assert_eq!(
+            tracker.step(&sequences, left),
+            [SequenceTrigger::Bind(left)]
+        );
+        assert!(!tracker.is_pending());
+    }
+
+    #[test]
+    fn broken_sequence_releases_held_bind() {
+        let sequences = sequences();
+        let mut tracker = GestureSequenceTracker::default();
+
+        let down = swipe(GestureDirection::Down);
+        let left = swipe(GestureDirection::Left);
+        assert!(tracker.step(&sequences, down).is_empty());
+        assert_eq!(
+            tracker.step(&sequences, left),
+            [SequenceTrigger::Bind(down), SequenceTrigger::Bind(left)]
+        );
+
+        // Starts over when the breaking gesture begins a sequence itself.
+        assert!(tracker.step(&sequences, down).is_empty());
+        assert_eq!(
+            tracker.step(&sequences, down),
+            [SequenceTrigger::Bind(down)]
+        );
+        assert!(tracker.is_pending());
+    }
+
+    #[test]
+    fn timeout_releases_held_bind() {
+        let sequences = sequences();
+        let mut tracker = GestureSequenceTracker::default();
+
+        let down = swipe(GestureDirection::Down);
+        assert!(tracker.step(&sequences, down).is_empty());
+        assert_eq!(tracker.abandon(), Some(SequenceTrigger::Bind(down)));
+        assert!(!tracker.is_pending());
+    }
+
+    #[test]
+    fn fling_matches_sequence_step() {
+        let sequences = sequences();
+        let mut tracker = GestureSequenceTracker::default();
+
+        let fling = GestureTrigger {
+            fling: true,
+            ..swipe(GestureDirection::Down)
+        };
+        assert!(tracker.step(&sequences, fling).is_empty());
+        assert_eq!(
+            tracker.step(&sequences, hold()),
+            [SequenceTrigger::Sequence(Action::ToggleOverview)]
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Hot corners triggering their action when the pointer enters them.
+//!
+//! A corner triggers once per visit, after the pointer has stayed in it for the dwell time and
+//! pushed into it for the pressure distance. Visits too soon after the last trigger don't count.
+
+use std::time::Duration;
+
+use calloop::timer::{TimeoutAction, Timer};
+use calloop::RegistrationToken;
+use niri_config::gestures::{Corner, HotCorners};
+use niri_config::Action;
+use smithay::output::Output;
+
+use super::grab_allows_hot_corner;
+use crate::niri::State;
+use crate::utils::get_monotonic_time;
+
+#[derive(Debug, Default)]
+pub struct HotCornerTracker {
+    /// Corner the pointer is in.
+    inside: Option<InsideCorner>,
+    /// Time of the last trigger.
+    last_trigger: Option<Duration>,
+    /// Timer for when the pointer has stayed in the corner for long enough.
+    dwell_timer: Option<RegistrationToken>,
+}
+
+#[derive(Debug)]
+pub(super) struct InsideCorner {
+    corner: Corner,
+    action: Action,
+    dwell: Duration,
+    retrigger_delay: Duration,
+    /// Pressure needed to trigger; pointer motion pushing into the corner adds to `pushed`.
+    pressure: f64,
+    entered: Duration,
+    pushed: f64,
+    /// Whether this visit is done, either because it triggered or because it can't anymore.
+    done: bool,
+}
+
+enum HotCornerCheck {
+    Trigger(Action),
+    Wait(Duration),
+    Nothing,
+}
+
+impl InsideCorner {
+    fn new(corner: Corner, action: Action, hot_corners: &HotCorners, now: Duration) -> Self {
+        Self {
+            corner,
+            action,
+            dwell: Duration::from_millis(u64::from(hot_corners.dwell_ms)),
+            retrigger_delay: Duration::from_millis(u64::from(hot_corners.retrigger_delay_ms)),
+            pressure: f64::from(hot_corners.pressure),
+            entered: now,
+            pushed: 0.,
+            done: false,
+        }
+    }
+}
+
+impl HotCornerTracker {
+    /// Feeds pointer motion inside a corner.
+    ///
+    /// `pushed` is the distance the pointer moved, or `None` for absolute devices which can't push
+    /// into the corner, and so aren't held back by the pressure.
+    fn motion(&mut self, inside: InsideCorner, pushed: Option<f64>) {
+        let inside = self.inside.insert(inside);
+        match pushed {
+            Some(pushed) => inside.pushed += pushed,
+            None => inside.pushed = f64::INFINITY,
+        }
+    }
+
+    /// Checks whether the corner the pointer is in should trigger now.
+    fn check(&mut self, now: Duration) -> HotCornerCheck {
+        let Some(inside) = &mut self.inside else {
+            return HotCornerCheck::Nothing;
+        };
+        if inside.done || inside.pushed < inside.pressure {
+            return HotCornerCheck::Nothing;
+        }
+
+        if let Some(last) = self.last_trigger {
+            if now.saturating_sub(last) < inside.retrigger_delay {
+                inside.done = true;
+                return HotCornerCheck::Nothing;
+            }
+        }
+
+        let ready = inside.entered + inside.dwell;
+        if now < ready {
+            return HotCornerCheck::Wait(ready - now);
+        }
+
+        inside.done = true;
+        self.last_trigger = Some(now);
+        HotCornerCheck::Trigger(inside.action.clone())
+    }
+}
+
+impl State {
+    /// Takes the pointer out of the hot corner, returning its visit state.
+    pub(super) fn leave_hot_corner(&mut self) -> Option<InsideCorner> {
+        if let Some(token) = self.niri.hot_corner.dwell_timer.take() {
+            self.niri.event_loop.remove(token);
+        }
+        self.niri.hot_corner.inside.take()
+    }
+
+    /// Updates the hot corner after pointer motion.
+    ///
+    /// `previous` is the visit state from [`Self::leave_hot_corner()`] before the motion.
+    pub(super) fn hot_corner_motion(
+        &mut self,
+        previous: Option<InsideCorner>,
+        output: Option<&Output>,
+        corner: Option<Corner>,
+        pushed: Option<f64>,
+    ) {
+        let (Some(output), Some(corner)) = (output, corner) else {
+            return;
+        };
+
+        let now = get_monotonic_time();
+        let mut inside = match previous {
+            Some(previous) if previous.corner == corner => previous,
+            _ => {
+                let hot_corners = self.niri.hot_corners(output);
+                let Some(action) = hot_corners.action(corner) else {
+                    return;
+                };
+                InsideCorner::new(corner, action, &hot_corners, now)
+            }
+        };
+
+        let pointer = self.niri.seat.get_pointer().unwrap();
+        if !pointer
+            .with_grab(|_, grab| grab_allows_hot_corner(grab))
+            .unwrap_or(true)
+        {
+            inside.done = true;
+        }
+
+        self.niri.hot_corner.motion(inside, pushed);
+        self.check_hot_corner(now);
+    }
+
+    fn check_hot_corner(&mut self, now: Duration) {
+        match self.niri.hot_corner.check(now) {
+            HotCornerCheck::Trigger(action) => self.do_action(action, false),
+            HotCornerCheck::Wait(delay) => {
+                if self.niri.hot_corner.dwell_timer.is_some() {
+                    return;
+                }
+
+                let timer = Timer::from_duration(delay);
+                let token = self
+                    .niri
+                    .event_loop
+                    .insert_source(timer, |_, _, state| {
+                        state.niri.hot_corner.dwell_timer = None;
+                        state.check_hot_corner(get_monotonic_time());
+                        TimeoutAction::Drop
+                    })
+                    .unwrap();
+                self.niri.hot_corner.dwell_timer = Some(token);
+            }
+            HotCornerCheck::Nothing => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    fn hot_corners(dwell_ms: u16, retrigger_delay_ms: u16, pressure: u16) -> HotCorners {
+        HotCorners {
+            dwell_ms,
+            retrigger_delay_ms,
+            pressure,
+            ..Default::default()
+        }
+    }
+
+    fn enter(tracker: &mut HotCornerTracker, hot_corners: &HotCorners, now: Duration) {
+        let inside = InsideCorner::new(Corner::TopLeft, Action::ToggleOverview, hot_corners, now);
+        tracker.motion(inside, Some(1.));
+    }
+
+    fn move_inside(tracker: &mut HotCornerTracker, pushed: f64) {
+        let inside = tracker.inside.take().unwrap();
+        tracker.motion(inside, Some(pushed));
+    }
+
+    #[test]
+    fn triggers_once_per_visit() {
+        let hot_corners = hot_corners(0, 0, 0);
+        let mut tracker = HotCornerTracker::default();
+        enter(&mut tracker, &hot_corners, Duration::ZERO);
+        assert!(matches!(
+            tracker.check(Duration::ZERO),
+            HotCornerCheck::Trigger(_)
+        ));
+
+        move_inside(&mut tracker, 5.);
+        assert!(matches!(tracker.check(MS), HotCornerCheck::Nothing));
+    }
+
+    #[test]
+    fn dwell_waits() {
+        let hot_corners = hot_corners(100, 0, 0);
+        let mut tracker = HotCornerTracker::default();
+        enter(&mut tracker, &hot_corners, Duration::ZERO);
+        assert!(matches!(
+            tracker.check(40 * MS),
+            HotCornerCheck::Wait(delay) if delay == 60 * MS
+        ));
+        assert!(matches!(
+            tracker.check(100 * MS),
+            HotCornerCheck::Trigger(_)
+        ));
+    }
+
+    #[test]
+    fn pressure_accumulates() {
+        let hot_corners = hot_corners(0, 0, 10);
+        let mut tracker = HotCornerTracker::default();
+        enter(&mut tracker, &hot_corners, Duration::ZERO);
+        assert!(matches!(tracker.check(MS), HotCornerCheck::Nothing));
+
+        move_inside(&mut tracker, 5.);
+        assert!(matches!(tracker.check(2 * MS), HotCornerCheck::Nothing));
+        move_inside(&mut tracker, 5.);
+        assert!(matches!(tracker.check(3 * MS), HotCornerCheck::Trigger(_)));
+    }
+
+    #[test]
+    fn retrigger_delay_skips_visits() {
+        let hot_corners = hot_corners(0, 500, 0);
+        let mut tracker = HotCornerTracker::default();
+        enter(&mut tracker, &hot_corners, Duration::ZERO);
+        assert!(matches!(
+            tracker.check(Duration::ZERO),
+            HotCornerCheck::Trigger(_)
+        ));
+
+        tracker.inside = None;
+        enter(&mut tracker, &hot_corners, 200 * MS);
+        assert!(matches!(tracker.check(200 * MS), HotCornerCheck::Nothing));
+
+        // Staying in the corner past the delay doesn't trigger the skipped visit.
+        assert!(matches!(tracker.check(600 * MS), HotCornerCheck::Nothing));
+
+        tracker.inside = None;
+        enter(&mut tracker, &hot_corners, 700 * MS);
+        assert!(matches!(
+            tracker.check(700 * MS),
+            HotCornerCheck::Trigger(_)
+        ));
+    }
+}
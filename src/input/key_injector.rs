@@ -0,0 +1,187 @@
+//! Synthesized keyboard input.
+//!
+//! Sends key combos to the focused client through the seat keyboard, as if they were typed on a
+//! physical keyboard. This goes through the regular XKB state, so the client sees consistent
+//! modifiers, but it bypasses the compositor binds. Combos for a specific window briefly move the
+//! keyboard focus to it.
+
+use niri_config::{Key, ModKey, Modifiers, Trigger};
+use smithay::backend::input::{KeyState, Keycode};
+use smithay::input::keyboard::{FilterResult, Keysym};
+use smithay::utils::SERIAL_COUNTER;
+
+use crate::niri::State;
+use crate::utils::get_monotonic_time;
+
+impl State {
+    /// Sends key combos to the client with the keyboard focus.
+    ///
+    /// Each combo presses its modifiers, then the key, then releases everything in reverse order.
+    /// If any of the combos can't be typed with the current keymap, nothing is sent.
+    ///
+    /// Modifiers held on the physical keyboard, like the ones of the bind that sent the keys, are
+    /// released for the duration of the combos and pressed again afterwards. This way they don't
+    /// apply to the combos, and the XKB state matches the physical keyboard in the end.
+    pub fn send_keys(&mut self, combos: &[Key]) {
+        let keycodes = match self.resolve_keys(combos) {
+            Ok(keycodes) => keycodes,
+            Err(err) => {
+                warn!("not sending keys: {err}");
+                return;
+            }
+        };
+
+        let held = self.held_modifier_keys();
+        for &keycode in &held {
+            self.inject_key(keycode, KeyState::Released);
+        }
+
+        for keycodes in keycodes {
+            for &keycode in &keycodes {
+                self.inject_key(keycode, KeyState::Pressed);
+            }
+            for &keycode in keycodes.iter().rev() {
+                self.inject_key(keycode, KeyState::Released);
+            }
+        }
+
+        for &keycode in &held {
+            self.inject_key(keycode, KeyState::Pressed);
+        }
+    }
+
+    /// Sends key combos to the window with this id, even if it isn't focused.
+    ///
+    /// The keyboard focus moves to the window for the duration of the combos, and then goes back
+    /// to where it was.
+    pub fn send_keys_to_window(&mut self, id: u64, combos: &[Key]) {
+        let Some(surface) = self
+            .niri
+            .layout
+            .windows()
+            .find(|(_, m)| m.id().get() == id)
+            .map(|(_, m)| m.toplevel().wl_surface().clone())
+        else {
+            warn!("not sending keys: no window with id {id}");
+            return;
+        };
+
+        if self.niri.keyboard_focus.surface() == Some(&surface) {
+            self.send_keys(combos);
+            return;
+        }
+
+        if let Err(err) = self.resolve_keys(combos) {
+            warn!("not sending keys: {err}");
+            return;
+        }
+
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        keyboard.set_focus(self, Some(surface), SERIAL_COUNTER.next_serial());
+        self.send_keys(combos);
+
+        let focus = self.niri.keyboard_focus.clone().into_surface();
+        keyboard.set_focus(self, focus, SERIAL_COUNTER.next_serial());
+    }
+
+    /// Resolves the keycodes for every combo, failing if any of them can't be typed.
+    pub fn resolve_keys(&mut self, combos: &[Key]) -> Result<Vec<Vec<Keycode>>, String> {
+        let mod_key = self.backend.mod_key(&self.niri.config.borrow());
+
+        combos
+            .iter()
+            .map(|combo| {
+                self.keycodes_for_combo(combo, mod_key)
+                    .ok_or_else(|| format!("{combo:?} can't be typed with the current keymap"))
+            })
+            .collect()
+    }
+
+    pub(super) fn inject_key(&mut self, keycode: Keycode, state: KeyState) {
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        let serial = SERIAL_COUNTER.next_serial();
+        let time = get_monotonic_time().as_millis() as u32;
+        keyboard.input::<(), _>(self, keycode, state, serial, time, |_, _, _| {
+            FilterResult::Forward
+        });
+    }
+
+    /// Returns the currently pressed modifier keys.
+    fn held_modifier_keys(&self) -> Vec<Keycode> {
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        keyboard.with_pressed_keysyms(|keysyms| {
+            keysyms
+                .iter()
+                .filter(|handle| handle.raw_syms().iter().any(|sym| sym.is_modifier_key()))
+                .map(|handle| handle.raw_code())
+                .collect()
+        })
+    }
+
+    /// Resolves the modifier and key keycodes for a combo, modifiers first.
+    ///
+    /// Keysyms on a shifted level, like uppercase letters, add the modifiers for that level.
+    fn keycodes_for_combo(&mut self, combo: &Key, mod_key: ModKey) -> Option<Vec<Keycode>> {
+        let Trigger::Keysym(keysym) = combo.trigger else {
+            return None;
+        };
+
+        let mut modifiers = combo.modifiers;
+        if modifiers.contains(Modifiers::COMPOSITOR) {
+            modifiers.remove(Modifiers::COMPOSITOR);
+            modifiers |= mod_key.to_modifiers();
+        }
+
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        keyboard.with_xkb_state(self, |context| {
+            let xkb = context.xkb().lock().unwrap();
+            let layout = xkb.active_layout();
+            // SAFETY: we're not changing the ref count.
+            let keymap = unsafe { xkb.keymap() };
+
+            let find = |keysym: Keysym, level: u32| {
+                let mut found = None;
+                keymap.key_for_each(|keymap, keycode| {
+                    if found.is_some() || keymap.num_levels_for_key(keycode, layout.0) <= level {
+                        return;
+                    }
+
+                    let syms = keymap.key_get_syms_by_level(keycode, layout.0, level);
+                    if syms.contains(&keysym) {
+                        found = Some(keycode);
+                    }
+                });
+                found
+            };
+
+            // Prefer the lowest level, so that a keysym is typed without extra modifiers where
+            // possible. The levels follow the usual FOUR_LEVEL key type.
+            let (key, level_modifiers) = [
+                (0, Modifiers::empty()),
+                (1, Modifiers::SHIFT),
+                (2, Modifiers::ISO_LEVEL3_SHIFT),
+                (3, Modifiers::SHIFT | Modifiers::ISO_LEVEL3_SHIFT),
+            ]
+            .into_iter()
+            .find_map(|(level, mods)| find(keysym, level).map(|key| (key, mods)))?;
+            modifiers |= level_modifiers;
+
+            let mut keycodes = Vec::new();
+            for (modifier, keysym) in [
+                (Modifiers::CTRL, Keysym::Control_L),
+                (Modifiers::SHIFT, Keysym::Shift_L),
+                (Modifiers::ALT, Keysym::Alt_L),
+                (Modifiers::SUPER, Keysym::Super_L),
+                (Modifiers::ISO_LEVEL3_SHIFT, Keysym::ISO_Level3_Shift),
+                (Modifiers::ISO_LEVEL5_SHIFT, Keysym::ISO_Level5_Shift),
+            ] {
+                if modifiers.contains(modifier) {
+                    keycodes.push(find(keysym, 0)?);
+                }
+            }
+            keycodes.push(key);
+
+            Some(keycodes)
+        })
+    }
+}
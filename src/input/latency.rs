@@ -0,0 +1,316 @@
+//! Input latency tracing for performance debugging.
+//!
+//! With the `trace-input-latency` debug flag, libinput events get timestamps at every stage as
+//! they go through niri, and the time between the stages goes into histograms that niri msg can
+//! print.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use niri_ipc::{InputLatency, InputLatencyStage, InputStage, LatencyBucket};
+use smithay::backend::input::{Event as _, InputBackend, InputEvent};
+use smithay::output::Output;
+
+use crate::niri::State;
+use crate::utils::get_monotonic_time;
+
+const STAGES: [InputStage; 4] = [
+    InputStage::Receive,
+    InputStage::Dispatch,
+    InputStage::Flush,
+    InputStage::Present,
+];
+
+/// Upper bounds of the histogram buckets; one more bucket holds the latencies above them.
+const BUCKET_BOUNDS: [Duration; 9] = [
+    Duration::from_micros(250),
+    Duration::from_micros(500),
+    Duration::from_millis(1),
+    Duration::from_millis(2),
+    Duration::from_millis(4),
+    Duration::from_millis(8),
+    Duration::from_millis(16),
+    Duration::from_millis(32),
+    Duration::from_millis(64),
+];
+
+/// Events that wait for a frame for longer than this don't count towards the presentation.
+///
+/// Events that don't change anything on screen only get presented with the next unrelated frame,
+/// which can come much later.
+const MAX_FRAME_WAIT: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default)]
+pub struct InputLatencyTracker {
+    histograms: [Histogram; STAGES.len()],
+    /// Times when the dispatch of the events finished, waiting for the flush.
+    unflushed: Vec<Duration>,
+    /// Timestamps of the events waiting for a frame.
+    unrendered: Vec<Duration>,
+    /// Timestamps of the events rendered on each output, waiting for the presentation.
+    unpresented: HashMap<Output, Vec<Duration>>,
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+    counts: [u64; BUCKET_BOUNDS.len() + 1],
+    total: Duration,
+    max: Duration,
+}
+
+impl Histogram {
+    fn add(&mut self, latency: Duration) {
+        let bucket = BUCKET_BOUNDS
+            .iter()
+            .position(|bound| latency <= *bound)
+            .unwrap_or(BUCKET_BOUNDS.len());
+        self.counts[bucket] += 1;
+        self.total += latency;
+        self.max = self.max.max(latency);
+    }
+
+    fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    fn to_ipc(&self, stage: InputStage) -> InputLatencyStage {
+        let count = self.count();
+        let mean = if count == 0 {
+            Duration::ZERO
+        } else {
+            self.total.div_f64(count as f64)
+        };
+
+        let bounds = BUCKET_BOUNDS
+            .iter()
+            .map(|bound| Some(bound.as_micros() as u64));
+        let buckets = bounds
+            .chain([None])
+            .zip(self.counts)
+            .map(|(max_us, count)| LatencyBucket { max_us, count })
+            .collect();
+
+        InputLatencyStage {
+            stage,
+            count,
+            mean_us: mean.as_micros() as u64,
+            max_us: self.max.as_micros() as u64,
+            buckets,
+        }
+    }
+}
+
+impl InputLatencyTracker {
+    fn add(&mut self, stage: InputStage, latency: Duration) {
+        let idx = STAGES.iter().position(|s| *s == stage).unwrap();
+        self.histograms[idx].add(latency);
+    }
+
+    /// Records an event with the kernel timestamp `time` that niri processed from `start` to
+    /// `end`.
+    pub fn event_processed(&mut self, time: Duration, start: Duration, end: Duration) {
+        self.add(InputStage::Receive, start.saturating_sub(time));
+        self.add(InputStage::Dispatch, end.saturating_sub(start));
+        self.unflushed.push(end);
+
+        // Drop the events that never made it into a frame.
+        self.unrendered
+            .retain(|old| time.saturating_sub(*old) < MAX_FRAME_WAIT);
+        self.unrendered.push(time);
+    }
+
+    /// Records that niri sent the queued events to the clients.
+    pub fn flushed(&mut self, now: Duration) {
+        for end in std::mem::take(&mut self.unflushed) {
+            self.add(InputStage::Flush, now.saturating_sub(end));
+        }
+    }
+
+    /// Records that niri submitted a frame for the output.
+    pub fn rendered(&mut self, output: &Output) {
+        if self.unrendered.is_empty() {
+            return;
+        }
+
+        let unpresented = self.unpresented.entry(output.clone()).or_default();
+        unpresented.append(&mut self.unrendered);
+    }
+
+    /// Records that the last frame submitted for the output was presented.
+    pub fn presented(&mut self, output: &Output, presentation_time: Duration) {
+        let Some(times) = self.unpresented.remove(output) else {
+            return;
+        };
+
+        for time in times {
+            let latency = presentation_time.saturating_sub(time);
+            if latency < MAX_FRAME_WAIT {
+                self.add(InputStage::Present, latency);
+            }
+        }
+    }
+
+    pub fn has_unflushed(&self) -> bool {
+        !self.unflushed.is_empty()
+    }
+
+    pub fn to_ipc(&self, enabled: bool) -> InputLatency {
+        let stages = STAGES
+            .iter()
+            .zip(&self.histograms)
+            .map(|(stage, histogram)| histogram.to_ipc(*stage))
+            .collect();
+        InputLatency { enabled, stages }
+    }
+
+    pub fn output_removed(&mut self, output: &Output) {
+        self.unpresented.remove(output);
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Returns the kernel timestamp of the event.
+pub fn event_time<I: InputBackend>(event: &InputEvent<I>) -> Option<Duration> {
+    let time = match event {
+        InputEvent::DeviceAdded { .. }
+        | InputEvent::DeviceRemoved { .. }
+        | InputEvent::Special(_) => return None,
+        InputEvent::Keyboard { event } => event.time(),
+        InputEvent::PointerMotion { event } => event.time(),
+        InputEvent::PointerMotionAbsolute { event } => event.time(),
+        InputEvent::PointerButton { event } => event.time(),
+        InputEvent::PointerAxis { event } => event.time(),
+        InputEvent::TabletToolAxis { event } => event.time(),
+        InputEvent::TabletToolTip { event } => event.time(),
+        InputEvent::TabletToolProximity { event } => event.time(),
+        InputEvent::TabletToolButton { event } => event.time(),
+        InputEvent::GestureSwipeBegin { event } => event.time(),
+        InputEvent::GestureSwipeUpdate { event } => event.time(),
+        InputEvent::GestureSwipeEnd { event } => event.time(),
+        InputEvent::GesturePinchBegin { event } => event.time(),
+        InputEvent::GesturePinchUpdate { event } => event.time(),
+        InputEvent::GesturePinchEnd { event } => event.time(),
+        InputEvent::GestureHoldBegin { event } => event.time(),
+        InputEvent::GestureHoldEnd { event } => event.time(),
+        InputEvent::TouchDown { event } => event.time(),
+        InputEvent::TouchMotion { event } => event.time(),
+        InputEvent::TouchUp { event } => event.time(),
+        InputEvent::TouchCancel { event } => event.time(),
+        InputEvent::TouchFrame { event } => event.time(),
+        InputEvent::SwitchToggle { event } => event.time(),
+    };
+
+    // libinput timestamps are in microseconds of CLOCK_MONOTONIC, like get_monotonic_time().
+    Some(Duration::from_micros(time))
+}
+
+impl State {
+    /// Processes a libinput event, tracing its latency if enabled.
+    pub fn process_traced_input_event<I: InputBackend + 'static>(&mut self, event: InputEvent<I>)
+    where
+        I::Device: 'static,
+    {
+        let time = if self.niri.config.borrow().debug.trace_input_latency {
+            event_time(&event)
+        } else {
+            None
+        };
+
+        let Some(time) = time else {
+            self.process_input_event(event);
+            return;
+        };
+
+        let start = get_monotonic_time();
+        self.process_input_event(event);
+        let end = get_monotonic_time();
+        self.niri.input_latency.event_processed(time, start, end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smithay::output::{PhysicalProperties, Subpixel};
+    use smithay::utils::Size;
+
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    fn stage(tracker: &InputLatencyTracker, stage: InputStage) -> InputLatencyStage {
+        let ipc = tracker.to_ipc(true);
+        ipc.stages.into_iter().find(|s| s.stage == stage).unwrap()
+    }
+
+    #[test]
+    fn histogram_buckets() {
+        let mut histogram = Histogram::default();
+        histogram.add(Duration::from_micros(100));
+        histogram.add(3 * MS);
+        histogram.add(4 * MS);
+        histogram.add(100 * MS);
+
+        let ipc = histogram.to_ipc(InputStage::Receive);
+        assert_eq!(ipc.count, 4);
+        assert_eq!(ipc.max_us, 100_000);
+        assert_eq!(ipc.mean_us, 26_775);
+
+        let counts: Vec<_> = ipc.buckets.iter().map(|b| (b.max_us, b.count)).collect();
+        assert_eq!(counts[0], (Some(250), 1));
+        assert_eq!(counts[4], (Some(4000), 2));
+        assert_eq!(counts[9], (None, 1));
+    }
+
+    #[test]
+    fn stages() {
+        let mut tracker = InputLatencyTracker::default();
+        tracker.event_processed(10 * MS, 11 * MS, 13 * MS);
+        assert!(tracker.has_unflushed());
+        tracker.flushed(16 * MS);
+        assert!(!tracker.has_unflushed());
+
+        assert_eq!(stage(&tracker, InputStage::Receive).mean_us, 1000);
+        assert_eq!(stage(&tracker, InputStage::Dispatch).mean_us, 2000);
+        assert_eq!(stage(&tracker, InputStage::Flush).mean_us, 3000);
+        assert_eq!(stage(&tracker, InputStage::Present).count, 0);
+    }
+
+    #[test]
+    fn presentation_on_rendered_output() {
+        let output = |name: &str| {
+            Output::new(
+                name.to_owned(),
+                PhysicalProperties {
+                    size: Size::from((1280, 720)),
+                    subpixel: Subpixel::Unknown,
+                    make: String::new(),
+                    model: String::new(),
+                    serial_number: String::new(),
+                },
+            )
+        };
+        let (a, b) = (output("a"), output("b"));
+
+        let mut tracker = InputLatencyTracker::default();
+        tracker.event_processed(10 * MS, 10 * MS, 10 * MS);
+        tracker.rendered(&a);
+        tracker.rendered(&b);
+
+        // Events go with the first frame rendered after them.
+        tracker.presented(&b, 20 * MS);
+        assert_eq!(stage(&tracker, InputStage::Present).count, 0);
+        tracker.presented(&a, 30 * MS);
+        assert_eq!(stage(&tracker, InputStage::Present).mean_us, 20_000);
+    }
+
+    #[test]
+    fn stale_events_are_not_presented() {
+        let mut tracker = InputLatencyTracker::default();
+        tracker.event_processed(Duration::ZERO, MS, MS);
+        tracker.event_processed(2 * MAX_FRAME_WAIT, 2 * MAX_FRAME_WAIT, 2 * MAX_FRAME_WAIT);
+        assert_eq!(tracker.unrendered.len(), 1);
+    }
+}
@@ -6,8 +6,15 @@ use std::time::Duration;
 
 use calloop::timer::{TimeoutAction, Timer};
 use input::event::gesture::GestureEventCoordinates as _;
+use niri_config::gestures::{
+    GestureTarget, GestureThresholds, GestureTrigger, MouseGestureButton, SimulatedGesture,
+    SwipeTarget,
+};
+use niri_config::input::{ButtonRemapTarget, FocusAtEdge};
+use niri_config::utils::MergeWith as _;
 use niri_config::{
-    Action, Bind, Binds, Config, Key, ModKey, Modifiers, MruDirection, SwitchBinds, Trigger,
+    Action, Bind, Binds, Config, GestureDirection, GestureKind, Key, ModKey, Modifiers,
+    MruDirection, SwitchBinds, Trigger,
 };
 use niri_ipc::LayoutSwitchTarget;
 use smithay::backend::input::{
@@ -34,36 +41,60 @@ use smithay::input::SeatHandler;
 use smithay::output::Output;
 use smithay::reexports::wayland_server::protocol::wl_data_source::WlDataSource;
 use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
-use smithay::utils::{Logical, Point, Rectangle, Transform, SERIAL_COUNTER};
+use smithay::utils::{Logical, Point, Rectangle, Size, Transform, SERIAL_COUNTER};
 use smithay::wayland::keyboard_shortcuts_inhibit::KeyboardShortcutsInhibitor;
 use smithay::wayland::pointer_constraints::{with_pointer_constraint, PointerConstraint};
+use smithay::wayland::shell::wlr_layer::Layer;
 use smithay::wayland::tablet_manager::{TabletDescriptor, TabletSeatTrait};
 use touch_overview_grab::TouchOverviewGrab;
 
+use self::double_press::DoublePress;
+use self::gesture_sequence::SequenceTrigger;
+use self::modifier_tap::is_modifier_keysym;
+use self::mouse_gesture_grab::MouseGestureGrab;
 use self::move_grab::MoveGrab;
 use self::resize_grab::ResizeGrab;
 use self::spatial_movement_grab::SpatialMovementGrab;
+use self::touchpad_gesture::{
+    HoldGesture, MaximizeSwipeGesture, MaximizeSwipeStep, OverviewPinchGesture, PinchGesture,
+    ResizePinchGesture, SwipeGesture, TouchpadGesture,
+};
 #[cfg(feature = "dbus")]
 use crate::dbus::freedesktop_a11y::KbMonBlock;
 use crate::layout::scrolling::ScrollDirection;
-use crate::layout::{ActivateWindow, LayoutElement as _};
-use crate::niri::{CastTarget, PointerVisibility, State};
+use crate::layout::{ActivateWindow, HitType, LayoutElement as _};
+use crate::niri::{CastTarget, PointContents, PointerVisibility, State};
+use crate::ui::exit_confirm_dialog::{Confirmation, ForceCloseTarget};
 use crate::ui::mru::{WindowMru, WindowMruUi};
 use crate::ui::screenshot_ui::ScreenshotUi;
 use crate::utils::spawning::{spawn, spawn_sh};
-use crate::utils::{center, get_monotonic_time, ResizeEdge};
+use crate::utils::{center, get_monotonic_time, with_toplevel_role, ResizeEdge};
+use crate::window::Mapped;
 
+pub mod accessibility_keys;
 pub mod backend_ext;
+pub mod double_press;
+pub mod gesture_sequence;
+pub mod hot_corner;
+pub mod key_injector;
+pub mod latency;
+pub mod modifier_tap;
+pub mod mouse_gesture_grab;
 pub mod move_grab;
 pub mod pick_color_grab;
 pub mod pick_window_grab;
 pub mod resize_grab;
+pub mod resize_mode;
 pub mod scroll_swipe_gesture;
 pub mod scroll_tracker;
 pub mod spatial_movement_grab;
 pub mod swipe_tracker;
+pub mod tablet_pad;
+pub mod touch_edge_swipe;
 pub mod touch_overview_grab;
 pub mod touch_resize_grab;
+pub mod touchpad_gesture;
+pub mod touchscreen_gesture;
 
 use backend_ext::{NiriInputBackend as InputBackend, NiriInputDevice as _};
 
@@ -146,6 +177,10 @@ impl State {
         let hide_exit_confirm_dialog =
             self.niri.exit_confirm_dialog.is_open() && should_hide_exit_confirm_dialog(&event);
 
+        let redraw_gesture_debug_overlay =
+            self.niri.gesture_debug_overlay.is_open() && is_gesture_event(&event);
+        let gesture_progress = self.shown_gesture_progress();
+
         let mut consumed_by_a11y = false;
         use InputEvent::*;
         match event {
@@ -177,6 +212,11 @@ impl State {
             Special(_) => (),
         }
 
+        // The progress indicator only needs a redraw when it's visible or its state changed.
+        if redraw_gesture_debug_overlay || self.shown_gesture_progress() != gesture_progress {
+            self.niri.queue_redraw_all();
+        }
+
         // Don't hide overlays if consumed by a11y, so that you can use the screen reader
         // navigation keys.
         if consumed_by_a11y {
@@ -235,6 +275,10 @@ impl State {
                 self.niri.tablets.remove(device);
                 self.niri.devices.remove(device);
             }
+            // Tablet pads only come from libinput.
+            InputEvent::Special(input::event::Event::TabletPad(event)) => {
+                self.on_tablet_pad(event);
+            }
             _ => (),
         }
     }
@@ -296,8 +340,16 @@ impl State {
         let device_output = device_output.as_ref();
         let (target_geo, keep_ratio, px, transform) =
             if let Some(output) = device_output.or_else(|| self.niri.output_for_tablet()) {
+                let mut geo = self.niri.global_space.output_geometry(output).unwrap();
+
+                let region = self.niri.config.borrow().input.tablet.map_to_region;
+                if let Some(region) = region.filter(|r| r.width > 0 && r.height > 0) {
+                    let loc = geo.loc + Point::from((region.x, region.y));
+                    geo = Rectangle::new(loc, Size::from((region.width, region.height)));
+                }
+
                 (
-                    self.niri.global_space.output_geometry(output).unwrap(),
+                    geo,
                     true,
                     1. / output.current_scale().fractional_scale(),
                     output.current_transform(),
@@ -364,12 +416,27 @@ impl State {
         &mut self,
         event: I::KeyboardKeyEvent,
         consumed_by_a11y: &mut bool,
+    ) {
+        let key_code = event.key_code();
+        let state = event.state();
+        let time = Event::time_msec(&event);
+
+        // Slow keys and sticky keys come first since they can hold back and add key presses.
+        self.accessibility_keys_input(key_code, state, time, consumed_by_a11y);
+    }
+
+    /// Handles a key press or release that passed the keyboard accessibility options.
+    fn on_key(
+        &mut self,
+        key_code: Keycode,
+        state: KeyState,
+        time: u32,
+        consumed_by_a11y: &mut bool,
     ) {
         let mod_key = self.backend.mod_key(&self.niri.config.borrow());
 
         let serial = SERIAL_COUNTER.next_serial();
-        let time = Event::time_msec(&event);
-        let pressed = event.state() == KeyState::Pressed;
+        let pressed = state == KeyState::Pressed;
 
         // Stop bind key repeat on any release. This won't work 100% correctly in cases like:
         // 1. Press Mod
@@ -397,11 +464,8 @@ impl State {
         // other changes.
         #[cfg(feature = "dbus")]
         let block = {
-            let block = self.a11y_process_key(
-                Duration::from_millis(u64::from(time)),
-                event.key_code(),
-                event.state(),
-            );
+            let block =
+                self.a11y_process_key(Duration::from_millis(u64::from(time)), key_code, state);
             if block != KbMonBlock::Pass {
                 *consumed_by_a11y = true;
             }
@@ -415,18 +479,24 @@ impl State {
         #[cfg(not(feature = "dbus"))]
         let _ = consumed_by_a11y;
 
-        let Some(Some(bind)) = self.niri.seat.get_keyboard().unwrap().input(
+        let mut tapped_mods = None;
+        let res = self.niri.seat.get_keyboard().unwrap().input(
             self,
-            event.key_code(),
-            event.state(),
+            key_code,
+            state,
             serial,
             time,
             |this, mods, keysym| {
-                let key_code = event.key_code();
                 let modified = keysym.modified_sym();
                 let raw = keysym.raw_latin_sym_or_raw_current_sym();
                 let modifiers = modifiers_from_state(*mods);
 
+                let is_modifier = is_modifier_keysym(modified);
+                tapped_mods = this
+                    .niri
+                    .modifier_tap
+                    .key(is_modifier, pressed, *mods, time);
+
                 // After updating XKB state from accessibility-grabbed keys, return right away and
                 // don't handle them.
                 #[cfg(feature = "dbus")]
@@ -467,8 +537,13 @@ impl State {
 
                 if this.niri.exit_confirm_dialog.is_open() && pressed {
                     if raw == Some(Keysym::Return) {
-                        info!("quitting after confirming exit dialog");
-                        this.niri.stop_signal.stop();
+                        match this.niri.exit_confirm_dialog.confirmation().clone() {
+                            Confirmation::Exit => {
+                                info!("quitting after confirming exit dialog");
+                                this.niri.stop_signal.stop();
+                            }
+                            Confirmation::ForceClose(target) => this.force_close_window(&target),
+                        }
                     }
 
                     // Don't send this press to any clients.
@@ -503,14 +578,71 @@ impl State {
                     return FilterResult::Intercept(None);
                 }
 
+                // The resize mode keys take precedence over all binds.
+                if pressed {
+                    if let Some(bind) = raw.and_then(|raw| this.resize_mode_bind(raw, *mods)) {
+                        this.niri.suppressed_keys.insert(key_code);
+                        return FilterResult::Intercept(Some(bind));
+                    }
+                }
+
+                // Escape goes back to the regular binds, unless the bind mode binds it itself.
+                if pressed && raw == Some(Keysym::Escape) && modifiers.is_empty() {
+                    let config = this.niri.config.borrow();
+                    let escape = Key {
+                        trigger: Trigger::Keysym(Keysym::Escape),
+                        modifiers: Modifiers::empty(),
+                    };
+                    let leave_mode = this
+                        .niri
+                        .bind_mode
+                        .as_deref()
+                        .and_then(|name| config.bind_modes.find(name))
+                        .is_some_and(|mode| !mode.binds.iter().any(|bind| bind.key == escape));
+                    drop(config);
+
+                    if leave_mode {
+                        this.niri.switch_bind_mode("default");
+                        this.niri.suppressed_keys.insert(key_code);
+                        return FilterResult::Intercept(None);
+                    }
+                }
+
+                if pressed {
+                    let cancel_key = this.niri.config.borrow().gestures.cancel_key;
+                    if raw.is_some_and(|raw| cancel_key.trigger == Trigger::Keysym(raw))
+                        && cancel_key.modifiers == modifiers
+                        && this.cancel_gesture()
+                    {
+                        this.niri.suppressed_keys.insert(key_code);
+                        return FilterResult::Intercept(None);
+                    }
+                }
+
                 if let Some(Keysym::space) = raw {
                     this.niri.screenshot_ui.set_space_down(pressed);
                 }
 
+                // Modifier presses are left alone so that double modifier taps work.
+                let mut double_press = DoublePress::None;
+                if let Some(raw) = raw.filter(|_| pressed && !is_modifier) {
+                    double_press = this.double_press_input(Trigger::Keysym(raw), *mods, time);
+                }
+                if let DoublePress::Double(bind) = &double_press {
+                    if !(is_inhibiting_shortcuts && bind.allow_inhibiting) {
+                        this.niri.suppressed_keys.insert(key_code);
+                        return FilterResult::Intercept(Some(bind.clone()));
+                    }
+                }
+
                 let res = {
                     let config = this.niri.config.borrow();
-                    let bindings =
-                        make_binds_iter(&config, &mut this.niri.window_mru_ui, modifiers);
+                    let bindings = make_binds_iter(
+                        &config,
+                        &mut this.niri.window_mru_ui,
+                        this.niri.bind_mode.as_deref(),
+                        modifiers,
+                    );
 
                     should_intercept_key(
                         &mut this.niri.suppressed_keys,
@@ -527,6 +659,14 @@ impl State {
                     )
                 };
 
+                if let (DoublePress::First(window), FilterResult::Intercept(Some(bind))) =
+                    (&double_press, &res)
+                {
+                    // Wait and see if a double press follows before triggering the bind.
+                    this.hold_back_bind(bind.clone(), *window);
+                    return FilterResult::Intercept(None);
+                }
+
                 if matches!(res, FilterResult::Forward) {
                     // If we didn't find any bind, try other hardcoded keys.
                     if this.niri.keyboard_focus.is_overview() && pressed {
@@ -544,7 +684,13 @@ impl State {
 
                 res
             },
-        ) else {
+        );
+
+        if let Some(mods) = tapped_mods {
+            self.handle_modifier_tap(mods, time, is_inhibiting_shortcuts);
+        }
+
+        let Some(Some(bind)) = res else {
             return;
         };
 
@@ -557,6 +703,42 @@ impl State {
         self.start_key_repeat(bind);
     }
 
+    fn handle_modifier_tap(
+        &mut self,
+        mods: ModifiersState,
+        time: u32,
+        is_inhibiting_shortcuts: bool,
+    ) {
+        let double_press = self.double_press_input(Trigger::ModifierTap, mods, time);
+
+        let bind = if let DoublePress::Double(bind) = &double_press {
+            Some(bind.clone())
+        } else {
+            let config = self.niri.config.borrow();
+            let mod_key = self.backend.mod_key(&config);
+            let bindings = make_binds_iter(
+                &config,
+                &mut self.niri.window_mru_ui,
+                self.niri.bind_mode.as_deref(),
+                modifiers_from_state(mods),
+            );
+            find_configured_bind(bindings, mod_key, Trigger::ModifierTap, mods)
+        };
+
+        let Some(bind) = bind else {
+            return;
+        };
+        if is_inhibiting_shortcuts && bind.allow_inhibiting {
+            return;
+        }
+
+        if let DoublePress::First(window) = double_press {
+            self.hold_back_bind(bind, window);
+        } else {
+            self.handle_bind(bind);
+        }
+    }
+
     fn start_key_repeat(&mut self, bind: Bind) {
         if !bind.repeat {
             return;
@@ -646,6 +828,23 @@ impl State {
         }
     }
 
+    /// Runs the action of a gesture bind.
+    ///
+    /// Window actions skip the focused window when it is excluded from gesture actions.
+    pub fn do_gesture_action(&mut self, action: Action) {
+        if acts_on_focused_window(&action)
+            && self
+                .niri
+                .layout
+                .focus()
+                .is_some_and(|mapped| mapped.rules().exclude_from_gesture_actions == Some(true))
+        {
+            return;
+        }
+
+        self.do_action(action, false);
+    }
+
     pub fn do_action(&mut self, action: Action, allow_when_locked: bool) {
         if self.niri.is_locked() && !(allow_when_locked || allowed_when_locked(&action)) {
             return;
@@ -655,9 +854,12 @@ impl State {
             touch.cancel(self);
         }
 
+        let focus_at_edge = self.niri.config.borrow().input.focus_at_edge;
+        let action = apply_focus_at_edge(action, focus_at_edge);
+
         match action {
             Action::Quit(skip_confirmation) => {
-                if !skip_confirmation && self.niri.exit_confirm_dialog.show() {
+                if !skip_confirmation && self.niri.exit_confirm_dialog.show(Confirmation::Exit) {
                     self.niri.queue_redraw_all();
                     return;
                 }
@@ -692,6 +894,15 @@ impl State {
             Action::DebugToggleDamage => {
                 self.niri.debug_toggle_damage();
             }
+            Action::DebugToggleGestureOverlay => {
+                self.niri.debug_toggle_gesture_overlay();
+            }
+            Action::ResetMagnifier => {
+                self.niri.reset_magnifier();
+            }
+            Action::SimulateGesture(simulated) => {
+                self.simulate_gesture(simulated);
+            }
             Action::Spawn(command) => {
                 let (token, _) = self.niri.activation_state.create_external_token(None);
                 spawn(command, Some(token.clone()));
@@ -700,6 +911,24 @@ impl State {
                 let (token, _) = self.niri.activation_state.create_external_token(None);
                 spawn_sh(command, Some(token.clone()));
             }
+            Action::SendKeys(keys) => {
+                self.send_keys(&keys);
+            }
+            Action::SendKeysById { id, keys } => {
+                self.send_keys_to_window(id, &keys);
+            }
+            Action::SwitchBindMode(name) => {
+                self.niri.switch_bind_mode(&name);
+            }
+            Action::EnterResizeMode => {
+                self.enter_resize_mode();
+            }
+            Action::ConfirmResizeMode => {
+                self.confirm_resize_mode();
+            }
+            Action::CancelResizeMode => {
+                self.cancel_resize_mode();
+            }
             Action::DoScreenTransition(delay_ms) => {
                 self.backend.with_primary_renderer(|renderer| {
                     self.niri.do_screen_transition(renderer, delay_ms);
@@ -712,6 +941,62 @@ impl State {
                         if let Err(err) = self.niri.screenshot(
                             renderer,
                             &active,
+                            None,
+                            write_to_disk,
+                            show_pointer,
+                            path,
+                        ) {
+                            warn!("error taking screenshot: {err:?}");
+                        }
+                    });
+                }
+            }
+            Action::ScreenshotOutput {
+                output,
+                write_to_disk,
+                show_pointer,
+                path,
+            } => {
+                let output = self.niri.output_by_name_match(&output).cloned();
+                if let Some(output) = output {
+                    self.backend.with_primary_renderer(|renderer| {
+                        if let Err(err) = self.niri.screenshot(
+                            renderer,
+                            &output,
+                            None,
+                            write_to_disk,
+                            show_pointer,
+                            path,
+                        ) {
+                            warn!("error taking screenshot: {err:?}");
+                        }
+                    });
+                }
+            }
+            Action::ScreenshotRegion {
+                region,
+                write_to_disk,
+                show_pointer,
+                path,
+            } => {
+                let (Ok(width), Ok(height)) =
+                    (i32::try_from(region.width), i32::try_from(region.height))
+                else {
+                    warn!("screenshot region is too large: {region:?}");
+                    return;
+                };
+
+                let pos = Point::from((region.x, region.y));
+                let output = self.niri.output_under(pos.to_f64());
+                if let Some((output, pos_within_output)) = output {
+                    let output = output.clone();
+                    let size = Size::from((width, height));
+                    let region = Rectangle::new(pos_within_output.to_i32_round(), size);
+                    self.backend.with_primary_renderer(|renderer| {
+                        if let Err(err) = self.niri.screenshot(
+                            renderer,
+                            &output,
+                            Some(region),
                             write_to_disk,
                             show_pointer,
                             path,
@@ -784,15 +1069,31 @@ impl State {
                     });
                 }
             }
+            Action::PickColor => {
+                let (tx, rx) = async_channel::bounded(1);
+                self.handle_pick_color(tx);
+
+                let event_loop = self.niri.event_loop.clone();
+                let future = async move {
+                    if let Ok(Some(color)) = rx.recv().await {
+                        event_loop.insert_idle(move |state| state.niri.copy_color(color));
+                    }
+                };
+                if let Err(err) = self.niri.scheduler.schedule(future) {
+                    warn!("error scheduling color picking: {err:?}");
+                }
+            }
             Action::ToggleKeyboardShortcutsInhibit => {
-                if let Some(inhibitor) = self.niri.keyboard_focus.surface().and_then(|surface| {
-                    self.niri
-                        .keyboard_shortcuts_inhibiting_surfaces
-                        .get(surface)
-                }) {
+                if let Some((surface, inhibitor)) =
+                    self.niri.keyboard_focus.surface().and_then(|surface| {
+                        self.niri
+                            .keyboard_shortcuts_inhibiting_surfaces
+                            .get_key_value(surface)
+                    })
+                {
                     if inhibitor.is_active() {
                         inhibitor.inactivate();
-                    } else {
+                    } else if self.niri.allows_keyboard_shortcuts_inhibit(surface) {
                         inhibitor.activate();
                     }
                 }
@@ -808,6 +1109,47 @@ impl State {
                     mapped.toplevel().send_close();
                 }
             }
+            Action::ForceCloseWindow(under_cursor) => {
+                let mapped = if under_cursor {
+                    self.niri.window_under_cursor()
+                } else {
+                    self.niri.layout.focus()
+                };
+                if let Some(target) = mapped.and_then(force_close_target) {
+                    self.confirm_force_close_window(target);
+                }
+            }
+            Action::ForceCloseWindowById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                if let Some(target) = window.and_then(|(_, mapped)| force_close_target(mapped)) {
+                    self.confirm_force_close_window(target);
+                }
+            }
+            Action::MinimizeWindow => {
+                let focus = self.niri.layout.focus().map(|m| m.window.clone());
+                if let Some(window) = focus {
+                    self.minimize_window(&window);
+                }
+            }
+            Action::MinimizeWindowById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.minimize_window(&window);
+                }
+            }
+            Action::UnminimizeWindow => {
+                let last = self.niri.minimized_windows.last();
+                let window = last.map(|minimized| minimized.mapped.window.clone());
+                if let Some(window) = window {
+                    self.unminimize_window(&window);
+                }
+            }
+            Action::UnminimizeWindowById(id) => {
+                if let Some(window) = self.find_minimized_window_by_id(id) {
+                    self.unminimize_window(&window);
+                }
+            }
             Action::FullscreenWindow => {
                 let focus = self.niri.layout.focus().map(|m| m.window.clone());
                 if let Some(window) = focus {
@@ -847,6 +1189,9 @@ impl State {
                 let window = window.map(|(_, m)| m.window.clone());
                 if let Some(window) = window {
                     self.focus_window(&window);
+                } else if let Some(window) = self.find_minimized_window_by_id(id) {
+                    // Focusing a minimized window restores it.
+                    self.unminimize_window(&window);
                 }
             }
             Action::FocusWindowInColumn(index) => {
@@ -1564,6 +1909,12 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::ToggleColumnAccordionDisplay => {
+                self.niri.layout.toggle_column_accordion_display();
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::SetColumnDisplay(display) => {
                 self.niri.layout.set_column_display(display);
                 self.maybe_warp_cursor_to_focus();
@@ -2019,6 +2370,21 @@ impl State {
             Action::ExpandColumnToAvailableWidth => {
                 self.niri.layout.expand_column_to_available_width();
             }
+            Action::EqualizeColumnWidths => {
+                self.niri.layout.distribute_column_widths(false);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::DistributeColumnWidths => {
+                self.niri.layout.distribute_column_widths(true);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::ToggleMonocle => {
+                self.niri.layout.toggle_monocle();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::ShowHotkeyOverlay => {
                 if self.niri.hotkey_overlay.show() {
                     self.niri.queue_redraw_all();
@@ -2148,6 +2514,34 @@ impl State {
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::ToggleWindowPinned => {
+                self.niri.layout.toggle_window_pinned(None);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::ToggleWindowPinnedById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.layout.toggle_window_pinned(Some(&window));
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
+            Action::ToggleWindowAlwaysOnTop => {
+                self.niri.layout.toggle_window_always_on_top(None);
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::ToggleWindowAlwaysOnTopById(id) => {
+                let window = self.niri.layout.windows().find(|(_, m)| m.id().get() == id);
+                let window = window.map(|(_, m)| m.window.clone());
+                if let Some(window) = window {
+                    self.niri.layout.toggle_window_always_on_top(Some(&window));
+                    // FIXME: granular
+                    self.niri.queue_redraw_all();
+                }
+            }
             Action::FocusFloating => {
                 self.niri.layout.focus_floating();
                 self.maybe_warp_cursor_to_focus();
@@ -2212,6 +2606,111 @@ impl State {
                     }
                 }
             }
+            Action::ToggleWindowTag(tag) => {
+                let active_window = self
+                    .niri
+                    .layout
+                    .active_workspace_mut()
+                    .and_then(|ws| ws.active_window_mut());
+                if let Some(window) = active_window {
+                    window.toggle_tag(&tag);
+                }
+            }
+            Action::ToggleWindowTagById { id, tag } => {
+                let window = self
+                    .niri
+                    .layout
+                    .workspaces_mut()
+                    .find_map(|ws| ws.windows_mut().find(|w| w.id().get() == id));
+                if let Some(window) = window {
+                    window.toggle_tag(&tag);
+                }
+            }
+            Action::FocusWindowWithTag(tag) => {
+                let current = self.niri.layout.focus().map(|win| win.id());
+                let tagged: Vec<_> = self
+                    .niri
+                    .layout
+                    .windows()
+                    .map(|(_, win)| win)
+                    .filter(|win| win.has_tag(&tag))
+                    .map(|win| (win.id(), win.window.clone()))
+                    .collect();
+
+                // Cycle through the tagged windows, starting after the focused one.
+                let next = tagged
+                    .iter()
+                    .position(|(id, _)| Some(*id) == current)
+                    .map_or(0, |idx| idx + 1);
+                let window = tagged.get(next).or(tagged.first());
+                if let Some((_, window)) = window {
+                    self.focus_window(window);
+                }
+            }
+            Action::BringWindowsWithTag(tag) => {
+                let windows: Vec<_> = self
+                    .niri
+                    .layout
+                    .windows()
+                    .map(|(_, win)| win)
+                    .filter(|win| win.has_tag(&tag))
+                    .map(|win| win.window.clone())
+                    .collect();
+
+                for window in windows {
+                    // Moving windows out can remove workspaces, so look up the target every time.
+                    let Some(mon) = self.niri.layout.active_monitor_ref() else {
+                        break;
+                    };
+                    if mon.active_workspace_ref().has_window(&window) {
+                        continue;
+                    }
+
+                    let output = mon.output().clone();
+                    let idx = mon.active_workspace_idx();
+
+                    self.niri.layout.move_to_output(
+                        Some(&window),
+                        &output,
+                        Some(idx),
+                        ActivateWindow::No,
+                    );
+                }
+
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::SetMark(mark) => {
+                let id = self.niri.layout.focus().map(|win| win.id().get());
+                if let Some(id) = id {
+                    self.set_window_mark(id, mark);
+                }
+            }
+            Action::SetMarkById { id, mark } => {
+                self.set_window_mark(id, mark);
+            }
+            Action::GotoMark(mark) => {
+                let window = self
+                    .niri
+                    .layout
+                    .windows()
+                    .find(|(_, win)| win.has_mark(mark))
+                    .map(|(_, win)| win.window.clone());
+                if let Some(window) = window {
+                    self.focus_window(&window);
+                }
+            }
+            Action::SetWindowRuleProfile(profile) => {
+                self.niri.set_window_rule_profile(profile);
+            }
+            Action::ToggleWindowRuleProfile(profile) => {
+                let profile = if self.niri.window_rule_profile.as_ref() == Some(&profile) {
+                    None
+                } else {
+                    Some(profile)
+                };
+                self.niri.set_window_rule_profile(profile);
+            }
             Action::SetDynamicCastWindow => {
                 let id = self
                     .niri
@@ -2383,9 +2882,8 @@ impl State {
     }
 
     fn on_pointer_motion<I: InputBackend>(&mut self, event: I::PointerMotionEvent) {
-        let was_inside_hot_corner = self.niri.pointer_inside_hot_corner;
         // Any of the early returns here mean that the pointer is not inside the hot corner.
-        self.niri.pointer_inside_hot_corner = false;
+        let hot_corner = self.leave_hot_corner();
 
         // We need an output to be able to move the pointer.
         if self.niri.global_space.outputs().next().is_none() {
@@ -2602,16 +3100,13 @@ impl State {
 
         // contents_under() will return no surface when the hot corner should trigger, so
         // pointer.motion() will set the current focus to None.
-        if under.hot_corner && pointer.current_focus().is_none() {
-            if !was_inside_hot_corner
-                && pointer
-                    .with_grab(|_, grab| grab_allows_hot_corner(grab))
-                    .unwrap_or(true)
-            {
-                self.niri.layout.toggle_overview();
-            }
-            self.niri.pointer_inside_hot_corner = true;
-        }
+        let corner = under
+            .hot_corner
+            .filter(|_| pointer.current_focus().is_none());
+        let delta = event.delta();
+        let pushed = delta.x.hypot(delta.y);
+        self.hot_corner_motion(hot_corner, under.output.as_ref(), corner, Some(pushed));
+        self.update_border_resize_cursor();
 
         // Activate a new confinement if necessary.
         self.niri.maybe_activate_pointer_constraint();
@@ -2636,9 +3131,8 @@ impl State {
         &mut self,
         event: I::PointerMotionAbsoluteEvent,
     ) {
-        let was_inside_hot_corner = self.niri.pointer_inside_hot_corner;
         // Any of the early returns here mean that the pointer is not inside the hot corner.
-        self.niri.pointer_inside_hot_corner = false;
+        let hot_corner = self.leave_hot_corner();
 
         let Some(pos) = self.compute_absolute_location(&event, None).or_else(|| {
             self.global_bounding_rectangle().map(|output_geo| {
@@ -2695,16 +3189,11 @@ impl State {
 
         // contents_under() will return no surface when the hot corner should trigger, so
         // pointer.motion() will set the current focus to None.
-        if under.hot_corner && pointer.current_focus().is_none() {
-            if !was_inside_hot_corner
-                && pointer
-                    .with_grab(|_, grab| grab_allows_hot_corner(grab))
-                    .unwrap_or(true)
-            {
-                self.niri.layout.toggle_overview();
-            }
-            self.niri.pointer_inside_hot_corner = true;
-        }
+        let corner = under
+            .hot_corner
+            .filter(|_| pointer.current_focus().is_none());
+        self.hot_corner_motion(hot_corner, under.output.as_ref(), corner, None);
+        self.update_border_resize_cursor();
 
         self.niri.maybe_activate_pointer_constraint();
 
@@ -2735,12 +3224,42 @@ impl State {
 
         let serial = SERIAL_COUNTER.next_serial();
 
-        let button = event.button();
+        let mut button = event.button();
 
-        let button_code = event.button_code();
+        let mut button_code = event.button_code();
 
         let button_state = event.state();
 
+        let remap = {
+            let config = self.niri.config.borrow();
+            let device = event.device();
+            config
+                .input
+                .button_remap(&device.name(), button_code)
+                .cloned()
+        };
+        match remap {
+            Some(ButtonRemapTarget::Button(code)) => {
+                button_code = code;
+                button = mouse_button_from_code(code);
+            }
+            Some(ButtonRemapTarget::Action(action)) => {
+                if button_state == ButtonState::Pressed {
+                    self.niri.suppressed_buttons.insert(button_code);
+                    self.do_action(action, false);
+                } else {
+                    self.niri.suppressed_buttons.remove(&button_code);
+                }
+                return;
+            }
+            None => (),
+        }
+
+        // Clicking while holding a modifier makes it a chord rather than a tap.
+        if button_state == ButtonState::Pressed {
+            self.niri.modifier_tap.interrupt();
+        }
+
         let mod_key = self.backend.mod_key(&self.niri.config.borrow());
 
         // Ignore release events for mouse clicks that triggered a bind.
@@ -2774,7 +3293,11 @@ impl State {
                 }
             }
 
-            if is_mru_open || self.niri.mods_with_mouse_binds.contains(&modifiers) {
+            let is_bind_mode_active = self.niri.bind_mode.is_some();
+            if is_mru_open
+                || is_bind_mode_active
+                || self.niri.mods_with_mouse_binds.contains(&modifiers)
+            {
                 if let Some(bind) = match button {
                     Some(MouseButton::Left) => Some(Trigger::MouseLeft),
                     Some(MouseButton::Right) => Some(Trigger::MouseRight),
@@ -2785,8 +3308,12 @@ impl State {
                 }
                 .and_then(|trigger| {
                     let config = self.niri.config.borrow();
-                    let bindings =
-                        make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                    let bindings = make_binds_iter(
+                        &config,
+                        &mut self.niri.window_mru_ui,
+                        self.niri.bind_mode.as_deref(),
+                        modifiers,
+                    );
                     find_configured_bind(bindings, mod_key, trigger, mods)
                 }) {
                     self.niri.suppressed_buttons.insert(button_code);
@@ -2801,6 +3328,35 @@ impl State {
 
             let is_overview_open = self.niri.layout.is_overview_open();
 
+            if !is_overview_open
+                && !pointer.is_grabbed()
+                && modifiers.is_empty()
+                && !self.window_disables_pointer_gestures()
+            {
+                let config = self.niri.config.borrow();
+                let mouse = &config.gestures.mouse;
+                let gesture_button = mouse.button.map(|button| match button {
+                    MouseGestureButton::Right => MouseButton::Right,
+                    MouseGestureButton::Middle => MouseButton::Middle,
+                    MouseGestureButton::Back => MouseButton::Back,
+                    MouseGestureButton::Forward => MouseButton::Forward,
+                });
+
+                if button.is_some() && button == gesture_button && !mouse.strokes.is_empty() {
+                    let distance = config.gestures.thresholds.swipe_distance;
+                    drop(config);
+
+                    let start_data = PointerGrabStartData {
+                        focus: None,
+                        button: button_code,
+                        location: pointer.current_location(),
+                    };
+                    // The grab holds back the press until it knows whether it's a stroke.
+                    let grab = MouseGestureGrab::new(start_data, distance);
+                    pointer.set_grab(self, grab, serial, Focus::Keep);
+                }
+            }
+
             if is_overview_open && !pointer.is_grabbed() && button == Some(MouseButton::Right) {
                 if let Some((output, ws)) = self.niri.workspace_under_cursor(true) {
                     let ws_id = ws.id();
@@ -2906,6 +3462,33 @@ impl State {
                                     .set_cursor_image(CursorImageStatus::Named(icon));
                             }
                         }
+                    } else if self.niri.config.borrow().gestures.border_resize {
+                        // Check if we need to start an interactive resize by the window border.
+                        let location = pointer.current_location();
+                        let edges = self.niri.output_under(location).and_then(|(output, pos)| {
+                            self.niri.layout.border_resize_edges_under(output, pos)
+                        });
+
+                        if let Some(edges) = edges {
+                            self.niri.layout.activate_window(&window);
+
+                            if self
+                                .niri
+                                .layout
+                                .interactive_resize_begin(window.clone(), edges)
+                            {
+                                let start_data = PointerGrabStartData {
+                                    focus: None,
+                                    button: button_code,
+                                    location,
+                                };
+                                let grab = ResizeGrab::new(start_data, window.clone());
+                                pointer.set_grab(self, grab, serial, Focus::Clear);
+                                self.niri.cursor_manager.set_cursor_image(
+                                    CursorImageStatus::Named(edges.cursor_icon()),
+                                );
+                            }
+                        }
                     }
                 }
                 // Check if we need to start an interactive resize.
@@ -2999,6 +3582,27 @@ impl State {
             } else if let Some(output) = self.niri.output_under_cursor() {
                 self.niri.layout.focus_output(&output);
 
+                // Check if we need to start dragging the view by the background.
+                if !is_overview_open
+                    && button == Some(MouseButton::Left)
+                    && !pointer.is_grabbed()
+                    && self.niri.config.borrow().gestures.background_drag
+                    && is_background(&self.niri.pointer_contents)
+                {
+                    if let Some(mon) = self.niri.layout.monitor_for_output(&output) {
+                        let ws_id = mon.active_workspace_ref().id();
+
+                        let start_data = PointerGrabStartData {
+                            focus: None,
+                            button: button_code,
+                            location: pointer.current_location(),
+                        };
+                        // Horizontal drags scroll the view, and vertical drags switch workspaces.
+                        let grab = SpatialMovementGrab::new(start_data, output, ws_id, false);
+                        pointer.set_grab(self, grab, serial, Focus::Clear);
+                    }
+                }
+
                 // FIXME: granular.
                 self.niri.queue_redraw_all();
             }
@@ -3057,6 +3661,8 @@ impl State {
 
         let source = event.source();
 
+        self.niri.modifier_tap.interrupt();
+
         let mod_key = self.backend.mod_key(&self.niri.config.borrow());
 
         // We received an event for the regular pointer, so show it now. This is also needed for
@@ -3094,6 +3700,56 @@ impl State {
 
         let is_mru_open = self.niri.window_mru_ui.is_open();
 
+        // Handle wheel scroll bindings over the workspace background and the window borders.
+        if source == AxisSource::Wheel && !is_overview_open && !is_mru_open {
+            let mods = self.niri.seat.get_keyboard().unwrap().modifier_state();
+
+            let config = self.niri.config.borrow();
+            let binds = self.region_binds_under_pointer(&config);
+            let find = |trigger| {
+                binds.and_then(|binds| find_configured_bind(binds, mod_key, trigger, mods))
+            };
+            let bind_left = find(Trigger::WheelScrollLeft);
+            let bind_right = find(Trigger::WheelScrollRight);
+            let bind_up = find(Trigger::WheelScrollUp);
+            let bind_down = find(Trigger::WheelScrollDown);
+            drop(config);
+
+            let has_binds = bind_left.is_some()
+                || bind_right.is_some()
+                || bind_up.is_some()
+                || bind_down.is_some();
+            if has_binds {
+                let horizontal = horizontal_amount_v120.unwrap_or(0.);
+                let ticks = self.niri.horizontal_wheel_tracker.accumulate(horizontal);
+                if let Some(right) = bind_right {
+                    for _ in 0..ticks {
+                        self.handle_bind(right.clone());
+                    }
+                }
+                if let Some(left) = bind_left {
+                    for _ in ticks..0 {
+                        self.handle_bind(left.clone());
+                    }
+                }
+
+                let vertical = vertical_amount_v120.unwrap_or(0.);
+                let ticks = self.niri.vertical_wheel_tracker.accumulate(vertical);
+                if let Some(down) = bind_down {
+                    for _ in 0..ticks {
+                        self.handle_bind(down.clone());
+                    }
+                }
+                if let Some(up) = bind_up {
+                    for _ in ticks..0 {
+                        self.handle_bind(up.clone());
+                    }
+                }
+
+                return;
+            }
+        }
+
         // Handle wheel scroll bindings.
         if source == AxisSource::Wheel {
             // If we have a scroll bind with current modifiers, then accumulate and don't pass to
@@ -3117,6 +3773,7 @@ impl State {
                                 action: Action::FocusColumnLeftUnderMouse,
                                 repeat: true,
                                 cooldown: None,
+                                double_press: None,
                                 allow_when_locked: false,
                                 allow_inhibiting: false,
                                 hotkey_overlay_title: None,
@@ -3129,6 +3786,7 @@ impl State {
                                 action: Action::FocusColumnRightUnderMouse,
                                 repeat: true,
                                 cooldown: None,
+                                double_press: None,
                                 allow_when_locked: false,
                                 allow_inhibiting: false,
                                 hotkey_overlay_title: None,
@@ -3136,8 +3794,12 @@ impl State {
                             (bind_left, bind_right)
                         } else {
                             let config = self.niri.config.borrow();
-                            let bindings =
-                                make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                            let bindings = make_binds_iter(
+                                &config,
+                                &mut self.niri.window_mru_ui,
+                                self.niri.bind_mode.as_deref(),
+                                modifiers,
+                            );
                             let bind_left = find_configured_bind(
                                 bindings.clone(),
                                 mod_key,
@@ -3178,6 +3840,7 @@ impl State {
                             action: Action::FocusWorkspaceUpUnderMouse,
                             repeat: true,
                             cooldown: Some(Duration::from_millis(50)),
+                            double_press: None,
                             allow_when_locked: false,
                             allow_inhibiting: false,
                             hotkey_overlay_title: None,
@@ -3190,6 +3853,7 @@ impl State {
                             action: Action::FocusWorkspaceDownUnderMouse,
                             repeat: true,
                             cooldown: Some(Duration::from_millis(50)),
+                            double_press: None,
                             allow_when_locked: false,
                             allow_inhibiting: false,
                             hotkey_overlay_title: None,
@@ -3204,6 +3868,7 @@ impl State {
                             action: Action::FocusColumnLeftUnderMouse,
                             repeat: true,
                             cooldown: Some(Duration::from_millis(50)),
+                            double_press: None,
                             allow_when_locked: false,
                             allow_inhibiting: false,
                             hotkey_overlay_title: None,
@@ -3216,6 +3881,7 @@ impl State {
                             action: Action::FocusColumnRightUnderMouse,
                             repeat: true,
                             cooldown: Some(Duration::from_millis(50)),
+                            double_press: None,
                             allow_when_locked: false,
                             allow_inhibiting: false,
                             hotkey_overlay_title: None,
@@ -3223,8 +3889,12 @@ impl State {
                         (bind_up, bind_down)
                     } else {
                         let config = self.niri.config.borrow();
-                        let bindings =
-                            make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                        let bindings = make_binds_iter(
+                            &config,
+                            &mut self.niri.window_mru_ui,
+                            self.niri.bind_mode.as_deref(),
+                            modifiers,
+                        );
                         let bind_up = find_configured_bind(
                             bindings.clone(),
                             mod_key,
@@ -3368,8 +4038,12 @@ impl State {
                     .accumulate(horizontal);
                 if ticks != 0 {
                     let config = self.niri.config.borrow();
-                    let bindings =
-                        make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                    let bindings = make_binds_iter(
+                        &config,
+                        &mut self.niri.window_mru_ui,
+                        self.niri.bind_mode.as_deref(),
+                        modifiers,
+                    );
                     let bind_left = find_configured_bind(
                         bindings.clone(),
                         mod_key,
@@ -3398,8 +4072,12 @@ impl State {
                     .accumulate(vertical);
                 if ticks != 0 {
                     let config = self.niri.config.borrow();
-                    let bindings =
-                        make_binds_iter(&config, &mut self.niri.window_mru_ui, modifiers);
+                    let bindings = make_binds_iter(
+                        &config,
+                        &mut self.niri.window_mru_ui,
+                        self.niri.bind_mode.as_deref(),
+                        modifiers,
+                    );
                     let bind_up = find_configured_bind(
                         bindings.clone(),
                         mod_key,
@@ -3735,18 +4413,45 @@ impl State {
         }
     }
 
-    fn on_gesture_swipe_begin<I: InputBackend>(&mut self, event: I::GestureSwipeBeginEvent) {
+    fn on_gesture_swipe_begin<I: InputBackend + 'static>(
+        &mut self,
+        event: I::GestureSwipeBeginEvent,
+    ) where
+        I::Device: 'static,
+    {
         if self.niri.window_mru_ui.is_open() {
             // Don't start swipe gestures while in the MRU.
             return;
         }
 
-        if event.fingers() == 3 {
+        let fingers = u8::try_from(event.fingers()).unwrap_or(u8::MAX);
+
+        // A hold turned into a window move continues as a swipe.
+        if let Some(TouchpadGesture::Hold(gesture)) = self.niri.touchpad_gesture.take() {
+            if gesture.is_converted() && gesture.fingers() == fingers && self.begin_hold_drag_move()
+            {
+                self.niri.touchpad_gesture = Some(TouchpadGesture::Hold(gesture));
+
+                // We handled this event.
+                return;
+            }
+        }
+
+        // Gestures disabled on this device or over this window go straight to the client.
+        let enabled = self.is_gesture_enabled(&event.device(), GestureKind::Swipe);
+
+        let thresholds = self.gesture_thresholds(&event.device());
+        if enabled && self.gesture_bind_swipe_begin(fingers, thresholds) {
+            // We handled this event.
+            return;
+        }
+
+        if enabled && event.fingers() == 3 {
             self.niri.gesture_swipe_3f_cumulative = Some((0., 0.));
 
             // We handled this event.
             return;
-        } else if event.fingers() == 4 {
+        } else if enabled && event.fingers() == 4 {
             self.niri.layout.overview_gesture_begin();
             self.niri.queue_redraw_all();
 
@@ -3777,6 +4482,23 @@ impl State {
     ) where
         I::Device: 'static,
     {
+        if let Some(TouchpadGesture::Cancelled { .. }) = self.niri.touchpad_gesture {
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::Hold(_)) = self.niri.touchpad_gesture {
+            // Move the cursor like a regular pointer motion; the move grab carries the window.
+            let pointer = self.niri.seat.get_pointer().unwrap();
+            let location = pointer.current_location() + event.delta();
+            if self.niri.output_under(location).is_some() {
+                self.move_cursor(location);
+            }
+
+            // We handled this event.
+            return;
+        }
+
         let mut delta_x = event.delta_x();
         let mut delta_y = event.delta_y();
 
@@ -3787,6 +4509,21 @@ impl State {
             delta_y = libinput_event.dy_unaccelerated();
         }
 
+        let timestamp = Duration::from_micros(event.time());
+        if self.gesture_bind_swipe_update(delta_x, delta_y, timestamp) {
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::MaximizeSwipe(gesture)) = &mut self.niri.touchpad_gesture {
+            if let Some(step) = gesture.update(delta_y) {
+                self.do_maximize_swipe_step(step);
+            }
+
+            // We handled this event.
+            return;
+        }
+
         let uninverted_delta_y = delta_y;
 
         let device = event.device();
@@ -3809,7 +4546,19 @@ impl State {
                 self.niri.gesture_swipe_3f_cumulative = None;
 
                 if let Some(output) = self.niri.output_under_cursor() {
-                    if cx.abs() > cy.abs() {
+                    let is_horizontal = cx.abs() > cy.abs();
+                    let (horizontal, vertical) = self.three_finger_swipe_targets();
+                    let target = if is_horizontal { horizontal } else { vertical };
+
+                    if target == SwipeTarget::Client {
+                        let axis = if is_horizontal {
+                            Axis::Horizontal
+                        } else {
+                            Axis::Vertical
+                        };
+                        let gesture = TouchpadGesture::ClientScroll { fingers: 3, axis };
+                        self.niri.touchpad_gesture = Some(gesture);
+                    } else if is_horizontal {
                         let output_ws = if is_overview_open {
                             self.niri.workspace_under_cursor(true)
                         } else {
@@ -3827,6 +4576,11 @@ impl State {
                                 .layout
                                 .view_offset_gesture_begin(&output, Some(ws_idx), true);
                         }
+                    } else if !is_overview_open && self.niri.config.borrow().gestures.maximize_swipe
+                    {
+                        let distance = self.gesture_thresholds(&event.device()).swipe_distance;
+                        let gesture = MaximizeSwipeGesture::new(3, distance);
+                        self.niri.touchpad_gesture = Some(TouchpadGesture::MaximizeSwipe(gesture));
                     } else {
                         self.niri
                             .layout
@@ -3836,6 +4590,27 @@ impl State {
             }
         }
 
+        if let Some(TouchpadGesture::ClientScroll { axis, .. }) = self.niri.touchpad_gesture {
+            let amount = match axis {
+                Axis::Horizontal => delta_x,
+                Axis::Vertical => delta_y,
+            };
+
+            let pointer = self.niri.seat.get_pointer().unwrap();
+            if self.update_pointer_contents() {
+                pointer.frame(self);
+            }
+
+            let frame = AxisFrame::new(event.time_msec())
+                .source(AxisSource::Finger)
+                .value(axis, amount);
+            pointer.axis(self, frame);
+            pointer.frame(self);
+
+            // We handled this event.
+            return;
+        }
+
         let timestamp = Duration::from_micros(event.time());
 
         let mut handled = false;
@@ -3893,6 +4668,52 @@ impl State {
     }
 
     fn on_gesture_swipe_end<I: InputBackend>(&mut self, event: I::GestureSwipeEndEvent) {
+        if let Some(TouchpadGesture::Cancelled { .. }) = self.niri.touchpad_gesture {
+            self.niri.touchpad_gesture = None;
+
+            // We handled this event.
+            return;
+        }
+
+        let timestamp = Duration::from_micros(event.time());
+        if self.gesture_bind_swipe_end(timestamp, event.cancelled()) {
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::MaximizeSwipe(_)) = self.niri.touchpad_gesture {
+            self.niri.touchpad_gesture = None;
+
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::ClientScroll { axis, .. }) = self.niri.touchpad_gesture {
+            self.niri.touchpad_gesture = None;
+
+            // Stopping the scroll lets the window continue it with kinetic scrolling.
+            let pointer = self.niri.seat.get_pointer().unwrap();
+            let frame = AxisFrame::new(event.time_msec())
+                .source(AxisSource::Finger)
+                .stop(axis);
+            pointer.axis(self, frame);
+            pointer.frame(self);
+
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::Hold(_)) = self.niri.touchpad_gesture {
+            self.niri.touchpad_gesture = None;
+
+            // Drop the window where it is.
+            let pointer = self.niri.seat.get_pointer().unwrap();
+            pointer.unset_grab(self, SERIAL_COUNTER.next_serial(), event.time_msec());
+
+            // We handled this event.
+            return;
+        }
+
         self.niri.gesture_swipe_3f_cumulative = None;
 
         let mut handled = false;
@@ -3908,122 +4729,904 @@ impl State {
             handled = true;
         }
 
-        let res = self.niri.layout.overview_gesture_end();
-        if res {
+        let res = self.niri.layout.overview_gesture_end();
+        if res {
+            self.niri.queue_redraw_all();
+            handled = true;
+        }
+
+        if handled {
+            // We handled this event.
+            return;
+        }
+
+        let serial = SERIAL_COUNTER.next_serial();
+        let pointer = self.niri.seat.get_pointer().unwrap();
+
+        if self.update_pointer_contents() {
+            pointer.frame(self);
+        }
+
+        pointer.gesture_swipe_end(
+            self,
+            &GestureSwipeEndEvent {
+                serial,
+                time: event.time_msec(),
+                cancelled: event.cancelled(),
+            },
+        );
+    }
+
+    fn on_gesture_pinch_begin<I: InputBackend + 'static>(
+        &mut self,
+        event: I::GesturePinchBeginEvent,
+    ) where
+        I::Device: 'static,
+    {
+        let fingers = u8::try_from(event.fingers()).unwrap_or(u8::MAX);
+
+        // Gestures disabled on this device or over this window go straight to the client.
+        let enabled = self.is_gesture_enabled(&event.device(), GestureKind::Pinch);
+
+        if enabled && self.niri.config.borrow().gestures.magnifier_pinch == Some(fingers) {
+            if let Some(output) = self.niri.output_under_cursor() {
+                // Start zooming in only over the desktop, but zoom back out from anywhere.
+                if self.niri.magnifier.zoom(&output).is_some()
+                    || self.niri.window_under_cursor().is_none()
+                {
+                    self.niri.magnifier.gesture_begin(output);
+                    self.niri.touchpad_gesture = Some(TouchpadGesture::Magnifier { fingers });
+
+                    // We handled this event.
+                    return;
+                }
+            }
+        }
+
+        // Windows that zoom with pinches themselves, like image viewers and maps, get them as is.
+        let forward = self
+            .niri
+            .window_under_cursor()
+            .is_some_and(|mapped| mapped.rules().forward_pinch_gestures == Some(true));
+        let enabled = enabled && !forward;
+
+        if enabled && self.niri.config.borrow().gestures.resize_pinch == Some(fingers) {
+            if let Some(mapped) = self.niri.window_under_cursor() {
+                let window = mapped.window.clone();
+                let size = mapped.size().to_f64();
+                if mapped.is_floating()
+                    && self
+                        .niri
+                        .layout
+                        .interactive_resize_begin(window.clone(), ResizeEdge::BOTTOM_RIGHT)
+                {
+                    let gesture = ResizePinchGesture::new(fingers, window, size);
+                    self.niri.touchpad_gesture = Some(TouchpadGesture::ResizePinch(gesture));
+
+                    // We handled this event.
+                    return;
+                }
+            }
+        }
+
+        let thresholds = self.gesture_thresholds(&event.device());
+        if enabled && self.gesture_bind_pinch_begin(fingers, thresholds) {
+            // We handled this event.
+            return;
+        }
+
+        if enabled && self.niri.config.borrow().gestures.overview_pinch == Some(fingers) {
+            let gesture = OverviewPinchGesture::new(fingers);
+            self.niri.touchpad_gesture = Some(TouchpadGesture::OverviewPinch(gesture));
+            self.niri.layout.overview_gesture_begin();
+            self.niri.queue_redraw_all();
+
+            // We handled this event.
+            return;
+        }
+
+        let serial = SERIAL_COUNTER.next_serial();
+        let pointer = self.niri.seat.get_pointer().unwrap();
+
+        if self.update_pointer_contents() {
+            pointer.frame(self);
+        }
+
+        pointer.gesture_pinch_begin(
+            self,
+            &GesturePinchBeginEvent {
+                serial,
+                time: event.time_msec(),
+                fingers: event.fingers(),
+            },
+        );
+    }
+
+    fn on_gesture_pinch_update<I: InputBackend>(&mut self, event: I::GesturePinchUpdateEvent) {
+        if let Some(TouchpadGesture::Cancelled { .. }) = self.niri.touchpad_gesture {
+            // We handled this event.
+            return;
+        }
+
+        if self.gesture_bind_pinch_update(event.scale(), event.rotation()) {
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::ResizePinch(gesture)) = &mut self.niri.touchpad_gesture {
+            let delta = gesture.update(event.scale());
+            let window = gesture.window().clone();
+            if self.niri.layout.interactive_resize_update(&window, delta) {
+                // FIXME: granular.
+                self.niri.queue_redraw_all();
+            }
+
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::Magnifier { .. }) = self.niri.touchpad_gesture {
+            if let Some(output) = self.niri.magnifier.gesture_update(event.scale()) {
+                let output = output.clone();
+                self.niri.queue_redraw(&output);
+            }
+
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::OverviewPinch(gesture)) = &mut self.niri.touchpad_gesture {
+            let delta = gesture.update(event.scale());
+            let timestamp = Duration::from_micros(event.time());
+            if let Some(true) = self.niri.layout.overview_gesture_update(delta, timestamp) {
+                self.niri.queue_redraw_all();
+            }
+
+            // We handled this event.
+            return;
+        }
+
+        let pointer = self.niri.seat.get_pointer().unwrap();
+
+        if self.update_pointer_contents() {
+            pointer.frame(self);
+        }
+
+        pointer.gesture_pinch_update(
+            self,
+            &GesturePinchUpdateEvent {
+                time: event.time_msec(),
+                delta: event.delta(),
+                scale: event.scale(),
+                rotation: event.rotation(),
+            },
+        );
+    }
+
+    fn on_gesture_pinch_end<I: InputBackend>(&mut self, event: I::GesturePinchEndEvent) {
+        if let Some(TouchpadGesture::Cancelled { .. }) = self.niri.touchpad_gesture {
+            self.niri.touchpad_gesture = None;
+
+            // We handled this event.
+            return;
+        }
+
+        if self.gesture_bind_pinch_end(event.cancelled()) {
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::ResizePinch(gesture)) = self.niri.touchpad_gesture.take() {
+            // Commit the size reached so far.
+            self.niri.layout.interactive_resize_end(gesture.window());
+
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::Magnifier { .. }) = self.niri.touchpad_gesture {
+            self.niri.touchpad_gesture = None;
+            self.niri.magnifier.gesture_end();
+
+            // We handled this event.
+            return;
+        }
+
+        if let Some(TouchpadGesture::OverviewPinch(_)) = self.niri.touchpad_gesture {
+            self.niri.touchpad_gesture = None;
+
+            if self.niri.layout.overview_gesture_end() {
+                self.niri.queue_redraw_all();
+            }
+
+            // We handled this event.
+            return;
+        }
+
+        let serial = SERIAL_COUNTER.next_serial();
+        let pointer = self.niri.seat.get_pointer().unwrap();
+
+        if self.update_pointer_contents() {
+            pointer.frame(self);
+        }
+
+        pointer.gesture_pinch_end(
+            self,
+            &GesturePinchEndEvent {
+                serial,
+                time: event.time_msec(),
+                cancelled: event.cancelled(),
+            },
+        );
+    }
+
+    fn on_gesture_hold_begin<I: InputBackend + 'static>(&mut self, event: I::GestureHoldBeginEvent)
+    where
+        I::Device: 'static,
+    {
+        // Drop a converted hold that wasn't followed by a swipe.
+        self.niri.touchpad_gesture = None;
+
+        let fingers = u8::try_from(event.fingers()).unwrap_or(u8::MAX);
+        let hold_drag_move = self.niri.config.borrow().gestures.hold_drag_move == Some(fingers);
+
+        // Gestures disabled on this device or over this window go straight to the client.
+        let enabled = self.is_gesture_enabled(&event.device(), GestureKind::Hold);
+
+        if enabled && (hold_drag_move || self.has_gesture_binds(GestureKind::Hold, fingers)) {
+            let thresholds = self.gesture_thresholds(&event.device());
+            let gesture = HoldGesture::new(fingers, event.time_msec(), thresholds);
+            self.niri.touchpad_gesture = Some(TouchpadGesture::Hold(gesture));
+            self.ipc_gesture_begin(GestureKind::Hold, fingers);
+
+            // Applications use two-finger holds to stop kinetic scrolling, so they keep getting
+            // them alongside the binds.
+            if fingers != 2 {
+                // We handled this event.
+                return;
+            }
+        }
+
+        let serial = SERIAL_COUNTER.next_serial();
+        let pointer = self.niri.seat.get_pointer().unwrap();
+
+        if self.update_pointer_contents() {
+            pointer.frame(self);
+        }
+
+        pointer.gesture_hold_begin(
+            self,
+            &GestureHoldBeginEvent {
+                serial,
+                time: event.time_msec(),
+                fingers: event.fingers(),
+            },
+        );
+    }
+
+    fn on_gesture_hold_end<I: InputBackend>(&mut self, event: I::GestureHoldEndEvent) {
+        if let Some(TouchpadGesture::Cancelled { fingers, .. }) = self.niri.touchpad_gesture {
+            self.niri.touchpad_gesture = None;
+
+            // Applications also got the beginning of two-finger holds.
+            if fingers != 2 {
+                // We handled this event.
+                return;
+            }
+        }
+
+        if let Some(TouchpadGesture::Hold(gesture)) = &mut self.niri.touchpad_gesture {
+            let fingers = gesture.fingers();
+            let too_short = !gesture.is_long_enough(event.time_msec());
+
+            // A cancelled hold means that the fingers started moving or were lifted early.
+            if event.cancelled() {
+                // Keep the hold around so that the swipe that follows can move the window.
+                let hold_drag_move =
+                    self.niri.config.borrow().gestures.hold_drag_move == Some(fingers);
+                if !(hold_drag_move && gesture.convert(event.time_msec())) {
+                    self.niri.touchpad_gesture = None;
+                }
+            } else {
+                self.niri.touchpad_gesture = None;
+                if !too_short {
+                    self.trigger_gesture_bind(GestureKind::Hold, fingers, None, false);
+                }
+            }
+
+            self.ipc_gesture_end(event.cancelled() || too_short);
+
+            // Applications also got the beginning of two-finger holds.
+            if fingers != 2 {
+                // We handled this event.
+                return;
+            }
+        }
+
+        let serial = SERIAL_COUNTER.next_serial();
+        let pointer = self.niri.seat.get_pointer().unwrap();
+
+        if self.update_pointer_contents() {
+            pointer.frame(self);
+        }
+
+        pointer.gesture_hold_end(
+            self,
+            &GestureHoldEndEvent {
+                serial,
+                time: event.time_msec(),
+                cancelled: event.cancelled(),
+            },
+        );
+    }
+
+    fn do_maximize_swipe_step(&mut self, step: MaximizeSwipeStep) {
+        let Some(mapped) = self.niri.layout.focus() else {
+            return;
+        };
+
+        let window = mapped.window.clone();
+        let sizing_mode = mapped.pending_sizing_mode();
+        match step {
+            MaximizeSwipeStep::Maximize => {
+                if sizing_mode.is_normal() {
+                    self.niri.layout.toggle_maximized(&window);
+                }
+            }
+            MaximizeSwipeStep::Fullscreen => {
+                if !sizing_mode.is_fullscreen() {
+                    self.niri.layout.toggle_fullscreen(&window);
+                }
+            }
+            MaximizeSwipeStep::Restore => {
+                if sizing_mode.is_fullscreen() {
+                    self.niri.layout.toggle_fullscreen(&window);
+                } else if sizing_mode.is_maximized() {
+                    self.niri.layout.toggle_maximized(&window);
+                }
+            }
+        }
+
+        // FIXME: granular
+        self.niri.queue_redraw_all();
+    }
+
+    /// Returns where the built-in three-finger swipe goes horizontally and vertically.
+    ///
+    /// In the overview and during drag-and-drop, where windows don't get scroll events, the swipe
+    /// always goes to the layout. Otherwise, the window rules of the window under the cursor take
+    /// precedence over the config.
+    fn three_finger_swipe_targets(&self) -> (SwipeTarget, SwipeTarget) {
+        let pointer = self.niri.seat.get_pointer().unwrap();
+        let is_dnd_grab = pointer
+            .with_grab(|_, grab| State::is_dnd_grab(grab.as_any()))
+            .unwrap_or(false);
+        if self.niri.layout.is_overview_open() || is_dnd_grab {
+            return (SwipeTarget::Layout, SwipeTarget::Layout);
+        }
+
+        let mut targets = self.niri.config.borrow().gestures.three_finger_swipe;
+        if let Some(mapped) = self.niri.window_under_cursor() {
+            targets.merge_with(&mapped.rules().three_finger_swipe);
+        }
+
+        (
+            targets.horizontal.unwrap_or_default(),
+            targets.vertical.unwrap_or_default(),
+        )
+    }
+
+    /// Begins a swipe for the gesture binds; returns `false` if there are none for the fingers.
+    fn gesture_bind_swipe_begin(&mut self, fingers: u8, thresholds: GestureThresholds) -> bool {
+        if !self.has_gesture_binds(GestureKind::Swipe, fingers) {
+            return false;
+        }
+
+        let diagonals = self.has_diagonal_swipe_bind(fingers);
+        let fling_directions = self.fling_swipe_directions(fingers);
+        let gesture = SwipeGesture::new(fingers, thresholds, diagonals, fling_directions);
+        self.niri.touchpad_gesture = Some(TouchpadGesture::Swipe(gesture));
+        self.ipc_gesture_begin(GestureKind::Swipe, fingers);
+        true
+    }
+
+    /// Updates the gesture bind swipe in progress; returns `false` if there isn't one.
+    fn gesture_bind_swipe_update(&mut self, dx: f64, dy: f64, timestamp: Duration) -> bool {
+        let Some(TouchpadGesture::Swipe(gesture)) = &mut self.niri.touchpad_gesture else {
+            return false;
+        };
+
+        let fingers = gesture.fingers();
+        let triggered = gesture.update(dx, dy, timestamp);
+        let progress = triggered.map(|d| (d, 1.)).or_else(|| gesture.progress());
+
+        if let Some((direction, progress)) = progress {
+            self.ipc_gesture_update(direction, progress);
+        }
+        // This also runs when a triggered swipe changes direction, and the bind for the new
+        // direction takes over from the previous one.
+        if let Some(direction) = triggered {
+            self.trigger_gesture_bind(GestureKind::Swipe, fingers, Some(direction), false);
+        }
+        true
+    }
+
+    /// Ends the gesture bind swipe in progress; returns `false` if there isn't one.
+    fn gesture_bind_swipe_end(&mut self, timestamp: Duration, cancelled: bool) -> bool {
+        let Some(TouchpadGesture::Swipe(gesture)) = &mut self.niri.touchpad_gesture else {
+            return false;
+        };
+
+        let fingers = gesture.fingers();
+        let ended = gesture.end(timestamp);
+        self.niri.touchpad_gesture = None;
+
+        // Swipes in directions with fling binds trigger at the end.
+        if let Some((direction, fling)) = ended {
+            if !cancelled {
+                self.ipc_gesture_update(direction, 1.);
+                self.trigger_gesture_bind(GestureKind::Swipe, fingers, Some(direction), fling);
+            }
+        }
+
+        self.ipc_gesture_end(cancelled);
+        true
+    }
+
+    /// Begins a pinch for the gesture binds; returns `false` if there are none for the fingers.
+    fn gesture_bind_pinch_begin(&mut self, fingers: u8, thresholds: GestureThresholds) -> bool {
+        if !self.has_gesture_binds(GestureKind::Pinch, fingers) {
+            return false;
+        }
+
+        let gesture = PinchGesture::new(fingers, thresholds);
+        self.niri.touchpad_gesture = Some(TouchpadGesture::Pinch(gesture));
+        self.ipc_gesture_begin(GestureKind::Pinch, fingers);
+        true
+    }
+
+    /// Updates the gesture bind pinch in progress; returns `false` if there isn't one.
+    ///
+    /// The scale is relative to the start of the pinch, and the rotation is a delta in degrees.
+    fn gesture_bind_pinch_update(&mut self, scale: f64, rotation: f64) -> bool {
+        let Some(TouchpadGesture::Pinch(gesture)) = &mut self.niri.touchpad_gesture else {
+            return false;
+        };
+
+        let fingers = gesture.fingers();
+        let triggered = gesture.update(scale, rotation);
+        let progress = triggered.map(|d| (d, 1.)).or_else(|| gesture.progress());
+
+        if let Some((direction, progress)) = progress {
+            self.ipc_gesture_update(direction, progress);
+        }
+        if let Some(direction) = triggered {
+            self.trigger_gesture_bind(GestureKind::Pinch, fingers, Some(direction), false);
+        }
+        true
+    }
+
+    /// Ends the gesture bind pinch in progress; returns `false` if there isn't one.
+    fn gesture_bind_pinch_end(&mut self, cancelled: bool) -> bool {
+        let Some(TouchpadGesture::Pinch(_)) = self.niri.touchpad_gesture else {
+            return false;
+        };
+
+        self.niri.touchpad_gesture = None;
+        self.ipc_gesture_end(cancelled);
+        true
+    }
+
+    /// Runs a scripted touchpad gesture through the gesture binds.
+    ///
+    /// The updates are spread evenly over the gesture duration with synthetic timestamps, so
+    /// that fling detection sees the same velocity as for a real gesture.
+    fn simulate_gesture(&mut self, simulated: SimulatedGesture) {
+        if self.niri.touchpad_gesture.is_some() {
+            warn!("not simulating a gesture while a touchpad gesture is in progress");
+            return;
+        }
+
+        let SimulatedGesture {
+            kind,
+            fingers,
+            dx,
+            dy,
+            scale,
+            rotation,
+            duration_ms,
+            cancelled,
+        } = simulated;
+
+        // One update per 10 ms, roughly what a touchpad sends.
+        let steps = (duration_ms / 10).max(1);
+        let duration = Duration::from_millis(u64::from(duration_ms));
+        let start = get_monotonic_time();
+        let timestamp = |step: u32| start + duration * step / steps;
+
+        match kind {
+            GestureKind::Swipe => {
+                let thresholds = self.niri.config.borrow().gestures.thresholds;
+                if !self.gesture_bind_swipe_begin(fingers, thresholds) {
+                    warn!("no swipe gesture binds for {fingers} fingers");
+                    return;
+                }
+
+                let (step_dx, step_dy) = (dx / f64::from(steps), dy / f64::from(steps));
+                for step in 1..=steps {
+                    self.gesture_bind_swipe_update(step_dx, step_dy, timestamp(step));
+                }
+                self.gesture_bind_swipe_end(timestamp(steps), cancelled);
+            }
+            GestureKind::Pinch => {
+                let thresholds = self.niri.config.borrow().gestures.thresholds;
+                if !self.gesture_bind_pinch_begin(fingers, thresholds) {
+                    warn!("no pinch gesture binds for {fingers} fingers");
+                    return;
+                }
+
+                let step_rotation = rotation / f64::from(steps);
+                for step in 1..=steps {
+                    let progress = f64::from(step) / f64::from(steps);
+                    let step_scale = 1. + (scale - 1.) * progress;
+                    self.gesture_bind_pinch_update(step_scale, step_rotation);
+                }
+                self.gesture_bind_pinch_end(cancelled);
+            }
+            GestureKind::Hold => {
+                if !self.has_gesture_binds(GestureKind::Hold, fingers) {
+                    warn!("no hold gesture binds for {fingers} fingers");
+                    return;
+                }
+
+                self.ipc_gesture_begin(GestureKind::Hold, fingers);
+                if !cancelled {
+                    self.trigger_gesture_bind(GestureKind::Hold, fingers, None, false);
+                }
+                self.ipc_gesture_end(cancelled);
+            }
+        }
+    }
+
+    /// Cancels the gesture bind in progress so that it doesn't trigger anything else.
+    ///
+    /// Also drops a gesture sequence waiting for its next gesture. Returns whether there was
+    /// anything to cancel.
+    fn cancel_gesture(&mut self) -> bool {
+        let mut cancelled = false;
+
+        if let Some(gesture) = &self.niri.touchpad_gesture {
+            if gesture.is_cancellable() {
+                let kind = gesture.kind();
+                let fingers = gesture.fingers();
+                self.niri.touchpad_gesture = Some(TouchpadGesture::Cancelled { kind, fingers });
+                self.ipc_gesture_end(true);
+                cancelled = true;
+            }
+        }
+
+        // Touchscreen gestures only show up on IPC as they trigger, so there's nothing to report.
+        if self.niri.touchscreen_gesture.abort() {
+            cancelled = true;
+        }
+
+        if self.niri.gesture_sequence.is_pending() {
+            // Drop the held back bind too.
+            self.niri.gesture_sequence.abandon();
+            if let Some(token) = self.niri.gesture_sequence_timer.take() {
+                self.niri.event_loop.remove(token);
+            }
+            cancelled = true;
+        }
+
+        cancelled
+    }
+
+    /// Starts moving the window under the cursor for a hold turned into a drag.
+    fn begin_hold_drag_move(&mut self) -> bool {
+        let pointer = self.niri.seat.get_pointer().unwrap();
+        if pointer.is_grabbed() {
+            return false;
+        }
+
+        let Some(mapped) = self.niri.window_under_cursor() else {
+            return false;
+        };
+        let window = mapped.window.clone();
+
+        let start_data = PointerGrabStartData {
+            focus: None,
+            // BTN_LEFT, so that any click ends the move like releasing the button would.
+            button: 0x110,
+            location: pointer.current_location(),
+        };
+        let start_data = PointerOrTouchStartData::Pointer(start_data);
+        let icon = CursorIcon::Grabbing;
+        let Some(grab) = MoveGrab::new(self, start_data, window.clone(), false, Some(icon)) else {
+            return false;
+        };
+
+        self.niri.layout.activate_window(&window);
+        pointer.set_grab(self, grab, SERIAL_COUNTER.next_serial(), Focus::Clear);
+        self.niri
+            .cursor_manager
+            .set_cursor_image(CursorImageStatus::Named(icon));
+
+        true
+    }
+
+    /// Shows a resize cursor while the pointer hovers a window border that can be dragged.
+    fn update_border_resize_cursor(&mut self) {
+        if !self.niri.config.borrow().gestures.border_resize || self.niri.screenshot_ui.is_open() {
+            return;
+        }
+
+        let pointer = self.niri.seat.get_pointer().unwrap();
+        if pointer.is_grabbed() || pointer.current_focus().is_some() {
+            return;
+        }
+
+        // Leave the hidden cursor and the cursors set by clients alone.
+        if !matches!(
+            self.niri.cursor_manager.cursor_image(),
+            CursorImageStatus::Named(_)
+        ) {
+            return;
+        }
+
+        let edges = self
+            .niri
+            .output_under(pointer.current_location())
+            .and_then(|(output, pos)| self.niri.layout.border_resize_edges_under(output, pos));
+        let icon = edges.map_or(CursorIcon::Default, |edges| edges.cursor_icon());
+
+        let image = CursorImageStatus::Named(icon);
+        if *self.niri.cursor_manager.cursor_image() != image {
+            self.niri.cursor_manager.set_cursor_image(image);
+        }
+    }
+
+    /// Returns the region binds for the part of the screen under the pointer, if any.
+    fn region_binds_under_pointer<'a>(&self, config: &'a Config) -> Option<&'a [Bind]> {
+        if self.niri.is_locked()
+            || self.niri.exit_confirm_dialog.is_open()
+            || self.niri.screenshot_ui.is_open()
+        {
+            return None;
+        }
+
+        let contents = &self.niri.pointer_contents;
+        contents.output.as_ref()?;
+
+        match &contents.window {
+            Some((_, HitType::Activate { .. })) => Some(&config.region_binds.border[..]),
+            Some((_, HitType::Input { .. })) => None,
+            None => is_background(contents).then_some(&config.region_binds.background[..]),
+        }
+    }
+
+    /// Returns whether niri recognizes this kind of gesture coming from the device.
+    fn is_gesture_enabled(&self, device: &impl Device, kind: GestureKind) -> bool {
+        if self.window_disables_pointer_gestures() {
+            return false;
+        }
+
+        let name = device.name();
+        !self
+            .niri
+            .config
+            .borrow()
+            .gestures
+            .is_disabled_on_device(&name, kind)
+    }
+
+    /// Returns whether the window under the cursor wants all pointer input without gestures.
+    fn window_disables_pointer_gestures(&self) -> bool {
+        self.niri
+            .window_under_cursor()
+            .is_some_and(|mapped| mapped.rules().disable_pointer_gestures == Some(true))
+    }
+
+    fn confirm_force_close_window(&mut self, target: ForceCloseTarget) {
+        // Without the dialog, rather do nothing than kill without asking.
+        if self
+            .niri
+            .exit_confirm_dialog
+            .show(Confirmation::ForceClose(target))
+        {
             self.niri.queue_redraw_all();
-            handled = true;
         }
+    }
 
-        if handled {
-            // We handled this event.
+    fn force_close_window(&mut self, target: &ForceCloseTarget) {
+        // The window could've closed while the dialog was open, and its PID reused since.
+        let window = self
+            .niri
+            .layout
+            .windows()
+            .find(|(_, m)| m.id() == target.window);
+        let Some((_, mapped)) = window else {
+            return;
+        };
+        if mapped.credentials().map(|c| c.pid) != Some(target.pid) {
             return;
         }
 
-        let serial = SERIAL_COUNTER.next_serial();
-        let pointer = self.niri.seat.get_pointer().unwrap();
-
-        if self.update_pointer_contents() {
-            pointer.frame(self);
+        info!("force closing window by killing PID {}", target.pid);
+        // SAFETY: kill() doesn't touch any memory.
+        if unsafe { libc::kill(target.pid, libc::SIGKILL) } != 0 {
+            let err = std::io::Error::last_os_error();
+            warn!("error killing PID {}: {err}", target.pid);
         }
+    }
 
-        pointer.gesture_swipe_end(
-            self,
-            &GestureSwipeEndEvent {
-                serial,
-                time: event.time_msec(),
-                cancelled: event.cancelled(),
-            },
-        );
+    /// Returns the gesture thresholds for the device, scaled to its size when libinput knows it.
+    fn gesture_thresholds(&self, device: &(impl Device + 'static)) -> GestureThresholds {
+        let width_mm = (device as &dyn Any)
+            .downcast_ref::<input::Device>()
+            .and_then(|device| device.size())
+            .map(|(w, _h)| w);
+
+        self.niri
+            .config
+            .borrow()
+            .gestures
+            .thresholds_for_device(&device.name(), width_mm)
     }
 
-    fn on_gesture_pinch_begin<I: InputBackend>(&mut self, event: I::GesturePinchBeginEvent) {
-        let serial = SERIAL_COUNTER.next_serial();
-        let pointer = self.niri.seat.get_pointer().unwrap();
+    /// Calls `f` with the focused window and tablet mode, used to pick the gesture profile.
+    fn with_gesture_target<T>(&self, f: impl FnOnce(GestureTarget) -> T) -> T {
+        let (app_id, title) = match self.niri.layout.focus() {
+            Some(mapped) => with_toplevel_role(mapped.toplevel(), |role| {
+                (role.app_id.clone(), role.title.clone())
+            }),
+            None => (None, None),
+        };
 
-        if self.update_pointer_contents() {
-            pointer.frame(self);
+        f(GestureTarget {
+            app_id: app_id.as_deref(),
+            title: title.as_deref(),
+            is_tablet_mode: self.niri.is_tablet_mode,
+        })
+    }
+
+    /// Returns the gesture progress shown by the progress indicator, if it's enabled.
+    fn shown_gesture_progress(&self) -> Option<(GestureDirection, f64)> {
+        if !self.niri.config.borrow().gestures.progress_indicator {
+            return None;
         }
 
-        pointer.gesture_pinch_begin(
-            self,
-            &GesturePinchBeginEvent {
-                serial,
-                time: event.time_msec(),
-                fingers: event.fingers(),
-            },
-        );
+        let gesture = self.niri.touchpad_gesture.as_ref()?;
+        gesture.progress()
     }
 
-    fn on_gesture_pinch_update<I: InputBackend>(&mut self, event: I::GesturePinchUpdateEvent) {
-        let pointer = self.niri.seat.get_pointer().unwrap();
+    fn has_gesture_binds(&self, kind: GestureKind, fingers: u8) -> bool {
+        let config = self.niri.config.borrow();
+        let overrides = &self.niri.gesture_overrides;
+        self.with_gesture_target(|target| {
+            config
+                .gestures
+                .has_any_bind(target, overrides, kind, fingers)
+        })
+    }
 
-        if self.update_pointer_contents() {
-            pointer.frame(self);
-        }
+    fn has_diagonal_swipe_bind(&self, fingers: u8) -> bool {
+        let config = self.niri.config.borrow();
+        let overrides = &self.niri.gesture_overrides;
+        self.with_gesture_target(|target| {
+            config
+                .gestures
+                .has_diagonal_swipe_bind(target, overrides, fingers)
+        })
+    }
 
-        pointer.gesture_pinch_update(
-            self,
-            &GesturePinchUpdateEvent {
-                time: event.time_msec(),
-                delta: event.delta(),
-                scale: event.scale(),
-                rotation: event.rotation(),
-            },
-        );
+    fn fling_swipe_directions(&self, fingers: u8) -> Vec<GestureDirection> {
+        let config = self.niri.config.borrow();
+        let overrides = &self.niri.gesture_overrides;
+        self.with_gesture_target(|target| {
+            config
+                .gestures
+                .fling_swipe_directions(target, overrides, fingers)
+        })
     }
 
-    fn on_gesture_pinch_end<I: InputBackend>(&mut self, event: I::GesturePinchEndEvent) {
-        let serial = SERIAL_COUNTER.next_serial();
-        let pointer = self.niri.seat.get_pointer().unwrap();
+    fn trigger_gesture_bind(
+        &mut self,
+        kind: GestureKind,
+        fingers: u8,
+        direction: Option<GestureDirection>,
+        fling: bool,
+    ) {
+        let trigger = GestureTrigger {
+            kind,
+            fingers,
+            direction,
+            fling,
+        };
 
-        if self.update_pointer_contents() {
-            pointer.frame(self);
+        // Stop the previous sequence timeout if any.
+        if let Some(token) = self.niri.gesture_sequence_timer.take() {
+            self.niri.event_loop.remove(token);
         }
 
-        pointer.gesture_pinch_end(
-            self,
-            &GesturePinchEndEvent {
-                serial,
-                time: event.time_msec(),
-                cancelled: event.cancelled(),
-            },
-        );
-    }
+        let (triggers, timeout) = {
+            let config = self.niri.config.borrow();
+            let gestures = &config.gestures;
+            let triggers = self
+                .niri
+                .gesture_sequence
+                .step(&gestures.sequences, trigger);
+            (triggers, gestures.thresholds.sequence_timeout_ms)
+        };
 
-    fn on_gesture_hold_begin<I: InputBackend>(&mut self, event: I::GestureHoldBeginEvent) {
-        let serial = SERIAL_COUNTER.next_serial();
-        let pointer = self.niri.seat.get_pointer().unwrap();
+        for trigger in triggers {
+            self.do_sequence_trigger(trigger);
+        }
 
-        if self.update_pointer_contents() {
-            pointer.frame(self);
+        if self.niri.gesture_sequence.is_pending() {
+            let timer = Timer::from_duration(Duration::from_millis(u64::from(timeout)));
+            let token = self
+                .niri
+                .event_loop
+                .insert_source(timer, |_, _, state| {
+                    state.niri.gesture_sequence_timer = None;
+                    if let Some(trigger) = state.niri.gesture_sequence.abandon() {
+                        state.do_sequence_trigger(trigger);
+                    }
+                    TimeoutAction::Drop
+                })
+                .unwrap();
+            self.niri.gesture_sequence_timer = Some(token);
         }
+    }
 
-        pointer.gesture_hold_begin(
-            self,
-            &GestureHoldBeginEvent {
-                serial,
-                time: event.time_msec(),
-                fingers: event.fingers(),
-            },
-        );
+    fn do_sequence_trigger(&mut self, trigger: SequenceTrigger) {
+        match trigger {
+            SequenceTrigger::Bind(trigger) => self.do_gesture_bind(trigger),
+            SequenceTrigger::Sequence(action) => self.do_gesture_action(action),
+        }
     }
 
-    fn on_gesture_hold_end<I: InputBackend>(&mut self, event: I::GestureHoldEndEvent) {
-        let serial = SERIAL_COUNTER.next_serial();
-        let pointer = self.niri.seat.get_pointer().unwrap();
+    fn do_gesture_bind(&mut self, trigger: GestureTrigger) {
+        let action = {
+            let config = self.niri.config.borrow();
+            self.with_gesture_target(|target| {
+                let bind = config.gestures.find_bind(
+                    target,
+                    &self.niri.gesture_overrides,
+                    trigger.kind,
+                    trigger.fingers,
+                    trigger.direction,
+                    trigger.fling,
+                );
+                bind.map(|bind| bind.action.clone())
+            })
+        };
 
-        if self.update_pointer_contents() {
-            pointer.frame(self);
+        if let Some(action) = action {
+            self.do_gesture_action(action);
         }
+    }
 
-        pointer.gesture_hold_end(
-            self,
-            &GestureHoldEndEvent {
-                serial,
-                time: event.time_msec(),
-                cancelled: event.cancelled(),
-            },
-        );
+    /// Triggers the bind for a touchscreen gesture.
+    ///
+    /// Touchscreen gestures only decide their kind as they trigger, so they're reported to IPC all
+    /// at once.
+    fn trigger_touchscreen_gesture(&mut self, trigger: GestureTrigger) {
+        let GestureTrigger {
+            kind,
+            fingers,
+            direction,
+            fling,
+        } = trigger;
+
+        self.ipc_gesture_begin(kind, fingers);
+        if let Some(direction) = direction {
+            self.ipc_gesture_update(direction, 1.);
+        }
+        self.ipc_gesture_end(false);
+
+        self.trigger_gesture_bind(kind, fingers, direction, fling);
     }
 
     fn compute_absolute_location<I: InputBackend>(
@@ -4061,6 +5664,39 @@ impl State {
         };
         let slot = evt.slot();
 
+        self.niri.touchscreen_gesture.down(slot, pos);
+
+        // Edge swipes are single-finger, so only the first finger can start one.
+        let edge = if self.niri.touchscreen_gesture.fingers() == 1 {
+            self.touch_edge_at(pos)
+        } else {
+            None
+        };
+        self.niri.touch_edge_swipe.down(slot, edge, pos);
+
+        if self.niri.touchscreen_gesture.can_start() {
+            let fingers = self.niri.touchscreen_gesture.fingers();
+            let has_binds = [GestureKind::Swipe, GestureKind::Pinch, GestureKind::Hold]
+                .into_iter()
+                .any(|kind| self.has_gesture_binds(kind, fingers));
+            if has_binds {
+                // Take the contacts away from the clients for the rest of the gesture.
+                if !self.niri.touchscreen_gesture.is_active() {
+                    handle.cancel(self);
+                }
+                let thresholds = self.niri.config.borrow().gestures.thresholds;
+                let diagonals = self.has_diagonal_swipe_bind(fingers);
+                let fling_directions = self.fling_swipe_directions(fingers);
+                self.niri
+                    .touchscreen_gesture
+                    .start(thresholds, diagonals, fling_directions);
+                return;
+            }
+        }
+        if self.niri.touchscreen_gesture.is_active() {
+            return;
+        }
+
         let serial = SERIAL_COUNTER.next_serial();
 
         let under = self.niri.contents_under(pos);
@@ -4160,9 +5796,41 @@ impl State {
 
                 // FIXME: granular.
                 self.niri.queue_redraw_all();
-            } else if let Some(output) = under.output {
+            } else if let Some(output) = under.output.clone() {
                 self.niri.layout.focus_output(&output);
 
+                // Check if we need to start dragging the view by the background.
+                if !self.niri.layout.is_overview_open()
+                    && self.niri.config.borrow().gestures.background_drag
+                    && is_background(&under)
+                {
+                    let ws_id = self
+                        .niri
+                        .layout
+                        .monitor_for_output(&output)
+                        .map(|mon| mon.active_workspace_ref().id());
+                    let pos_within_output = self.niri.output_under(pos).unwrap().1;
+
+                    let start_data = TouchGrabStartData {
+                        focus: None,
+                        slot,
+                        location: pos,
+                    };
+                    let start_timestamp = Duration::from_micros(evt.time());
+                    // Without a window, the overview grab only scrolls the view or switches
+                    // workspaces, which is what we want outside the overview too.
+                    let grab = TouchOverviewGrab::new(
+                        start_data,
+                        start_timestamp,
+                        output,
+                        pos_within_output,
+                        ws_id,
+                        false,
+                        None,
+                    );
+                    handle.set_grab(self, grab, serial);
+                }
+
                 // FIXME: granular.
                 self.niri.queue_redraw_all();
             }
@@ -4189,6 +5857,20 @@ impl State {
         };
         let slot = evt.slot();
 
+        let timestamp = Duration::from_micros(evt.time());
+        if self.niri.touchscreen_gesture.is_active() {
+            if let Some(trigger) = self.niri.touchscreen_gesture.up(slot, timestamp) {
+                self.trigger_touchscreen_gesture(trigger);
+            }
+            return;
+        }
+        self.niri.touchscreen_gesture.up(slot, timestamp);
+
+        // The client already got a cancel when the swipe took the touch.
+        if self.niri.touch_edge_swipe.up(slot) {
+            return;
+        }
+
         if let Some(capture) = self.niri.screenshot_ui.pointer_up(Some(slot)) {
             if capture {
                 self.confirm_screenshot(true);
@@ -4216,6 +5898,25 @@ impl State {
         };
         let slot = evt.slot();
 
+        let timestamp = Duration::from_micros(evt.time());
+        if self.niri.touchscreen_gesture.is_active() {
+            if let Some(trigger) = self.niri.touchscreen_gesture.motion(slot, pos, timestamp) {
+                self.trigger_touchscreen_gesture(trigger);
+            }
+            return;
+        }
+        self.niri.touchscreen_gesture.motion(slot, pos, timestamp);
+
+        if self.niri.touch_edge_swipe.is_claimed(slot) {
+            return;
+        }
+        if let Some(edge) = self.niri.touch_edge_swipe.motion(slot, pos) {
+            // Take the touch away from the client for the rest of the swipe.
+            handle.cancel(self);
+            self.trigger_touch_edge_swipe(edge);
+            return;
+        }
+
         if let Some(output) = self.niri.screenshot_ui.selection_output().cloned() {
             let geom = self.niri.global_space.output_geometry(&output).unwrap();
             let mut point = (pos - geom.loc.to_f64())
@@ -4258,12 +5959,21 @@ impl State {
         let Some(handle) = self.niri.seat.get_touch() else {
             return;
         };
+        if self.niri.touchscreen_gesture.is_active() {
+            return;
+        }
         handle.frame(self);
     }
     fn on_touch_cancel<I: InputBackend>(&mut self, _evt: I::TouchCancelEvent) {
         let Some(handle) = self.niri.seat.get_touch() else {
             return;
         };
+        if self.niri.touchscreen_gesture.is_active() {
+            self.niri.touchscreen_gesture.cancel();
+            return;
+        }
+        self.niri.touchscreen_gesture.cancel();
+        self.niri.touch_edge_swipe.cancel();
         handle.cancel(self);
     }
 
@@ -4276,6 +5986,13 @@ impl State {
             let is_closed = evt.state() == SwitchState::On;
             trace!("lid switch {}", if is_closed { "closed" } else { "opened" });
             self.set_lid_closed(is_closed);
+        } else if switch == Switch::TabletMode {
+            let is_tablet_mode = evt.state() == SwitchState::On;
+            trace!(
+                "tablet mode switch {}",
+                if is_tablet_mode { "on" } else { "off" }
+            );
+            self.set_tablet_mode(is_tablet_mode);
         }
 
         let action = {
@@ -4351,6 +6068,7 @@ fn should_intercept_key<'a>(
                     action,
                     repeat: true,
                     cooldown: None,
+                    double_press: None,
                     allow_when_locked: false,
                     // The screenshot UI owns the focus anyway, so this doesn't really matter.
                     // But logically, nothing can inhibit its actions. Only opening it can be
@@ -4415,6 +6133,7 @@ fn find_bind<'a>(
             action,
             repeat: true,
             cooldown: None,
+            double_press: None,
             allow_when_locked: false,
             // In a worst-case scenario, the user has no way to unlock the compositor and a
             // misbehaving client has a keyboard shortcuts inhibitor, "jailing" the user.
@@ -4435,6 +6154,30 @@ fn find_configured_bind<'a>(
     mod_key: ModKey,
     trigger: Trigger,
     mods: ModifiersState,
+) -> Option<Bind> {
+    let bindings = bindings
+        .into_iter()
+        .filter(|bind| bind.double_press.is_none());
+    find_bind_with_key(bindings, mod_key, trigger, mods)
+}
+
+fn find_double_press_bind<'a>(
+    bindings: impl IntoIterator<Item = &'a Bind>,
+    mod_key: ModKey,
+    trigger: Trigger,
+    mods: ModifiersState,
+) -> Option<Bind> {
+    let bindings = bindings
+        .into_iter()
+        .filter(|bind| bind.double_press.is_some());
+    find_bind_with_key(bindings, mod_key, trigger, mods)
+}
+
+fn find_bind_with_key<'a>(
+    bindings: impl IntoIterator<Item = &'a Bind>,
+    mod_key: ModKey,
+    trigger: Trigger,
+    mods: ModifiersState,
 ) -> Option<Bind> {
     // Handle configured binds.
     let mut modifiers = modifiers_from_state(mods);
@@ -4481,6 +6224,18 @@ fn find_configured_switch_action(
         .map(|switch_action| Action::Spawn(switch_action.spawn.clone()))
 }
 
+/// Returns the mouse button for an evdev button code, like the input backends do.
+fn mouse_button_from_code(code: u32) -> Option<MouseButton> {
+    match code {
+        0x110 => Some(MouseButton::Left),
+        0x111 => Some(MouseButton::Right),
+        0x112 => Some(MouseButton::Middle),
+        0x113 => Some(MouseButton::Back),
+        0x114 => Some(MouseButton::Forward),
+        _ => None,
+    }
+}
+
 fn modifiers_from_state(mods: ModifiersState) -> Modifiers {
     let mut modifiers = Modifiers::empty();
     if mods.ctrl {
@@ -4540,6 +6295,26 @@ fn should_hide_hotkey_overlay<I: InputBackend>(event: &InputEvent<I>) -> bool {
     }
 }
 
+/// Returns whether the event can change the state shown in the gesture debug overlay.
+fn is_gesture_event<I: InputBackend>(event: &InputEvent<I>) -> bool {
+    matches!(
+        event,
+        InputEvent::Keyboard { .. }
+            | InputEvent::GestureSwipeBegin { .. }
+            | InputEvent::GestureSwipeUpdate { .. }
+            | InputEvent::GestureSwipeEnd { .. }
+            | InputEvent::GesturePinchBegin { .. }
+            | InputEvent::GesturePinchUpdate { .. }
+            | InputEvent::GesturePinchEnd { .. }
+            | InputEvent::GestureHoldBegin { .. }
+            | InputEvent::GestureHoldEnd { .. }
+            | InputEvent::TouchDown { .. }
+            | InputEvent::TouchMotion { .. }
+            | InputEvent::TouchUp { .. }
+            | InputEvent::TouchCancel { .. }
+    )
+}
+
 fn should_hide_exit_confirm_dialog<I: InputBackend>(event: &InputEvent<I>) -> bool {
     match event {
         InputEvent::Keyboard { event } if event.state() == KeyState::Pressed => true,
@@ -4576,6 +6351,104 @@ fn should_reset_pointer_inactivity_timer<I: InputBackend>(event: &InputEvent<I>)
     )
 }
 
+fn force_close_target(mapped: &Mapped) -> Option<ForceCloseTarget> {
+    let pid = mapped.credentials()?.pid;
+
+    // Never kill niri itself or init.
+    if pid <= 1 || u32::try_from(pid) == Ok(std::process::id()) {
+        return None;
+    }
+
+    let title = with_toplevel_role(mapped.toplevel(), |role| role.title.clone());
+    let process_name = std::fs::read_to_string(format!("/proc/{pid}/comm"))
+        .ok()
+        .map(|name| name.trim_end().to_owned());
+
+    Some(ForceCloseTarget {
+        window: mapped.id(),
+        pid,
+        title,
+        process_name,
+    })
+}
+
+/// Returns whether the contents are the desktop background, with no window or panel on top.
+fn is_background(contents: &PointContents) -> bool {
+    if contents.output.is_none() || contents.window.is_some() {
+        return false;
+    }
+
+    // The wallpaper counts as the background too.
+    match &contents.layer {
+        Some(layer) => layer.layer() == Layer::Background,
+        None => contents.surface.is_none(),
+    }
+}
+
+/// Returns whether the action operates on the focused window.
+fn acts_on_focused_window(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::CloseWindow
+            | Action::ForceCloseWindow(false)
+            | Action::MinimizeWindow
+            | Action::EnterResizeMode
+            | Action::FullscreenWindow
+            | Action::ToggleWindowedFullscreen
+            | Action::MoveWindowDown
+            | Action::MoveWindowUp
+            | Action::MoveWindowDownOrToWorkspaceDown
+            | Action::MoveWindowUpOrToWorkspaceUp
+            | Action::ConsumeOrExpelWindowLeft
+            | Action::ConsumeOrExpelWindowRight
+            | Action::ExpelWindowFromColumn
+            | Action::SwapWindowLeft
+            | Action::SwapWindowRight
+            | Action::CenterWindow
+            | Action::MoveWindowToWorkspaceDown(_)
+            | Action::MoveWindowToWorkspaceUp(_)
+            | Action::MoveWindowToWorkspace(..)
+            | Action::MoveWindowToMonitorLeft
+            | Action::MoveWindowToMonitorRight
+            | Action::MoveWindowToMonitorDown
+            | Action::MoveWindowToMonitorUp
+            | Action::MoveWindowToMonitorPrevious
+            | Action::MoveWindowToMonitorNext
+            | Action::MoveWindowToMonitor(_)
+            | Action::SetWindowWidth(_)
+            | Action::SetWindowHeight(_)
+            | Action::ResetWindowHeight
+            | Action::SwitchPresetWindowWidth
+            | Action::SwitchPresetWindowWidthBack
+            | Action::SwitchPresetWindowHeight
+            | Action::SwitchPresetWindowHeightBack
+            | Action::MaximizeWindowToEdges
+            | Action::ToggleWindowFloating
+            | Action::MoveWindowToFloating
+            | Action::MoveWindowToTiling
+            | Action::ToggleWindowPinned
+            | Action::ToggleWindowAlwaysOnTop
+            | Action::ToggleWindowRuleOpacity
+            | Action::ToggleWindowTag(_)
+            | Action::SetMark(_)
+    )
+}
+
+/// Turns the directional focus actions into their wrapping or cross-monitor versions.
+fn apply_focus_at_edge(action: Action, focus_at_edge: FocusAtEdge) -> Action {
+    match (focus_at_edge, action) {
+        (FocusAtEdge::Wrap, Action::FocusColumnLeft) => Action::FocusColumnLeftOrLast,
+        (FocusAtEdge::Wrap, Action::FocusColumnRight) => Action::FocusColumnRightOrFirst,
+        (FocusAtEdge::Wrap, Action::FocusWindowUp) => Action::FocusWindowUpOrBottom,
+        (FocusAtEdge::Wrap, Action::FocusWindowDown) => Action::FocusWindowDownOrTop,
+        (FocusAtEdge::Monitor, Action::FocusColumnLeft) => Action::FocusColumnOrMonitorLeft,
+        (FocusAtEdge::Monitor, Action::FocusColumnRight) => Action::FocusColumnOrMonitorRight,
+        (FocusAtEdge::Monitor, Action::FocusWindowUp) => Action::FocusWindowOrMonitorUp,
+        (FocusAtEdge::Monitor, Action::FocusWindowDown) => Action::FocusWindowOrMonitorDown,
+        (_, action) => action,
+    }
+}
+
 fn allowed_when_locked(action: &Action) -> bool {
     matches!(
         action,
@@ -4655,6 +6528,7 @@ fn hardcoded_overview_bind(raw: Keysym, mods: ModifiersState) -> Option<Bind> {
         action,
         repeat,
         cooldown: None,
+        double_press: None,
         allow_when_locked: false,
         allow_inhibiting: false,
         hotkey_overlay_title: None,
@@ -4945,6 +6819,29 @@ pub fn apply_libinput_settings(config: &niri_config::Input, device: &mut input::
                 .unwrap_or(IDENTITY_MATRIX),
         );
     }
+
+    // Settings for this specific device go on top; later sections win.
+    let name = device.name().to_owned();
+    for c in config.devices.iter().filter(|c| c.name == name) {
+        if let Some(tap) = c.tap {
+            let _ = device.config_tap_set_enabled(tap);
+        }
+        if let Some(natural_scroll) = c.natural_scroll {
+            let _ = device.config_scroll_set_natural_scroll_enabled(natural_scroll);
+        }
+        if let Some(accel_speed) = c.accel_speed {
+            let _ = device.config_accel_set_speed(accel_speed.0);
+        }
+        if let Some(accel_profile) = c.accel_profile {
+            let _ = device.config_accel_set_profile(accel_profile.into());
+        }
+        if let Some(dwt) = c.dwt {
+            let _ = device.config_dwt_set_enabled(dwt);
+        }
+        if let Some(method) = c.click_method {
+            let _ = device.config_click_set_method(method.into());
+        }
+    }
 }
 
 pub fn mods_with_binds(mod_key: ModKey, binds: &Binds, triggers: &[Trigger]) -> HashSet<Modifiers> {
@@ -5037,10 +6934,15 @@ fn grab_allows_hot_corner(grab: &(dyn PointerGrab<State> + 'static)) -> bool {
 fn make_binds_iter<'a>(
     config: &'a Config,
     mru: &'a mut WindowMruUi,
+    bind_mode: Option<&str>,
     mods: Modifiers,
 ) -> impl Iterator<Item = &'a Bind> + Clone {
+    // The active bind mode replaces the general binds.
+    let mode = bind_mode.and_then(|name| config.bind_modes.find(name));
+    let general_binds = mode.map_or(&config.binds.0, |mode| &mode.binds);
+
     // Figure out the binds to use depending on whether the MRU is enabled and/or open.
-    let general_binds = (!mru.is_open()).then_some(config.binds.0.iter());
+    let general_binds = (!mru.is_open()).then_some(general_binds.iter());
     let general_binds = general_binds.into_iter().flatten();
 
     let mru_binds =
@@ -5072,6 +6974,7 @@ mod tests {
             action: Action::CloseWindow,
             repeat: true,
             cooldown: None,
+            double_press: None,
             allow_when_locked: false,
             allow_inhibiting: true,
             hotkey_overlay_title: None,
@@ -5258,6 +7161,7 @@ mod tests {
                 action: Action::CloseWindow,
                 repeat: true,
                 cooldown: None,
+                double_press: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5270,6 +7174,7 @@ mod tests {
                 action: Action::FocusColumnLeft,
                 repeat: true,
                 cooldown: None,
+                double_press: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5282,6 +7187,7 @@ mod tests {
                 action: Action::FocusWindowDown,
                 repeat: true,
                 cooldown: None,
+                double_press: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5294,6 +7200,7 @@ mod tests {
                 action: Action::FocusWindowUp,
                 repeat: true,
                 cooldown: None,
+                double_press: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5306,6 +7213,7 @@ mod tests {
                 action: Action::FocusColumnRight,
                 repeat: true,
                 cooldown: None,
+                double_press: None,
                 allow_when_locked: false,
                 allow_inhibiting: true,
                 hotkey_overlay_title: None,
@@ -5431,4 +7339,27 @@ mod tests {
             None,
         );
     }
+
+    #[test]
+    fn focus_at_edge_actions() {
+        let apply = |action| apply_focus_at_edge(action, FocusAtEdge::Stop);
+        assert_eq!(apply(Action::FocusColumnLeft), Action::FocusColumnLeft);
+
+        let apply = |action| apply_focus_at_edge(action, FocusAtEdge::Wrap);
+        assert_eq!(
+            apply(Action::FocusColumnLeft),
+            Action::FocusColumnLeftOrLast
+        );
+        assert_eq!(apply(Action::FocusWindowDown), Action::FocusWindowDownOrTop);
+
+        let apply = |action| apply_focus_at_edge(action, FocusAtEdge::Monitor);
+        assert_eq!(
+            apply(Action::FocusColumnRight),
+            Action::FocusColumnOrMonitorRight
+        );
+        assert_eq!(apply(Action::FocusWindowUp), Action::FocusWindowOrMonitorUp);
+
+        // Other actions stay the same.
+        assert_eq!(apply(Action::FocusColumnFirst), Action::FocusColumnFirst);
+    }
 }
@@ -0,0 +1,162 @@
+//! Tracking of bare modifier taps for the modifier tap binds.
+//!
+//! A tap is pressing and releasing one or more modifiers with nothing else in between: no other
+//! key, mouse button or scroll. Taps held down for too long don't count either, so that holding a
+//! modifier and then changing your mind doesn't trigger anything.
+
+use std::time::Duration;
+
+use smithay::input::keyboard::{Keysym, ModifiersState};
+
+use super::modifiers_from_state;
+
+/// Longest time from the first modifier press to the last release that still counts as a tap.
+const MAX_TAP_DURATION: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Default)]
+pub struct ModifierTapTracker {
+    state: TapState,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum TapState {
+    /// No modifiers are held down.
+    #[default]
+    Idle,
+    /// Only modifiers were pressed since the first one went down.
+    Tapping {
+        /// Modifiers as of the last modifier press.
+        mods: ModifiersState,
+        /// Time of the first modifier press, in milliseconds.
+        start: u32,
+    },
+    /// Something else happened while the modifiers were held down.
+    Interrupted,
+}
+
+impl ModifierTapTracker {
+    /// Feeds a key press or release, with the modifiers as they are after it.
+    ///
+    /// Returns the tapped modifiers when this release completes a tap.
+    pub fn key(
+        &mut self,
+        is_modifier: bool,
+        pressed: bool,
+        mods: ModifiersState,
+        time: u32,
+    ) -> Option<ModifiersState> {
+        if pressed {
+            self.state = match self.state {
+                TapState::Idle if is_modifier => TapState::Tapping { mods, start: time },
+                TapState::Tapping { start, .. } if is_modifier => TapState::Tapping { mods, start },
+                TapState::Tapping { .. } => TapState::Interrupted,
+                state => state,
+            };
+            return None;
+        }
+
+        if !modifiers_from_state(mods).is_empty() {
+            return None;
+        }
+
+        // All modifiers are released.
+        let state = std::mem::take(&mut self.state);
+        match state {
+            TapState::Tapping { mods, start } => {
+                let duration = Duration::from_millis(u64::from(time.wrapping_sub(start)));
+                (duration <= MAX_TAP_DURATION).then_some(mods)
+            }
+            _ => None,
+        }
+    }
+
+    /// Cancels the tap in progress because of a non-keyboard event, like a mouse button press.
+    pub fn interrupt(&mut self) {
+        if matches!(self.state, TapState::Tapping { .. }) {
+            self.state = TapState::Interrupted;
+        }
+    }
+}
+
+/// Returns whether the keysym is a modifier key, not counting the lock keys.
+pub fn is_modifier_keysym(keysym: Keysym) -> bool {
+    matches!(
+        keysym,
+        Keysym::Shift_L
+            | Keysym::Shift_R
+            | Keysym::Control_L
+            | Keysym::Control_R
+            | Keysym::Alt_L
+            | Keysym::Alt_R
+            | Keysym::Meta_L
+            | Keysym::Meta_R
+            | Keysym::Super_L
+            | Keysym::Super_R
+            | Keysym::Hyper_L
+            | Keysym::Hyper_R
+            | Keysym::ISO_Level3_Shift
+            | Keysym::ISO_Level5_Shift
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logo() -> ModifiersState {
+        ModifiersState {
+            logo: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tap_triggers() {
+        let mut tracker = ModifierTapTracker::default();
+        assert_eq!(tracker.key(true, true, logo(), 0), None);
+        assert_eq!(
+            tracker.key(true, false, ModifiersState::default(), 100),
+            Some(logo())
+        );
+    }
+
+    #[test]
+    fn chord_doesnt_trigger() {
+        let mut tracker = ModifierTapTracker::default();
+        tracker.key(true, true, logo(), 0);
+        tracker.key(false, true, logo(), 10);
+        tracker.key(false, false, logo(), 20);
+        assert_eq!(
+            tracker.key(true, false, ModifiersState::default(), 30),
+            None
+        );
+    }
+
+    #[test]
+    fn long_press_doesnt_trigger() {
+        let mut tracker = ModifierTapTracker::default();
+        tracker.key(true, true, logo(), 0);
+        assert_eq!(
+            tracker.key(true, false, ModifiersState::default(), 1000),
+            None
+        );
+    }
+
+    #[test]
+    fn interrupt_doesnt_trigger() {
+        let mut tracker = ModifierTapTracker::default();
+        tracker.key(true, true, logo(), 0);
+        tracker.interrupt();
+        assert_eq!(
+            tracker.key(true, false, ModifiersState::default(), 100),
+            None
+        );
+
+        // The next tap works again.
+        tracker.key(true, true, logo(), 200);
+        assert_eq!(
+            tracker.key(true, false, ModifiersState::default(), 300),
+            Some(logo())
+        );
+    }
+}
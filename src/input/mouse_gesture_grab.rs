@@ -0,0 +1,251 @@
+use niri_config::GestureDirection;
+use smithay::backend::input::ButtonState;
+use smithay::input::pointer::{
+    AxisFrame, ButtonEvent, GestureHoldBeginEvent, GestureHoldEndEvent, GesturePinchBeginEvent,
+    GesturePinchEndEvent, GesturePinchUpdateEvent, GestureSwipeBeginEvent, GestureSwipeEndEvent,
+    GestureSwipeUpdateEvent, GrabStartData as PointerGrabStartData, MotionEvent, PointerGrab,
+    PointerInnerHandle, RelativeMotionEvent,
+};
+use smithay::input::SeatHandler;
+use smithay::utils::{Logical, Point};
+
+use crate::niri::State;
+
+/// Grab that traces a mouse gesture stroke while its button is held.
+///
+/// The button press doesn't reach the client. If the pointer doesn't move far enough to draw a
+/// stroke, the click is replayed on release, so the button keeps working as usual.
+pub struct MouseGestureGrab {
+    start_data: PointerGrabStartData<State>,
+    stroke: StrokeTracker,
+}
+
+/// Splits the pointer movement into straight segments.
+#[derive(Debug)]
+struct StrokeTracker {
+    /// How far the pointer must move to start a new segment.
+    distance: f64,
+    /// Start of the segment in progress.
+    origin: Point<f64, Logical>,
+    segments: Vec<GestureDirection>,
+}
+
+impl MouseGestureGrab {
+    pub fn new(start_data: PointerGrabStartData<State>, distance: f64) -> Self {
+        let stroke = StrokeTracker::new(start_data.location, distance);
+        Self { start_data, stroke }
+    }
+}
+
+impl StrokeTracker {
+    fn new(origin: Point<f64, Logical>, distance: f64) -> Self {
+        Self {
+            distance,
+            origin,
+            segments: Vec::new(),
+        }
+    }
+
+    fn motion(&mut self, location: Point<f64, Logical>) {
+        let delta = location - self.origin;
+        if delta.x * delta.x + delta.y * delta.y < self.distance * self.distance {
+            return;
+        }
+
+        self.origin = location;
+
+        let direction = if delta.x.abs() > delta.y.abs() {
+            if delta.x > 0. {
+                GestureDirection::Right
+            } else {
+                GestureDirection::Left
+            }
+        } else if delta.y > 0. {
+            GestureDirection::Down
+        } else {
+            GestureDirection::Up
+        };
+
+        // Keep going in the same direction within one segment.
+        if self.segments.last() != Some(&direction) {
+            self.segments.push(direction);
+        }
+    }
+}
+
+impl PointerGrab<State> for MouseGestureGrab {
+    fn motion(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        focus: Option<(<State as SeatHandler>::PointerFocus, Point<f64, Logical>)>,
+        event: &MotionEvent,
+    ) {
+        self.stroke.motion(event.location);
+        handle.motion(data, focus, event);
+    }
+
+    fn relative_motion(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        focus: Option<(<State as SeatHandler>::PointerFocus, Point<f64, Logical>)>,
+        event: &RelativeMotionEvent,
+    ) {
+        handle.relative_motion(data, focus, event);
+    }
+
+    fn button(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        event: &ButtonEvent,
+    ) {
+        if event.button != self.start_data.button {
+            handle.button(data, event);
+            return;
+        }
+
+        // Hold back the press that started the stroke.
+        if event.state == ButtonState::Pressed {
+            return;
+        }
+
+        if self.stroke.segments.is_empty() {
+            // No stroke, so this was a regular click.
+            let press = ButtonEvent {
+                state: ButtonState::Pressed,
+                ..*event
+            };
+            handle.button(data, &press);
+            handle.button(data, event);
+        } else {
+            let config = data.niri.config.borrow();
+            let stroke = config.gestures.mouse.find(&self.stroke.segments);
+            let action = stroke.map(|stroke| stroke.action.clone());
+            drop(config);
+
+            if let Some(action) = action {
+                // Run the action outside of the grab, since it may use the pointer.
+                data.niri
+                    .event_loop
+                    .insert_idle(move |state| state.do_gesture_action(action));
+            }
+        }
+
+        handle.unset_grab(self, data, event.serial, event.time, true);
+    }
+
+    fn axis(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        details: AxisFrame,
+    ) {
+        handle.axis(data, details);
+    }
+
+    fn frame(&mut self, data: &mut State, handle: &mut PointerInnerHandle<'_, State>) {
+        handle.frame(data);
+    }
+
+    fn gesture_swipe_begin(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        event: &GestureSwipeBeginEvent,
+    ) {
+        handle.gesture_swipe_begin(data, event);
+    }
+
+    fn gesture_swipe_update(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        event: &GestureSwipeUpdateEvent,
+    ) {
+        handle.gesture_swipe_update(data, event);
+    }
+
+    fn gesture_swipe_end(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        event: &GestureSwipeEndEvent,
+    ) {
+        handle.gesture_swipe_end(data, event);
+    }
+
+    fn gesture_pinch_begin(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        event: &GesturePinchBeginEvent,
+    ) {
+        handle.gesture_pinch_begin(data, event);
+    }
+
+    fn gesture_pinch_update(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        event: &GesturePinchUpdateEvent,
+    ) {
+        handle.gesture_pinch_update(data, event);
+    }
+
+    fn gesture_pinch_end(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        event: &GesturePinchEndEvent,
+    ) {
+        handle.gesture_pinch_end(data, event);
+    }
+
+    fn gesture_hold_begin(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        event: &GestureHoldBeginEvent,
+    ) {
+        handle.gesture_hold_begin(data, event);
+    }
+
+    fn gesture_hold_end(
+        &mut self,
+        data: &mut State,
+        handle: &mut PointerInnerHandle<'_, State>,
+        event: &GestureHoldEndEvent,
+    ) {
+        handle.gesture_hold_end(data, event);
+    }
+
+    fn start_data(&self) -> &PointerGrabStartData<State> {
+        &self.start_data
+    }
+
+    fn unset(&mut self, _data: &mut State) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stroke_segments() {
+        let mut stroke = StrokeTracker::new(Point::from((0., 0.)), 50.);
+        stroke.motion(Point::from((0., 30.)));
+        assert!(stroke.segments.is_empty());
+
+        stroke.motion(Point::from((5., 60.)));
+        stroke.motion(Point::from((0., 120.)));
+        assert_eq!(stroke.segments, [GestureDirection::Down]);
+
+        stroke.motion(Point::from((80., 125.)));
+        assert_eq!(
+            stroke.segments,
+            [GestureDirection::Down, GestureDirection::Right]
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Keyboard resize mode.
+//!
+//! While the mode is active, the arrow keys and hjkl resize the window in the configured steps.
+//! Enter keeps the new size, and Escape puts back the size that the window had when the mode
+//! started.
+
+use niri_config::{Action, Bind, Key, Modifiers, Trigger};
+use niri_ipc::SizeChange;
+use smithay::input::keyboard::{Keysym, ModifiersState};
+use smithay::utils::{Logical, Size};
+
+use super::modifiers_from_state;
+use crate::layout::LayoutElement as _;
+use crate::niri::{Niri, State};
+use crate::window::mapped::MappedId;
+use crate::window::Mapped;
+
+#[derive(Debug)]
+pub struct ResizeMode {
+    /// Window being resized.
+    pub window: MappedId,
+    /// Size of the window when the mode started.
+    original_size: Size<i32, Logical>,
+}
+
+impl State {
+    /// Starts resizing the focused window with the keyboard.
+    pub fn enter_resize_mode(&mut self) {
+        let Some(mapped) = self.niri.layout.focus() else {
+            return;
+        };
+
+        self.niri.resize_mode = Some(ResizeMode {
+            window: mapped.id(),
+            original_size: expected_size(mapped),
+        });
+        self.niri.queue_redraw_all();
+    }
+
+    /// Leaves the resize mode, keeping the new window size.
+    pub fn confirm_resize_mode(&mut self) {
+        if self.niri.resize_mode.take().is_some() {
+            self.niri.queue_redraw_all();
+        }
+    }
+
+    /// Leaves the resize mode, putting back the original window size.
+    pub fn cancel_resize_mode(&mut self) {
+        let Some(mode) = self.niri.resize_mode.take() else {
+            return;
+        };
+        self.niri.queue_redraw_all();
+
+        let Some(mapped) = self.niri.resize_mode_window(&mode) else {
+            return;
+        };
+        let window = mapped.window.clone();
+        let size = expected_size(mapped);
+        let original = mode.original_size;
+
+        // Only touch the sizes that changed, to keep automatic heights where possible.
+        if size.w != original.w {
+            let change = SizeChange::SetFixed(original.w);
+            self.niri.layout.set_window_width(Some(&window), change);
+        }
+        if size.h != original.h {
+            let change = SizeChange::SetFixed(original.h);
+            self.niri.layout.set_window_height(Some(&window), change);
+        }
+    }
+
+    /// Returns the bind for a key pressed while the resize mode is active.
+    ///
+    /// Leaves the mode if its window has gone away.
+    pub(super) fn resize_mode_bind(&mut self, raw: Keysym, mods: ModifiersState) -> Option<Bind> {
+        let mode = self.niri.resize_mode.as_ref()?;
+        let id = mode.window.get();
+        if self.niri.resize_mode_window(mode).is_none() {
+            self.niri.resize_mode = None;
+            self.niri.queue_redraw_all();
+            return None;
+        }
+
+        if !modifiers_from_state(mods).is_empty() {
+            return None;
+        }
+
+        let config = self.niri.config.borrow();
+        let steps = &config.resize_mode;
+
+        let mut repeat = true;
+        let action = match raw {
+            Keysym::Left | Keysym::h => Action::SetWindowWidthById {
+                id,
+                change: step(steps.width_step, false),
+            },
+            Keysym::Right | Keysym::l => Action::SetWindowWidthById {
+                id,
+                change: step(steps.width_step, true),
+            },
+            Keysym::Up | Keysym::k => Action::SetWindowHeightById {
+                id,
+                change: step(steps.height_step, false),
+            },
+            Keysym::Down | Keysym::j => Action::SetWindowHeightById {
+                id,
+                change: step(steps.height_step, true),
+            },
+            Keysym::Return | Keysym::KP_Enter => {
+                repeat = false;
+                Action::ConfirmResizeMode
+            }
+            Keysym::Escape => {
+                repeat = false;
+                Action::CancelResizeMode
+            }
+            _ => return None,
+        };
+
+        Some(Bind {
+            key: Key {
+                trigger: Trigger::Keysym(raw),
+                modifiers: Modifiers::empty(),
+            },
+            action,
+            repeat,
+            cooldown: None,
+            double_press: None,
+            allow_when_locked: false,
+            allow_inhibiting: false,
+            hotkey_overlay_title: None,
+        })
+    }
+}
+
+impl Niri {
+    /// Returns the window of the resize mode, if it is still in the layout.
+    pub fn resize_mode_window(&self, mode: &ResizeMode) -> Option<&Mapped> {
+        let window = self.layout.windows().find(|(_, m)| m.id() == mode.window);
+        window.map(|(_, mapped)| mapped)
+    }
+}
+
+/// Returns the text of the resize mode hint, with the current window size and the steps.
+pub fn hint_text(mapped: &Mapped, steps: &niri_config::ResizeMode) -> String {
+    let size = expected_size(mapped);
+    let width_step = format_step(steps.width_step);
+    let height_step = format_step(steps.height_step);
+
+    format!(
+        "{} × {}\n\
+         ←/→ width {width_step}, ↑/↓ height {height_step}\n\
+         Enter to apply, Escape to revert",
+        size.w, size.h,
+    )
+}
+
+fn expected_size(mapped: &Mapped) -> Size<i32, Logical> {
+    mapped.expected_size().unwrap_or_else(|| mapped.size())
+}
+
+/// Turns a configured step into a size change in the given direction.
+fn step(step: SizeChange, grow: bool) -> SizeChange {
+    let sign = if grow { 1 } else { -1 };
+    match step {
+        SizeChange::SetFixed(px) | SizeChange::AdjustFixed(px) => {
+            SizeChange::AdjustFixed(px.abs() * sign)
+        }
+        SizeChange::SetProportion(prop) | SizeChange::AdjustProportion(prop) => {
+            SizeChange::AdjustProportion(prop.abs() * f64::from(sign))
+        }
+    }
+}
+
+fn format_step(step: SizeChange) -> String {
+    match step {
+        SizeChange::SetFixed(px) | SizeChange::AdjustFixed(px) => format!("±{} px", px.abs()),
+        SizeChange::SetProportion(prop) | SizeChange::AdjustProportion(prop) => {
+            format!("±{}%", prop.abs())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_uses_magnitude() {
+        assert_eq!(
+            step(SizeChange::SetProportion(10.), false),
+            SizeChange::AdjustProportion(-10.)
+        );
+        assert_eq!(
+            step(SizeChange::AdjustProportion(-5.), true),
+            SizeChange::AdjustProportion(5.)
+        );
+        assert_eq!(
+            step(SizeChange::SetFixed(50), true),
+            SizeChange::AdjustFixed(50)
+        );
+        assert_eq!(
+            step(SizeChange::AdjustFixed(20), false),
+            SizeChange::AdjustFixed(-20)
+        );
+    }
+}
@@ -0,0 +1,178 @@
+//! Tablet pad buttons, rings and strips bound to the config pad actions.
+//!
+//! Rings and strips report absolute finger positions, so they trigger their action once for
+//! every step that the finger moves, in the direction it moves in.
+
+use std::collections::HashMap;
+
+use smithay::reexports::input::event::tablet_pad::{
+    ButtonState, TabletPadButtonEvent, TabletPadEvent, TabletPadRingEvent, TabletPadStripEvent,
+};
+
+use crate::niri::State;
+
+/// Ring rotation in degrees that triggers the ring action once.
+const RING_STEP: f64 = 15.;
+
+/// Strip movement, as a fraction of the strip length, that triggers the strip action once.
+const STRIP_STEP: f64 = 0.1;
+
+#[derive(Debug, Default)]
+pub struct TabletPadTracker {
+    rings: HashMap<u32, ScrubTracker>,
+    strips: HashMap<u32, ScrubTracker>,
+}
+
+/// Turns the absolute positions of a ring or a strip into steps.
+#[derive(Debug, Default)]
+struct ScrubTracker {
+    /// Last finger position, or `None` when the finger is lifted.
+    last: Option<f64>,
+    /// Movement since the last step.
+    accumulated: f64,
+}
+
+impl ScrubTracker {
+    /// Feeds a new position, or a negative one when the finger is lifted.
+    ///
+    /// Positions wrap around at `wrap`, if set. Returns the number of steps moved; negative steps
+    /// are towards the lower positions.
+    fn update(&mut self, position: f64, wrap: Option<f64>, step: f64) -> i32 {
+        if position < 0. {
+            *self = Self::default();
+            return 0;
+        }
+
+        let Some(last) = self.last.replace(position) else {
+            return 0;
+        };
+
+        let mut delta = position - last;
+        if let Some(wrap) = wrap {
+            // Take the shorter way around.
+            if delta > wrap / 2. {
+                delta -= wrap;
+            } else if delta < -wrap / 2. {
+                delta += wrap;
+            }
+        }
+
+        self.accumulated += delta;
+        let steps = (self.accumulated / step).trunc();
+        self.accumulated -= steps * step;
+        steps as i32
+    }
+}
+
+impl State {
+    pub(super) fn on_tablet_pad(&mut self, event: &TabletPadEvent) {
+        match event {
+            TabletPadEvent::Button(event) => self.on_tablet_pad_button(event),
+            TabletPadEvent::Ring(event) => self.on_tablet_pad_ring(event),
+            TabletPadEvent::Strip(event) => self.on_tablet_pad_strip(event),
+            _ => (),
+        }
+    }
+
+    fn on_tablet_pad_button(&mut self, event: &TabletPadButtonEvent) {
+        if event.button_state() != ButtonState::Pressed {
+            return;
+        }
+
+        let config = self.niri.config.borrow();
+        let button = event.button_number();
+        let action = config
+            .input
+            .tablet
+            .pad
+            .buttons
+            .iter()
+            .find(|bind| bind.button == button)
+            .map(|bind| bind.action.clone());
+        drop(config);
+
+        if let Some(action) = action {
+            self.do_action(action, false);
+        }
+    }
+
+    fn on_tablet_pad_ring(&mut self, event: &TabletPadRingEvent) {
+        let tracker = self
+            .niri
+            .tablet_pad
+            .rings
+            .entry(event.number())
+            .or_default();
+        let steps = tracker.update(event.position(), Some(360.), RING_STEP);
+
+        // Ring positions increase clockwise.
+        let config = self.niri.config.borrow();
+        let pad = &config.input.tablet.pad;
+        let action = if steps > 0 {
+            &pad.ring_cw
+        } else {
+            &pad.ring_ccw
+        };
+        let action = action.as_ref().map(|action| action.0.clone());
+        drop(config);
+
+        if let Some(action) = action {
+            for _ in 0..steps.unsigned_abs() {
+                self.do_action(action.clone(), false);
+            }
+        }
+    }
+
+    fn on_tablet_pad_strip(&mut self, event: &TabletPadStripEvent) {
+        let tracker = self
+            .niri
+            .tablet_pad
+            .strips
+            .entry(event.number())
+            .or_default();
+        let steps = tracker.update(event.position(), None, STRIP_STEP);
+
+        // Strip positions increase downwards.
+        let config = self.niri.config.borrow();
+        let pad = &config.input.tablet.pad;
+        let action = if steps > 0 {
+            &pad.strip_down
+        } else {
+            &pad.strip_up
+        };
+        let action = action.as_ref().map(|action| action.0.clone());
+        drop(config);
+
+        if let Some(action) = action {
+            for _ in 0..steps.unsigned_abs() {
+                self.do_action(action.clone(), false);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_steps_wrap_around() {
+        let mut ring = ScrubTracker::default();
+        assert_eq!(ring.update(350., Some(360.), RING_STEP), 0);
+        assert_eq!(ring.update(10., Some(360.), RING_STEP), 1);
+        assert_eq!(ring.update(20., Some(360.), RING_STEP), 1);
+        assert_eq!(ring.update(345., Some(360.), RING_STEP), -2);
+    }
+
+    #[test]
+    fn lifting_finger_resets() {
+        let mut strip = ScrubTracker::default();
+        assert_eq!(strip.update(0.2, None, STRIP_STEP), 0);
+        assert_eq!(strip.update(0.25, None, STRIP_STEP), 0);
+        assert_eq!(strip.update(-1., None, STRIP_STEP), 0);
+
+        // The new touch starts from scratch rather than jumping from the old position.
+        assert_eq!(strip.update(0.9, None, STRIP_STEP), 0);
+        assert_eq!(strip.update(0.7, None, STRIP_STEP), -2);
+    }
+}
@@ -0,0 +1,216 @@
+//! Single-finger swipes from the touchscreen edges.
+//!
+//! A touch that starts at a bound edge still goes to the client under it, so tapping or dragging
+//! along the edge keeps working. Only once the finger moves far enough towards the middle of the
+//! output does the swipe take the touch away from the client and trigger its action.
+
+use niri_config::gestures::ScreenEdge;
+use smithay::backend::input::TouchSlot;
+use smithay::utils::{Logical, Point, Size};
+
+use crate::niri::State;
+
+/// Distance from the output edge within which a touch counts as starting at the edge.
+const EDGE_MARGIN: f64 = 16.;
+
+/// Distance towards the middle of the output that a touch needs to move to become a swipe.
+const SWIPE_THRESHOLD: f64 = 50.;
+
+#[derive(Debug, Default)]
+pub struct TouchEdgeSwipe {
+    touch: Option<EdgeTouch>,
+}
+
+#[derive(Debug)]
+struct EdgeTouch {
+    slot: TouchSlot,
+    edge: ScreenEdge,
+    start: Point<f64, Logical>,
+    /// Whether the touch turned into a swipe and no longer goes to the client.
+    claimed: bool,
+}
+
+impl TouchEdgeSwipe {
+    /// Feeds a new touch, with the bound edge it starts at, if any.
+    pub fn down(&mut self, slot: TouchSlot, edge: Option<ScreenEdge>, pos: Point<f64, Logical>) {
+        if self.touch.as_ref().is_some_and(|touch| touch.claimed) {
+            return;
+        }
+
+        // A second finger means it's not an edge swipe.
+        if self.touch.take().is_some() {
+            return;
+        }
+
+        self.touch = edge.map(|edge| EdgeTouch {
+            slot,
+            edge,
+            start: pos,
+            claimed: false,
+        });
+    }
+
+    /// Feeds a touch motion.
+    ///
+    /// Returns the edge when this motion turns the touch into a swipe.
+    pub fn motion(&mut self, slot: TouchSlot, pos: Point<f64, Logical>) -> Option<ScreenEdge> {
+        let touch = self.touch.as_mut().filter(|touch| touch.slot == slot)?;
+        if touch.claimed {
+            return None;
+        }
+
+        let delta = pos - touch.start;
+        let inwards = match touch.edge {
+            ScreenEdge::Top => delta.y,
+            ScreenEdge::Bottom => -delta.y,
+            ScreenEdge::Left => delta.x,
+            ScreenEdge::Right => -delta.x,
+        };
+        if inwards < SWIPE_THRESHOLD {
+            return None;
+        }
+
+        touch.claimed = true;
+        Some(touch.edge)
+    }
+
+    /// Feeds a touch release; returns whether the touch belonged to a swipe.
+    pub fn up(&mut self, slot: TouchSlot) -> bool {
+        match self.touch.take() {
+            Some(touch) if touch.slot == slot => touch.claimed,
+            touch => {
+                self.touch = touch;
+                false
+            }
+        }
+    }
+
+    /// Returns whether this touch belongs to a swipe.
+    pub fn is_claimed(&self, slot: TouchSlot) -> bool {
+        self.touch
+            .as_ref()
+            .is_some_and(|touch| touch.slot == slot && touch.claimed)
+    }
+
+    pub fn cancel(&mut self) {
+        self.touch = None;
+    }
+}
+
+/// Returns the output edge that the position is at, if any.
+fn edge_at(size: Size<f64, Logical>, pos: Point<f64, Logical>) -> Option<ScreenEdge> {
+    if pos.y < EDGE_MARGIN {
+        Some(ScreenEdge::Top)
+    } else if pos.y >= size.h - EDGE_MARGIN {
+        Some(ScreenEdge::Bottom)
+    } else if pos.x < EDGE_MARGIN {
+        Some(ScreenEdge::Left)
+    } else if pos.x >= size.w - EDGE_MARGIN {
+        Some(ScreenEdge::Right)
+    } else {
+        None
+    }
+}
+
+impl State {
+    /// Returns the bound output edge that the touch position is at, if any.
+    pub(super) fn touch_edge_at(&self, pos: Point<f64, Logical>) -> Option<ScreenEdge> {
+        let (output, pos_within_output) = self.niri.output_under(pos)?;
+        let size = self
+            .niri
+            .global_space
+            .output_geometry(output)?
+            .size
+            .to_f64();
+        let edge = edge_at(size, pos_within_output)?;
+
+        let config = self.niri.config.borrow();
+        config
+            .gestures
+            .find_edge_swipe(edge, self.niri.is_tablet_mode)
+            .is_some()
+            .then_some(edge)
+    }
+
+    pub(super) fn trigger_touch_edge_swipe(&mut self, edge: ScreenEdge) {
+        let config = self.niri.config.borrow();
+        let action = config
+            .gestures
+            .find_edge_swipe(edge, self.niri.is_tablet_mode)
+            .map(|swipe| swipe.action.clone());
+        drop(config);
+
+        if let Some(action) = action {
+            self.do_gesture_action(action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(id: u32) -> TouchSlot {
+        TouchSlot::from(Some(id))
+    }
+
+    #[test]
+    fn edges() {
+        let size = Size::from((1920., 1080.));
+        assert_eq!(
+            edge_at(size, Point::from((500., 5.))),
+            Some(ScreenEdge::Top)
+        );
+        assert_eq!(
+            edge_at(size, Point::from((500., 1075.))),
+            Some(ScreenEdge::Bottom)
+        );
+        assert_eq!(
+            edge_at(size, Point::from((1., 500.))),
+            Some(ScreenEdge::Left)
+        );
+        assert_eq!(edge_at(size, Point::from((500., 500.))), None);
+    }
+
+    #[test]
+    fn swipe_inwards_claims() {
+        let mut swipe = TouchEdgeSwipe::default();
+        swipe.down(
+            slot(0),
+            Some(ScreenEdge::Bottom),
+            Point::from((500., 1075.)),
+        );
+        assert_eq!(swipe.motion(slot(0), Point::from((500., 1050.))), None);
+        assert!(!swipe.is_claimed(slot(0)));
+
+        assert_eq!(
+            swipe.motion(slot(0), Point::from((500., 1000.))),
+            Some(ScreenEdge::Bottom)
+        );
+        assert!(swipe.is_claimed(slot(0)));
+
+        // The swipe triggers only once.
+        assert_eq!(swipe.motion(slot(0), Point::from((500., 900.))), None);
+        assert!(swipe.up(slot(0)));
+    }
+
+    #[test]
+    fn swipe_along_edge_goes_to_client() {
+        let mut swipe = TouchEdgeSwipe::default();
+        swipe.down(
+            slot(0),
+            Some(ScreenEdge::Bottom),
+            Point::from((500., 1075.)),
+        );
+        assert_eq!(swipe.motion(slot(0), Point::from((800., 1075.))), None);
+        assert!(!swipe.up(slot(0)));
+    }
+
+    #[test]
+    fn second_finger_cancels() {
+        let mut swipe = TouchEdgeSwipe::default();
+        swipe.down(slot(0), Some(ScreenEdge::Top), Point::from((500., 5.)));
+        swipe.down(slot(1), None, Point::from((600., 500.)));
+        assert_eq!(swipe.motion(slot(0), Point::from((500., 200.))), None);
+    }
+}
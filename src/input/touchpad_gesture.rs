@@ -0,0 +1,831 @@
+//! Discrete touchpad gestures for the config gesture binds.
+//!
+//! Tracks a libinput swipe, pinch or hold gesture from begin to end and decides when, and in
+//! which direction, it should trigger a bind. Every gesture triggers at most once, except for
+//! swipes that change direction with the `swipe-hysteresis` threshold.
+
+use std::time::Duration;
+
+use niri_config::gestures::GestureThresholds;
+use niri_config::{GestureDirection, GestureKind};
+use smithay::backend::input::Axis;
+use smithay::desktop::Window;
+use smithay::utils::{Logical, Point, Size};
+
+use super::swipe_tracker::SwipeTracker;
+
+/// Swipe distance, in touchpad units, equivalent to doubling the scale of an overview pinch.
+///
+/// Matches the swipe distance that fully opens the overview.
+const OVERVIEW_PINCH_DISTANCE: f64 = 300.;
+
+#[derive(Debug)]
+pub enum TouchpadGesture {
+    Swipe(SwipeGesture),
+    Pinch(PinchGesture),
+    Hold(HoldGesture),
+    OverviewPinch(OverviewPinchGesture),
+    ResizePinch(ResizePinchGesture),
+    MaximizeSwipe(MaximizeSwipeGesture),
+    /// Pinch that continuously zooms the magnifier.
+    Magnifier {
+        fingers: u8,
+    },
+    /// Three-finger swipe sent to the window under the cursor as scrolling along an axis.
+    ClientScroll {
+        fingers: u8,
+        axis: Axis,
+    },
+    /// Gesture cancelled with the cancel key, consuming its remaining events.
+    Cancelled {
+        kind: GestureKind,
+        fingers: u8,
+    },
+}
+
+#[derive(Debug)]
+pub struct SwipeGesture {
+    fingers: u8,
+    thresholds: GestureThresholds,
+    /// Whether to recognize diagonal directions in addition to the four main ones.
+    diagonals: bool,
+    /// Directions with fling binds, which are decided at the end of the swipe.
+    fling_directions: Vec<GestureDirection>,
+    /// Recent movement, to tell a fling from a drag.
+    tracker_x: SwipeTracker,
+    tracker_y: SwipeTracker,
+    /// Direction waiting for the end of the swipe to tell whether it was flung.
+    pending: Option<GestureDirection>,
+    cumulative: (f64, f64),
+    triggered: bool,
+    /// Direction the swipe triggered in, or is waiting to fling in.
+    direction: Option<GestureDirection>,
+    /// Movement to the side of the direction since the swipe last kept going its way.
+    sideways: f64,
+}
+
+#[derive(Debug)]
+pub struct PinchGesture {
+    fingers: u8,
+    thresholds: GestureThresholds,
+    /// Current absolute scale.
+    scale: f64,
+    /// Cumulative rotation in degrees, clockwise.
+    rotation: f64,
+    triggered: bool,
+}
+
+/// Pinch that continuously resizes a floating window.
+#[derive(Debug)]
+pub struct ResizePinchGesture {
+    fingers: u8,
+    window: Window,
+    /// Window size at the start of the pinch.
+    original_size: Size<f64, Logical>,
+    scale: f64,
+}
+
+/// Vertical swipe that steps the focused window through maximized and fullscreen.
+#[derive(Debug)]
+pub struct MaximizeSwipeGesture {
+    fingers: u8,
+    /// Swipe distance of each step.
+    distance: f64,
+    /// Cumulative vertical movement, positive when the fingers move down.
+    cumulative: f64,
+    /// Last step taken.
+    step: Option<MaximizeSwipeStep>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaximizeSwipeStep {
+    /// Swiped up: maximize the window.
+    Maximize,
+    /// Swiped up twice as far: make the window fullscreen.
+    Fullscreen,
+    /// Swiped down: take the window out of fullscreen or maximized.
+    Restore,
+}
+
+/// Pinch that continuously drives the overview open and closed.
+#[derive(Debug)]
+pub struct OverviewPinchGesture {
+    fingers: u8,
+    scale: f64,
+}
+
+#[derive(Debug)]
+pub struct HoldGesture {
+    fingers: u8,
+    thresholds: GestureThresholds,
+    start_time: u32,
+    state: HoldState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HoldState {
+    /// The fingers are resting on the touchpad.
+    Holding,
+    /// The fingers started moving after the hold, and the hold turned into a window move.
+    Converted,
+}
+
+impl TouchpadGesture {
+    pub fn kind(&self) -> GestureKind {
+        match self {
+            TouchpadGesture::Swipe(_) => GestureKind::Swipe,
+            TouchpadGesture::Pinch(_) => GestureKind::Pinch,
+            TouchpadGesture::Hold(_) => GestureKind::Hold,
+            TouchpadGesture::OverviewPinch(_) => GestureKind::Pinch,
+            TouchpadGesture::ResizePinch(_) => GestureKind::Pinch,
+            TouchpadGesture::MaximizeSwipe(_) => GestureKind::Swipe,
+            TouchpadGesture::Magnifier { .. } => GestureKind::Pinch,
+            TouchpadGesture::ClientScroll { .. } => GestureKind::Swipe,
+            TouchpadGesture::Cancelled { kind, .. } => *kind,
+        }
+    }
+
+    pub fn fingers(&self) -> u8 {
+        match self {
+            TouchpadGesture::Swipe(x) => x.fingers,
+            TouchpadGesture::Pinch(x) => x.fingers,
+            TouchpadGesture::Hold(x) => x.fingers,
+            TouchpadGesture::OverviewPinch(x) => x.fingers,
+            TouchpadGesture::ResizePinch(x) => x.fingers,
+            TouchpadGesture::MaximizeSwipe(x) => x.fingers,
+            TouchpadGesture::Magnifier { fingers } => *fingers,
+            TouchpadGesture::ClientScroll { fingers, .. } => *fingers,
+            TouchpadGesture::Cancelled { fingers, .. } => *fingers,
+        }
+    }
+
+    /// Returns whether this gesture can still trigger a bind, and so can be cancelled.
+    ///
+    /// The overview, resize and magnifier pinches, the maximize swipe, the client scroll and a
+    /// hold turned into a window move follow the fingers continuously instead.
+    pub fn is_cancellable(&self) -> bool {
+        match self {
+            TouchpadGesture::Swipe(_) | TouchpadGesture::Pinch(_) => true,
+            TouchpadGesture::Hold(x) => !x.is_converted(),
+            TouchpadGesture::OverviewPinch(_)
+            | TouchpadGesture::ResizePinch(_)
+            | TouchpadGesture::MaximizeSwipe(_)
+            | TouchpadGesture::Magnifier { .. }
+            | TouchpadGesture::ClientScroll { .. }
+            | TouchpadGesture::Cancelled { .. } => false,
+        }
+    }
+
+    /// Returns the direction the gesture is heading in, and how close it is to triggering a bind.
+    pub fn progress(&self) -> Option<(GestureDirection, f64)> {
+        match self {
+            TouchpadGesture::Swipe(x) => x.progress(),
+            TouchpadGesture::Pinch(x) => x.progress(),
+            _ => None,
+        }
+    }
+
+    /// Describes the gesture state for the gesture debug overlay.
+    pub fn debug_text(&self) -> String {
+        match self {
+            TouchpadGesture::Swipe(x) => x.debug_text(),
+            TouchpadGesture::Pinch(x) => x.debug_text(),
+            TouchpadGesture::Hold(x) => format!("hold-{}: {:?}", x.fingers, x.state),
+            TouchpadGesture::OverviewPinch(x) => {
+                format!("overview pinch-{}: scale {:.3}", x.fingers, x.scale)
+            }
+            TouchpadGesture::ResizePinch(x) => {
+                format!("resize pinch-{}: scale {:.3}", x.fingers, x.scale)
+            }
+            TouchpadGesture::MaximizeSwipe(x) => format!(
+                "maximize swipe-{}: {:.1}, {:?}",
+                x.fingers, x.cumulative, x.step
+            ),
+            TouchpadGesture::Magnifier { fingers } => format!("magnifier pinch-{fingers}"),
+            TouchpadGesture::ClientScroll { fingers, axis } => {
+                format!("client scroll swipe-{fingers}: {axis:?}")
+            }
+            TouchpadGesture::Cancelled { kind, fingers } => {
+                format!("{kind:?}-{fingers}: cancelled")
+            }
+        }
+    }
+}
+
+impl SwipeGesture {
+    pub fn new(
+        fingers: u8,
+        thresholds: GestureThresholds,
+        diagonals: bool,
+        fling_directions: Vec<GestureDirection>,
+    ) -> Self {
+        Self {
+            fingers,
+            thresholds,
+            diagonals,
+            fling_directions,
+            tracker_x: SwipeTracker::new(),
+            tracker_y: SwipeTracker::new(),
+            pending: None,
+            cumulative: (0., 0.),
+            triggered: false,
+            direction: None,
+            sideways: 0.,
+        }
+    }
+
+    /// Accumulates a swipe delta.
+    ///
+    /// The delta should be in the direction of the physical finger movement, i.e. not inverted
+    /// by natural scrolling. Returns the swipe direction once the swipe has traveled far enough.
+    ///
+    /// With the `swipe-hysteresis` threshold, a triggered swipe that turns far enough to the side
+    /// changes its direction, and the new direction is returned again. The caller should then
+    /// undo or replace whatever it did for the previous direction.
+    ///
+    /// Directions with fling binds aren't returned here; they are returned from [`Self::end()`]
+    /// instead.
+    pub fn update(&mut self, dx: f64, dy: f64, timestamp: Duration) -> Option<GestureDirection> {
+        self.tracker_x.push(dx, timestamp);
+        self.tracker_y.push(dy, timestamp);
+
+        if self.triggered {
+            return self.redecide(dx, dy);
+        }
+
+        let (cx, cy) = &mut self.cumulative;
+        *cx += dx;
+        *cy += dy;
+
+        let (cx, cy) = (*cx, *cy);
+        let distance = self.thresholds.swipe_distance;
+        if cx * cx + cy * cy < distance * distance {
+            return None;
+        }
+
+        self.triggered = true;
+
+        let direction = self.direction(cx, cy);
+        self.decide(direction)
+    }
+
+    fn decide(&mut self, direction: GestureDirection) -> Option<GestureDirection> {
+        self.direction = Some(direction);
+        self.sideways = 0.;
+
+        if self.fling_directions.contains(&direction) {
+            self.pending = Some(direction);
+            return None;
+        }
+
+        self.pending = None;
+        Some(direction)
+    }
+
+    /// Changes the direction of a triggered swipe once it turns far enough to the side.
+    fn redecide(&mut self, dx: f64, dy: f64) -> Option<GestureDirection> {
+        let factor = self.thresholds.swipe_hysteresis;
+        let current = self.direction?;
+        if factor <= 0. {
+            return None;
+        }
+
+        // Split the delta into the parts along and to the side of the current direction.
+        let (ux, uy) = direction_vector(current);
+        let along = dx * ux + dy * uy;
+        let side = dy * ux - dx * uy;
+
+        if along > side.abs() {
+            // Still going the same way, so the sideways movement was just drift.
+            self.sideways = 0.;
+            return None;
+        }
+
+        self.sideways += side;
+        if self.sideways.abs() < factor * self.thresholds.swipe_distance {
+            return None;
+        }
+
+        // The side is to the right of the direction for positive values.
+        let direction = self.direction(-uy * self.sideways, ux * self.sideways);
+        self.decide(direction)
+    }
+
+    /// Returns whether the swipe has traveled far enough to trigger.
+    pub fn is_triggered(&self) -> bool {
+        self.triggered
+    }
+
+    /// Ends the swipe.
+    ///
+    /// Returns the direction that was waiting for the end of the swipe, if any, along with whether
+    /// the swipe was flung in that direction.
+    pub fn end(&mut self, timestamp: Duration) -> Option<(GestureDirection, bool)> {
+        let direction = self.pending.take()?;
+
+        // Take into account any idle time between the last movement and the end.
+        self.tracker_x.push(0., timestamp);
+        self.tracker_y.push(0., timestamp);
+
+        let (ux, uy) = direction_vector(direction);
+        let velocity = self.tracker_x.velocity() * ux + self.tracker_y.velocity() * uy;
+        Some((direction, velocity >= self.thresholds.fling_velocity))
+    }
+
+    /// Returns the direction the swipe is heading in, and how close it is to triggering.
+    ///
+    /// Returns `None` once the swipe has triggered or if it hasn't moved yet.
+    pub fn progress(&self) -> Option<(GestureDirection, f64)> {
+        let (cx, cy) = self.cumulative;
+        if self.triggered || (cx == 0. && cy == 0.) {
+            return None;
+        }
+
+        let progress = cx.hypot(cy) / self.thresholds.swipe_distance;
+        Some((self.direction(cx, cy), progress.min(1.)))
+    }
+
+    /// Describes the swipe state for the gesture debug overlay.
+    pub fn debug_text(&self) -> String {
+        let (cx, cy) = self.cumulative;
+        let mut text = format!(
+            "swipe-{}: cumulative ({cx:.1}, {cy:.1}), velocity ({:.0}, {:.0})",
+            self.fingers,
+            self.tracker_x.velocity(),
+            self.tracker_y.velocity(),
+        );
+        if let Some((direction, progress)) = self.progress() {
+            text += &format!(", heading {direction:?} at {:.0}%", progress * 100.);
+        }
+        if let Some(direction) = self.pending {
+            text += &format!(", waiting to fling {direction:?}");
+        } else if self.triggered {
+            text += ", triggered";
+        }
+        text
+    }
+
+    fn direction(&self, dx: f64, dy: f64) -> GestureDirection {
+        if self.diagonals {
+            let angle = dy.abs().atan2(dx.abs()).to_degrees();
+            if (angle - 45.).abs() <= self.thresholds.diagonal_angle {
+                return match (dx > 0., dy > 0.) {
+                    (false, false) => GestureDirection::UpLeft,
+                    (true, false) => GestureDirection::UpRight,
+                    (false, true) => GestureDirection::DownLeft,
+                    (true, true) => GestureDirection::DownRight,
+                };
+            }
+        }
+
+        swipe_direction(dx, dy)
+    }
+}
+
+/// Unit vector pointing in the swipe direction.
+fn direction_vector(direction: GestureDirection) -> (f64, f64) {
+    let d = std::f64::consts::FRAC_1_SQRT_2;
+    match direction {
+        GestureDirection::Left => (-1., 0.),
+        GestureDirection::Right => (1., 0.),
+        GestureDirection::Up => (0., -1.),
+        GestureDirection::Down => (0., 1.),
+        GestureDirection::UpLeft => (-d, -d),
+        GestureDirection::UpRight => (d, -d),
+        GestureDirection::DownLeft => (-d, d),
+        GestureDirection::DownRight => (d, d),
+        GestureDirection::In
+        | GestureDirection::Out
+        | GestureDirection::RotateCw
+        | GestureDirection::RotateCcw => (0., 0.),
+    }
+}
+
+fn swipe_direction(dx: f64, dy: f64) -> GestureDirection {
+    if dx.abs() > dy.abs() {
+        if dx > 0. {
+            GestureDirection::Right
+        } else {
+            GestureDirection::Left
+        }
+    } else if dy > 0. {
+        GestureDirection::Down
+    } else {
+        GestureDirection::Up
+    }
+}
+
+impl PinchGesture {
+    pub fn new(fingers: u8, thresholds: GestureThresholds) -> Self {
+        Self {
+            fingers,
+            thresholds,
+            scale: 1.,
+            rotation: 0.,
+            triggered: false,
+        }
+    }
+
+    /// Updates the pinch with the current absolute scale and the rotation delta in degrees.
+    ///
+    /// Returns the pinch direction once the scale or the rotation has changed enough.
+    pub fn update(&mut self, scale: f64, rotation: f64) -> Option<GestureDirection> {
+        if self.triggered {
+            return None;
+        }
+
+        self.scale = scale;
+        self.rotation += rotation;
+
+        let thresholds = &self.thresholds;
+        let direction = if self.rotation >= thresholds.rotation_angle {
+            GestureDirection::RotateCw
+        } else if self.rotation <= -thresholds.rotation_angle {
+            GestureDirection::RotateCcw
+        } else if scale <= thresholds.pinch_in_scale {
+            GestureDirection::In
+        } else if scale >= thresholds.pinch_out_scale {
+            GestureDirection::Out
+        } else {
+            return None;
+        };
+
+        self.triggered = true;
+        Some(direction)
+    }
+
+    /// Returns the direction the pinch is closest to triggering in, and how close it is.
+    ///
+    /// Returns `None` once the pinch has triggered or if it hasn't changed yet.
+    pub fn progress(&self) -> Option<(GestureDirection, f64)> {
+        if self.triggered {
+            return None;
+        }
+
+        let thresholds = &self.thresholds;
+        let (scale_direction, scale_progress) = if self.scale < 1. {
+            let progress = (1. - self.scale) / (1. - thresholds.pinch_in_scale);
+            (GestureDirection::In, progress)
+        } else {
+            let progress = (self.scale - 1.) / (thresholds.pinch_out_scale - 1.);
+            (GestureDirection::Out, progress)
+        };
+
+        let rotation_direction = if self.rotation < 0. {
+            GestureDirection::RotateCcw
+        } else {
+            GestureDirection::RotateCw
+        };
+        let rotation_progress = self.rotation.abs() / thresholds.rotation_angle;
+
+        let (direction, progress) = if rotation_progress > scale_progress {
+            (rotation_direction, rotation_progress)
+        } else {
+            (scale_direction, scale_progress)
+        };
+
+        // Guard against zero thresholds dividing into NaN.
+        (progress > 0.).then(|| (direction, progress.min(1.)))
+    }
+
+    /// Describes the pinch state for the gesture debug overlay.
+    pub fn debug_text(&self) -> String {
+        let mut text = format!(
+            "pinch-{}: scale {:.3}, rotation {:.1}°",
+            self.fingers, self.scale, self.rotation,
+        );
+        if let Some((direction, progress)) = self.progress() {
+            text += &format!(", heading {direction:?} at {:.0}%", progress * 100.);
+        }
+        if self.triggered {
+            text += ", triggered";
+        }
+        text
+    }
+}
+
+impl ResizePinchGesture {
+    pub fn new(fingers: u8, window: Window, original_size: Size<f64, Logical>) -> Self {
+        Self {
+            fingers,
+            window,
+            original_size,
+            scale: 1.,
+        }
+    }
+
+    pub fn window(&self) -> &Window {
+        &self.window
+    }
+
+    /// Updates the pinch with the current absolute scale.
+    ///
+    /// Returns the interactive resize delta from the bottom-right corner that scales both the
+    /// width and the height of the window by the pinch scale.
+    pub fn update(&mut self, scale: f64) -> Point<f64, Logical> {
+        self.scale = scale;
+        let size = self.original_size;
+        Point::from((size.w * (scale - 1.), size.h * (scale - 1.)))
+    }
+}
+
+impl MaximizeSwipeGesture {
+    pub fn new(fingers: u8, distance: f64) -> Self {
+        Self {
+            fingers,
+            distance,
+            cumulative: 0.,
+            step: None,
+        }
+    }
+
+    /// Accumulates a vertical swipe delta, not inverted by natural scrolling.
+    ///
+    /// Returns the step to take once the swipe has traveled far enough. Each step is taken at
+    /// most once per swipe, and swiping up takes the fullscreen step after the maximize step.
+    pub fn update(&mut self, dy: f64) -> Option<MaximizeSwipeStep> {
+        self.cumulative += dy;
+
+        let step = match self.step {
+            None if self.cumulative <= -self.distance => MaximizeSwipeStep::Maximize,
+            None if self.cumulative >= self.distance => MaximizeSwipeStep::Restore,
+            Some(MaximizeSwipeStep::Maximize) if self.cumulative <= -self.distance * 2. => {
+                MaximizeSwipeStep::Fullscreen
+            }
+            _ => return None,
+        };
+
+        self.step = Some(step);
+        Some(step)
+    }
+}
+
+impl OverviewPinchGesture {
+    pub fn new(fingers: u8) -> Self {
+        Self { fingers, scale: 1. }
+    }
+
+    /// Updates the pinch with the current absolute scale.
+    ///
+    /// Returns the equivalent overview swipe delta, positive when the fingers move apart. Uses a
+    /// logarithmic scale so that pinching in and out by the same factor cancels out.
+    pub fn update(&mut self, scale: f64) -> f64 {
+        // libinput should never report a zero scale, but let's be careful with the logarithm.
+        let scale = scale.max(0.01);
+        let delta = (scale / self.scale).log2() * OVERVIEW_PINCH_DISTANCE;
+        self.scale = scale;
+        delta
+    }
+}
+
+impl HoldGesture {
+    pub fn new(fingers: u8, time: u32, thresholds: GestureThresholds) -> Self {
+        Self {
+            fingers,
+            thresholds,
+            start_time: time,
+            state: HoldState::Holding,
+        }
+    }
+
+    /// Converts the hold into a window move when the fingers start moving.
+    ///
+    /// Returns `false` if the fingers started moving too soon, in which case this wasn't a hold.
+    pub fn convert(&mut self, time: u32) -> bool {
+        if time.wrapping_sub(self.start_time) < u32::from(self.thresholds.hold_drag_delay_ms) {
+            return false;
+        }
+
+        self.state = HoldState::Converted;
+        true
+    }
+
+    pub fn is_converted(&self) -> bool {
+        self.state == HoldState::Converted
+    }
+
+    /// Returns whether the hold, ending at this time, lasted long enough to trigger.
+    ///
+    /// Two-finger holds must rest for a while, so that a two-finger tap or the fingers touching
+    /// down before a scroll don't trigger them.
+    pub fn is_long_enough(&self, time: u32) -> bool {
+        self.fingers != 2
+            || time.wrapping_sub(self.start_time)
+                >= u32::from(self.thresholds.two_finger_hold_delay_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swipe_triggers_once() {
+        let mut gesture = SwipeGesture::new(3, GestureThresholds::default(), false, vec![]);
+        assert_eq!(gesture.update(-10., 2., Duration::ZERO), None);
+        assert_eq!(
+            gesture.update(-45., 3., Duration::ZERO),
+            Some(GestureDirection::Left)
+        );
+        assert_eq!(gesture.update(-100., 0., Duration::ZERO), None);
+    }
+
+    #[test]
+    fn swipe_changes_direction_with_hysteresis() {
+        let thresholds = GestureThresholds {
+            swipe_hysteresis: 2.,
+            ..Default::default()
+        };
+        let mut gesture = SwipeGesture::new(3, thresholds, false, vec![]);
+        assert_eq!(
+            gesture.update(-60., 0., Duration::ZERO),
+            Some(GestureDirection::Left)
+        );
+
+        // Drifting to the side while going left doesn't count.
+        for _ in 0..10 {
+            assert_eq!(gesture.update(-20., -15., Duration::ZERO), None);
+        }
+
+        // Turning up does, once it goes far enough.
+        assert_eq!(gesture.update(0., -60., Duration::ZERO), None);
+        assert_eq!(
+            gesture.update(-5., -50., Duration::ZERO),
+            Some(GestureDirection::Up)
+        );
+        assert_eq!(gesture.update(0., -100., Duration::ZERO), None);
+
+        // Without hysteresis, the swipe keeps its first direction.
+        let mut gesture = SwipeGesture::new(3, GestureThresholds::default(), false, vec![]);
+        gesture.update(-60., 0., Duration::ZERO);
+        assert_eq!(gesture.update(0., -500., Duration::ZERO), None);
+    }
+
+    #[test]
+    fn swipe_progress() {
+        let mut gesture = SwipeGesture::new(3, GestureThresholds::default(), false, vec![]);
+        assert_eq!(gesture.progress(), None);
+        gesture.update(-25., 0., Duration::ZERO);
+        assert_eq!(gesture.progress(), Some((GestureDirection::Left, 0.5)));
+        gesture.update(-30., 0., Duration::ZERO);
+        assert_eq!(gesture.progress(), None);
+    }
+
+    #[test]
+    fn swipe_fling() {
+        let thresholds = GestureThresholds::default();
+        let ms = Duration::from_millis;
+
+        // A fast swipe is a fling.
+        let mut gesture = SwipeGesture::new(3, thresholds, false, vec![GestureDirection::Left]);
+        assert_eq!(gesture.update(-30., 0., ms(0)), None);
+        assert_eq!(gesture.update(-30., 0., ms(10)), None);
+        assert_eq!(gesture.update(-30., 0., ms(20)), None);
+        assert_eq!(gesture.end(ms(25)), Some((GestureDirection::Left, true)));
+
+        // A slow one isn't.
+        let mut gesture = SwipeGesture::new(3, thresholds, false, vec![GestureDirection::Left]);
+        assert_eq!(gesture.update(-30., 0., ms(0)), None);
+        assert_eq!(gesture.update(-30., 0., ms(100)), None);
+        assert_eq!(gesture.end(ms(140)), Some((GestureDirection::Left, false)));
+
+        // Directions without fling binds trigger right away.
+        let mut gesture = SwipeGesture::new(3, thresholds, false, vec![GestureDirection::Left]);
+        assert_eq!(
+            gesture.update(60., 0., ms(0)),
+            Some(GestureDirection::Right)
+        );
+        assert_eq!(gesture.end(ms(10)), None);
+    }
+
+    #[test]
+    fn pinch_progress_picks_closest_direction() {
+        let thresholds = GestureThresholds {
+            pinch_in_scale: 0.5,
+            rotation_angle: 20.,
+            ..Default::default()
+        };
+        let mut gesture = PinchGesture::new(2, thresholds);
+        gesture.update(0.75, 5.);
+        assert_eq!(gesture.progress(), Some((GestureDirection::In, 0.5)));
+        gesture.update(0.75, 10.);
+        assert_eq!(gesture.progress(), Some((GestureDirection::RotateCw, 0.75)));
+    }
+
+    #[test]
+    fn swipe_diagonals() {
+        let thresholds = GestureThresholds::default();
+
+        let mut gesture = SwipeGesture::new(4, thresholds, true, vec![]);
+        assert_eq!(
+            gesture.update(40., -45., Duration::ZERO),
+            Some(GestureDirection::UpRight)
+        );
+
+        // Too far from the diagonal.
+        let mut gesture = SwipeGesture::new(4, thresholds, true, vec![]);
+        assert_eq!(
+            gesture.update(-15., 60., Duration::ZERO),
+            Some(GestureDirection::Down)
+        );
+
+        // Diagonals disabled.
+        let mut gesture = SwipeGesture::new(4, thresholds, false, vec![]);
+        assert_eq!(
+            gesture.update(40., -45., Duration::ZERO),
+            Some(GestureDirection::Up)
+        );
+    }
+
+    #[test]
+    fn swipe_uses_configured_distance() {
+        let thresholds = GestureThresholds {
+            swipe_distance: 20.,
+            ..Default::default()
+        };
+        let mut gesture = SwipeGesture::new(3, thresholds, false, vec![]);
+        assert_eq!(
+            gesture.update(0., 25., Duration::ZERO),
+            Some(GestureDirection::Down)
+        );
+    }
+
+    #[test]
+    fn swipe_picks_dominant_axis() {
+        let mut gesture = SwipeGesture::new(4, GestureThresholds::default(), false, vec![]);
+        assert_eq!(
+            gesture.update(30., 60., Duration::ZERO),
+            Some(GestureDirection::Down)
+        );
+
+        let mut gesture = SwipeGesture::new(4, GestureThresholds::default(), false, vec![]);
+        assert_eq!(
+            gesture.update(20., -60., Duration::ZERO),
+            Some(GestureDirection::Up)
+        );
+    }
+
+    #[test]
+    fn two_finger_hold_needs_delay() {
+        let thresholds = GestureThresholds::default();
+
+        let gesture = HoldGesture::new(2, 1000, thresholds);
+        assert!(!gesture.is_long_enough(1200));
+        assert!(gesture.is_long_enough(1500));
+
+        let gesture = HoldGesture::new(3, 1000, thresholds);
+        assert!(gesture.is_long_enough(1010));
+    }
+
+    #[test]
+    fn pinch_triggers_once() {
+        let mut gesture = PinchGesture::new(3, GestureThresholds::default());
+        assert_eq!(gesture.update(0.9, 0.), None);
+        assert_eq!(gesture.update(1.1, 0.), None);
+        assert_eq!(gesture.update(1.5, 0.), Some(GestureDirection::Out));
+        assert_eq!(gesture.update(0.5, 0.), None);
+    }
+
+    #[test]
+    fn overview_pinch_is_symmetric() {
+        let mut gesture = OverviewPinchGesture::new(4);
+        assert_eq!(gesture.update(2.), OVERVIEW_PINCH_DISTANCE);
+        assert_eq!(gesture.update(1.), -OVERVIEW_PINCH_DISTANCE);
+        assert_eq!(gesture.update(0.5), -OVERVIEW_PINCH_DISTANCE);
+    }
+
+    #[test]
+    fn hold_converts_after_delay() {
+        let mut gesture = HoldGesture::new(3, 1000, GestureThresholds::default());
+        assert!(!gesture.convert(1100));
+        assert!(!gesture.is_converted());
+        assert!(gesture.convert(1400));
+        assert!(gesture.is_converted());
+    }
+
+    #[test]
+    fn maximize_swipe_steps() {
+        let mut gesture = MaximizeSwipeGesture::new(3, 50.);
+        assert_eq!(gesture.update(-30.), None);
+        assert_eq!(gesture.update(-30.), Some(MaximizeSwipeStep::Maximize));
+        assert_eq!(gesture.update(-30.), None);
+        assert_eq!(gesture.update(-20.), Some(MaximizeSwipeStep::Fullscreen));
+        assert_eq!(gesture.update(200.), None);
+
+        let mut gesture = MaximizeSwipeGesture::new(3, 50.);
+        assert_eq!(gesture.update(60.), Some(MaximizeSwipeStep::Restore));
+        assert_eq!(gesture.update(-200.), None);
+    }
+
+    #[test]
+    fn pinch_rotation_accumulates() {
+        let mut gesture = PinchGesture::new(2, GestureThresholds::default());
+        assert_eq!(gesture.update(1., -10.), None);
+        assert_eq!(gesture.update(1., -15.), None);
+        assert_eq!(
+            gesture.update(1.05, -10.),
+            Some(GestureDirection::RotateCcw)
+        );
+        assert_eq!(gesture.update(1., 90.), None);
+    }
+}
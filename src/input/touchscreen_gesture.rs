@@ -0,0 +1,305 @@
+//! Multi-finger touchscreen gestures for the config gesture binds.
+//!
+//! Tracks the touchscreen contacts and, once three or four fingers are down, recognizes the same
+//! swipes, pinches and holds as the touchpad, using the touchpad state machines. The swipe and
+//! pinch are fed from the centroid and the spread of the contacts.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use niri_config::gestures::{GestureThresholds, GestureTrigger};
+use niri_config::{GestureDirection, GestureKind};
+use smithay::backend::input::TouchSlot;
+use smithay::utils::{Logical, Point};
+
+use super::touchpad_gesture::{PinchGesture, SwipeGesture};
+
+#[derive(Debug, Default)]
+pub struct TouchscreenGesture {
+    /// Current positions of all touchscreen contacts.
+    points: HashMap<TouchSlot, Point<f64, Logical>>,
+    /// Gesture being recognized from the contacts.
+    active: Option<ActiveGesture>,
+}
+
+#[derive(Debug)]
+struct ActiveGesture {
+    fingers: u8,
+    centroid: Point<f64, Logical>,
+    initial_spread: f64,
+    swipe: SwipeGesture,
+    pinch: PinchGesture,
+    /// Whether the gesture has triggered a bind or lost a finger.
+    ///
+    /// The gesture keeps consuming the touch events until all fingers are lifted.
+    ended: bool,
+}
+
+impl TouchscreenGesture {
+    /// Returns whether a gesture is consuming the touch events.
+    pub fn is_active(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Returns the number of contacts currently down.
+    pub fn fingers(&self) -> u8 {
+        u8::try_from(self.points.len()).unwrap_or(u8::MAX)
+    }
+
+    /// Returns whether a gesture can start, or restart, with the current contacts.
+    pub fn can_start(&self) -> bool {
+        let ended = self.active.as_ref().is_some_and(|gesture| gesture.ended);
+        !ended && (3..=4).contains(&self.fingers())
+    }
+
+    /// Starts recognizing a gesture from the current contacts.
+    pub fn start(
+        &mut self,
+        thresholds: GestureThresholds,
+        diagonals: bool,
+        fling_directions: Vec<GestureDirection>,
+    ) {
+        let fingers = self.fingers();
+        let centroid = self.centroid();
+        self.active = Some(ActiveGesture {
+            fingers,
+            centroid,
+            initial_spread: self.spread(centroid),
+            swipe: SwipeGesture::new(fingers, thresholds, diagonals, fling_directions),
+            pinch: PinchGesture::new(fingers, thresholds),
+            ended: false,
+        });
+    }
+
+    pub fn down(&mut self, slot: TouchSlot, pos: Point<f64, Logical>) {
+        self.points.insert(slot, pos);
+    }
+
+    /// Moves a contact.
+    ///
+    /// Returns the gesture that should trigger, if any.
+    pub fn motion(
+        &mut self,
+        slot: TouchSlot,
+        pos: Point<f64, Logical>,
+        timestamp: Duration,
+    ) -> Option<GestureTrigger> {
+        let prev_pos = self.points.insert(slot, pos);
+
+        let centroid = self.centroid();
+        let spread = self.spread(centroid);
+        let count = self.points.len() as f64;
+
+        let gesture = self.active.as_mut()?;
+        if gesture.ended {
+            return None;
+        }
+
+        // The rotation of the whole gesture is the rotation of this contact around the centroid,
+        // averaged over all contacts.
+        let rotation = prev_pos.map_or(0., |prev_pos| {
+            let before = angle(prev_pos - gesture.centroid);
+            let after = angle(pos - centroid);
+            let delta = (after - before + 540.) % 360. - 180.;
+            delta / count
+        });
+
+        let delta = centroid - gesture.centroid;
+        gesture.centroid = centroid;
+
+        let triggered = if let Some(direction) = gesture.swipe.update(delta.x, delta.y, timestamp) {
+            Some((GestureKind::Swipe, direction))
+        } else if gesture.swipe.is_triggered() {
+            // The swipe is waiting for the fingers to lift to tell whether it was flung.
+            None
+        } else if gesture.initial_spread > 0. {
+            let scale = spread / gesture.initial_spread;
+            let direction = gesture.pinch.update(scale, rotation);
+            direction.map(|direction| (GestureKind::Pinch, direction))
+        } else {
+            None
+        };
+
+        let (kind, direction) = triggered?;
+        gesture.ended = true;
+        Some(GestureTrigger {
+            kind,
+            fingers: gesture.fingers,
+            direction: Some(direction),
+            fling: false,
+        })
+    }
+
+    /// Lifts a contact.
+    ///
+    /// Returns the gesture that should trigger, if any.
+    pub fn up(&mut self, slot: TouchSlot, timestamp: Duration) -> Option<GestureTrigger> {
+        self.points.remove(&slot);
+
+        let mut triggered = None;
+        if let Some(gesture) = &mut self.active {
+            if !gesture.ended {
+                gesture.ended = true;
+
+                let fingers = gesture.fingers;
+                triggered = Some(match gesture.swipe.end(timestamp) {
+                    Some((direction, fling)) => GestureTrigger {
+                        kind: GestureKind::Swipe,
+                        fingers,
+                        direction: Some(direction),
+                        fling,
+                    },
+                    // Lifting a finger before the gesture moved enough to swipe or pinch ends a
+                    // hold.
+                    None => GestureTrigger {
+                        kind: GestureKind::Hold,
+                        fingers,
+                        direction: None,
+                        fling: false,
+                    },
+                });
+            }
+        }
+
+        if self.points.is_empty() {
+            self.active = None;
+        }
+
+        triggered
+    }
+
+    /// Ends the active gesture without triggering anything.
+    ///
+    /// Returns whether there was a gesture to end. The contacts keep being consumed until they
+    /// are lifted.
+    pub fn abort(&mut self) -> bool {
+        match &mut self.active {
+            Some(gesture) if !gesture.ended => {
+                gesture.ended = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Describes the gesture state for the gesture debug overlay.
+    pub fn debug_text(&self) -> Option<String> {
+        let gesture = self.active.as_ref()?;
+        let mut text = format!(
+            "{} fingers\n  {}\n  {}",
+            gesture.fingers,
+            gesture.swipe.debug_text(),
+            gesture.pinch.debug_text(),
+        );
+        if gesture.ended {
+            text += "\n  ended";
+        }
+        Some(text)
+    }
+
+    pub fn cancel(&mut self) {
+        self.points.clear();
+        self.active = None;
+    }
+
+    fn centroid(&self) -> Point<f64, Logical> {
+        let count = self.points.len().max(1) as f64;
+        let sum = self
+            .points
+            .values()
+            .fold(Point::from((0., 0.)), |acc, pos| acc + *pos);
+        Point::from((sum.x / count, sum.y / count))
+    }
+
+    /// Average distance of the contacts from the centroid.
+    fn spread(&self, centroid: Point<f64, Logical>) -> f64 {
+        let count = self.points.len().max(1) as f64;
+        let sum: f64 = self
+            .points
+            .values()
+            .map(|pos| {
+                let d = *pos - centroid;
+                d.x.hypot(d.y)
+            })
+            .sum();
+        sum / count
+    }
+}
+
+/// Angle of the vector in degrees, growing clockwise on the screen.
+fn angle(vec: Point<f64, Logical>) -> f64 {
+    vec.y.atan2(vec.x).to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slot(id: u32) -> TouchSlot {
+        TouchSlot::from(Some(id))
+    }
+
+    fn trigger(kind: GestureKind, direction: Option<GestureDirection>) -> GestureTrigger {
+        GestureTrigger {
+            kind,
+            fingers: 3,
+            direction,
+            fling: false,
+        }
+    }
+
+    fn down_three(gesture: &mut TouchscreenGesture) {
+        gesture.down(slot(0), Point::from((100., 100.)));
+        gesture.down(slot(1), Point::from((200., 100.)));
+        gesture.down(slot(2), Point::from((150., 200.)));
+        assert!(gesture.can_start());
+        gesture.start(GestureThresholds::default(), false, vec![]);
+    }
+
+    #[test]
+    fn three_finger_swipe() {
+        let mut gesture = TouchscreenGesture::default();
+        down_three(&mut gesture);
+
+        let t = Duration::ZERO;
+        assert_eq!(gesture.motion(slot(0), Point::from((40., 100.)), t), None);
+        assert_eq!(gesture.motion(slot(1), Point::from((140., 100.)), t), None);
+        assert_eq!(
+            gesture.motion(slot(2), Point::from((90., 200.)), t),
+            Some(trigger(GestureKind::Swipe, Some(GestureDirection::Left)))
+        );
+
+        // Triggers only once, and lifting afterwards doesn't trigger a hold.
+        assert_eq!(gesture.motion(slot(2), Point::from((0., 200.)), t), None);
+        assert_eq!(gesture.up(slot(0), t), None);
+        assert!(gesture.is_active());
+        gesture.up(slot(1), t);
+        gesture.up(slot(2), t);
+        assert!(!gesture.is_active());
+    }
+
+    #[test]
+    fn three_finger_pinch() {
+        let mut gesture = TouchscreenGesture::default();
+        down_three(&mut gesture);
+
+        assert_eq!(
+            gesture.motion(slot(2), Point::from((150., 280.)), Duration::ZERO),
+            Some(trigger(GestureKind::Pinch, Some(GestureDirection::Out)))
+        );
+    }
+
+    #[test]
+    fn three_finger_hold() {
+        let mut gesture = TouchscreenGesture::default();
+        down_three(&mut gesture);
+
+        let t = Duration::ZERO;
+        assert_eq!(gesture.motion(slot(0), Point::from((102., 101.)), t), None);
+        assert_eq!(
+            gesture.up(slot(0), t),
+            Some(trigger(GestureKind::Hold, None))
+        );
+        assert_eq!(gesture.up(slot(1), t), None);
+    }
+}
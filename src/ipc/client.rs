@@ -6,13 +6,15 @@ use std::{env, slice};
 use anyhow::{anyhow, bail, Context};
 use niri_config::OutputName;
 use niri_ipc::socket::Socket;
+use niri_ipc::state::{EventStreamStatePart as _, WindowsState};
 use niri_ipc::{
-    Action, Event, KeyboardLayouts, LogicalOutput, Mode, Output, OutputConfigChanged, Overview,
-    Request, Response, Transform, Window, WindowLayout,
+    Action, Event, GestureBind, GestureTrigger, InputLatency, InputLatencyStage, KeyboardLayouts,
+    LatencyBucket, LogicalOutput, Mode, Output, OutputConfigChanged, Overview, Request, Response,
+    StateDump, Transform, WatchMessage, Window, WindowLayout, WorkspaceReferenceArg,
 };
 use serde_json::json;
 
-use crate::cli::Msg;
+use crate::cli::{Msg, WorkspaceMsg};
 use crate::utils::version;
 
 pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
@@ -21,6 +23,7 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
         action:
             Action::Screenshot { path, .. }
             | Action::ScreenshotScreen { path, .. }
+            | Action::ScreenshotRegion { path, .. }
             | Action::ScreenshotWindow { path, .. },
     } = &mut msg
     {
@@ -31,12 +34,30 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
 
     let request = match &msg {
         Msg::Version => Request::Version,
-        Msg::Outputs => Request::Outputs,
-        Msg::FocusedWindow => Request::FocusedWindow,
+        Msg::Outputs { .. } => Request::Outputs,
+        Msg::FocusedWindow { watch: false } => Request::FocusedWindow,
+        Msg::FocusedWindow { watch: true } => Request::EventStream,
         Msg::FocusedOutput => Request::FocusedOutput,
         Msg::PickWindow => Request::PickWindow,
         Msg::PickColor => Request::PickColor,
         Msg::Action { action } => Request::Action(action.clone()),
+        Msg::Actions { actions } => {
+            let actions = serde_json::from_str(actions).context("error parsing actions")?;
+            Request::Actions(actions)
+        }
+        Msg::Workspace { action } => {
+            let action = match action {
+                WorkspaceMsg::Rename { id, name } => Action::SetWorkspaceName {
+                    name: name.clone(),
+                    workspace: Some(WorkspaceReferenceArg::Id(*id)),
+                },
+                WorkspaceMsg::Move { id, index } => Action::MoveWorkspaceToIndex {
+                    index: *index,
+                    reference: Some(WorkspaceReferenceArg::Id(*id)),
+                },
+            };
+            Request::Action(action)
+        }
         Msg::Output { output, action } => Request::Output {
             output: output.clone(),
             action: action.clone(),
@@ -44,10 +65,33 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
         Msg::Workspaces => Request::Workspaces,
         Msg::Windows => Request::Windows,
         Msg::Layers => Request::Layers,
+        Msg::Marks => Request::Marks,
         Msg::KeyboardLayouts => Request::KeyboardLayouts,
         Msg::EventStream => Request::EventStream,
+        Msg::Watch { resources } => Request::Watch {
+            resources: resources.clone(),
+        },
         Msg::RequestError => Request::ReturnError,
         Msg::OverviewState => Request::OverviewState,
+        Msg::InputLatency => Request::InputLatency,
+        Msg::GestureBinds => Request::GestureBinds,
+        Msg::GestureBind {
+            kind,
+            fingers,
+            direction,
+            fling,
+            change,
+        } => Request::GestureBind {
+            trigger: GestureTrigger {
+                kind: *kind,
+                fingers: *fingers,
+                direction: *direction,
+                fling: *fling,
+            },
+            change: change.clone(),
+        },
+        Msg::ResetGestureBinds => Request::ResetGestureBinds,
+        Msg::DumpState => Request::DumpState,
     };
 
     let mut socket = Socket::connect().context("error connecting to the niri socket")?;
@@ -140,12 +184,12 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
             println!("Compositor version: {compositor_version}");
             println!("CLI version:        {cli_version}");
         }
-        Msg::Outputs => {
+        Msg::Outputs { kdl } => {
             let Response::Outputs(outputs) = response else {
                 bail!("unexpected response: expected Outputs, got {response:?}");
             };
 
-            if json {
+            if json && !kdl {
                 let output =
                     serde_json::to_string(&outputs).context("error formatting response")?;
                 println!("{output}");
@@ -158,12 +202,52 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 .collect::<Vec<_>>();
             outputs.sort_unstable_by(|a, b| a.0.compare(&b.0));
 
-            for (_name, output) in outputs.into_iter() {
-                print_output(output)?;
+            for (name, output) in outputs.into_iter() {
+                if kdl {
+                    print_output_kdl(&name, output)?;
+                } else {
+                    print_output(output)?;
+                }
+                println!();
+            }
+        }
+        Msg::FocusedWindow { watch: true } => {
+            let Response::Handled = response else {
+                bail!("unexpected response: expected Handled, got {response:?}");
+            };
+
+            let mut state = WindowsState::default();
+            let mut last = None;
+            let mut read_event = socket.read_events();
+            loop {
+                let event = read_event().context("error reading event from niri")?;
+                if state.apply(event).is_some() {
+                    continue;
+                }
+
+                let window = state.windows.values().find(|win| win.is_focused);
+                let key = window.map(|win| (win.id, win.title.clone(), win.app_id.clone()));
+                if last.as_ref() == Some(&key) {
+                    continue;
+                }
+                last = Some(key);
+
+                if json {
+                    let window =
+                        serde_json::to_string(&window).context("error formatting window")?;
+                    println!("{window}");
+                    continue;
+                }
+
+                if let Some(window) = window {
+                    print_window(window);
+                } else {
+                    println!("No window is focused.");
+                }
                 println!();
             }
         }
-        Msg::FocusedWindow => {
+        Msg::FocusedWindow { watch: false } => {
             let Response::FocusedWindow(window) = response else {
                 bail!("unexpected response: expected FocusedWindow, got {response:?}");
             };
@@ -316,7 +400,7 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 println!("No color was picked.");
             }
         }
-        Msg::Action { .. } => {
+        Msg::Action { .. } | Msg::Actions { .. } | Msg::Workspace { .. } => {
             let Response::Handled = response else {
                 bail!("unexpected response: expected Handled, got {response:?}");
             };
@@ -386,6 +470,29 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 println!("{is_active}{idx}{name}");
             }
         }
+        Msg::Marks => {
+            let Response::Marks(mut marks) = response else {
+                bail!("unexpected response: expected Marks, got {response:?}");
+            };
+
+            if json {
+                let marks = serde_json::to_string(&marks).context("error formatting response")?;
+                println!("{marks}");
+                return Ok(());
+            }
+
+            if marks.is_empty() {
+                println!("No marks set.");
+                return Ok(());
+            }
+
+            marks.sort_unstable_by_key(|m| m.mark);
+
+            println!("Marks:");
+            for mark in marks {
+                println!("  {}: window {}", mark.mark, mark.window_id);
+            }
+        }
         Msg::KeyboardLayouts => {
             let Response::KeyboardLayouts(response) = response else {
                 bail!("unexpected response: expected KeyboardLayouts, got {response:?}");
@@ -407,6 +514,35 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 println!("{is_active}{idx} {name}");
             }
         }
+        Msg::Watch { .. } => {
+            let Response::Handled = response else {
+                bail!("unexpected response: expected Handled, got {response:?}");
+            };
+
+            let mut read_message = socket.read_watch_messages();
+            loop {
+                let message = read_message().context("error reading message from niri")?;
+
+                if json {
+                    let message =
+                        serde_json::to_string(&message).context("error formatting message")?;
+                    println!("{message}");
+                    continue;
+                }
+
+                match message {
+                    WatchMessage::Snapshot { seq, events } => {
+                        println!("Snapshot at {seq}:");
+                        for event in events {
+                            println!("  {event:?}");
+                        }
+                    }
+                    WatchMessage::Diff { seq, event } => {
+                        println!("{seq}: {event:?}");
+                    }
+                }
+            }
+        }
         Msg::EventStream => {
             let Response::Handled = response else {
                 bail!("unexpected response: expected Handled, got {response:?}");
@@ -470,6 +606,9 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                     Event::WindowLayoutsChanged { changes } => {
                         println!("Window layouts changed: {changes:?}");
                     }
+                    Event::OutputsChanged { outputs } => {
+                        println!("Outputs changed: {outputs:?}");
+                    }
                     Event::KeyboardLayoutsChanged { keyboard_layouts } => {
                         println!("Keyboard layouts changed: {keyboard_layouts:?}");
                     }
@@ -479,6 +618,9 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                     Event::OverviewOpenedOrClosed { is_open: opened } => {
                         println!("Overview toggled: {opened}");
                     }
+                    Event::TabletModeChanged { is_tablet_mode } => {
+                        println!("Tablet mode toggled: {is_tablet_mode}");
+                    }
                     Event::ConfigLoaded { failed } => {
                         let status = if failed {
                             "with an error"
@@ -496,6 +638,19 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                         let description = parts.join(" and ");
                         println!("Screenshot captured: {description}");
                     }
+                    Event::GestureBegin { kind, fingers } => {
+                        println!("Gesture began: {kind:?} with {fingers} fingers");
+                    }
+                    Event::GestureUpdate {
+                        direction,
+                        progress,
+                    } => {
+                        println!("Gesture updated: {direction:?} at {progress:.2}");
+                    }
+                    Event::GestureEnd { cancelled } => {
+                        let status = if cancelled { "cancelled" } else { "ended" };
+                        println!("Gesture {status}");
+                    }
                 }
             }
         }
@@ -518,11 +673,207 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 println!("Overview is closed.");
             }
         }
+        Msg::InputLatency => {
+            let Response::InputLatency(response) = response else {
+                bail!("unexpected response: expected InputLatency, got {response:?}");
+            };
+
+            if json {
+                let response =
+                    serde_json::to_string(&response).context("error formatting response")?;
+                println!("{response}");
+                return Ok(());
+            }
+
+            print_input_latency(response);
+        }
+        Msg::GestureBinds => {
+            let Response::GestureBinds(response) = response else {
+                bail!("unexpected response: expected GestureBinds, got {response:?}");
+            };
+
+            if json {
+                let response =
+                    serde_json::to_string(&response).context("error formatting response")?;
+                println!("{response}");
+                return Ok(());
+            }
+
+            for bind in response {
+                print_gesture_bind(&bind);
+            }
+        }
+        Msg::GestureBind { .. } | Msg::ResetGestureBinds => {
+            let Response::Handled = response else {
+                bail!("unexpected response: expected Handled, got {response:?}");
+            };
+        }
+        Msg::DumpState => {
+            let Response::StateDump(response) = response else {
+                bail!("unexpected response: expected StateDump, got {response:?}");
+            };
+
+            if json {
+                let response =
+                    serde_json::to_string(&response).context("error formatting response")?;
+                println!("{response}");
+                return Ok(());
+            }
+
+            print_state_dump(response)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_state_dump(dump: StateDump) -> anyhow::Result<()> {
+    let StateDump {
+        version,
+        config_error,
+        outputs,
+        mut workspaces,
+        mut windows,
+        input_devices,
+        gestures,
+    } = dump;
+
+    println!("niri version: {version}");
+    println!();
+
+    if let Some(err) = config_error {
+        println!("Config failed to load:");
+        println!("{err}");
+    } else {
+        println!("Config loaded successfully.");
+    }
+    println!();
+
+    for output in outputs {
+        print_output(output)?;
+        println!();
+    }
+
+    workspaces.sort_unstable_by(|a, b| a.output.cmp(&b.output).then(a.idx.cmp(&b.idx)));
+    windows.sort_unstable_by_key(|win| win.layout.pos_in_scrolling_layout);
+    for ws in workspaces {
+        let output = ws.output.as_deref().unwrap_or("no output");
+        let name = ws
+            .name
+            .map(|name| format!(" \"{name}\""))
+            .unwrap_or_default();
+        let mut qualifier = Vec::new();
+        if ws.is_active {
+            qualifier.push("active");
+        }
+        if ws.is_focused {
+            qualifier.push("focused");
+        }
+        let qualifier = if qualifier.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", qualifier.join(", "))
+        };
+        println!(
+            "Workspace {}{name} on {output}, ID {}:{qualifier}",
+            ws.idx, ws.id
+        );
+
+        for win in windows.iter().filter(|win| win.workspace_id == Some(ws.id)) {
+            let app_id = win.app_id.as_deref().unwrap_or("(unset)");
+            let (width, height) = win.layout.window_size;
+            let pos = match win.layout.pos_in_scrolling_layout {
+                Some((column, tile)) => format!("column {column}, tile {tile}"),
+                None => String::from("floating"),
+            };
+            println!("  Window ID {}: {app_id}, {width}x{height}, {pos}", win.id);
+        }
+    }
+    println!();
+
+    println!("Input devices:");
+    for device in input_devices {
+        println!("  {}: {:?}", device.name, device.capabilities);
     }
+    println!();
+
+    println!("Gestures:");
+    println!("{gestures}");
 
     Ok(())
 }
 
+fn print_gesture_bind(bind: &GestureBind) {
+    let GestureTrigger {
+        kind,
+        fingers,
+        direction,
+        fling,
+    } = bind.trigger;
+
+    let mut gesture = format!("{kind:?} with {fingers} fingers");
+    if let Some(direction) = direction {
+        gesture.push_str(&format!(" {direction:?}"));
+    }
+    if fling {
+        gesture.push_str(" (fling)");
+    }
+
+    let action = bind.action.as_deref().unwrap_or("(disabled)");
+    let overridden = if bind.is_overridden {
+        " (changed over IPC)"
+    } else {
+        ""
+    };
+    println!("{gesture}: {action}{overridden}");
+}
+
+fn print_input_latency(latency: InputLatency) {
+    let InputLatency { enabled, stages } = latency;
+    if !enabled {
+        println!("Input latency tracing is off; enable the trace-input-latency debug flag.");
+        if stages.iter().all(|stage| stage.count == 0) {
+            return;
+        }
+        println!();
+    }
+
+    for stage in stages {
+        let InputLatencyStage {
+            stage,
+            count,
+            mean_us,
+            max_us,
+            buckets,
+        } = stage;
+
+        println!("{stage:?}: {count} events");
+        if count == 0 {
+            continue;
+        }
+
+        println!("  Mean: {}", fmt_micros(mean_us));
+        println!("  Max: {}", fmt_micros(max_us));
+        let total = count;
+        let mut prev_max_us = 0;
+        for LatencyBucket { max_us, count } in buckets {
+            let bound = match max_us {
+                Some(max_us) => {
+                    prev_max_us = max_us;
+                    format!("up to {}", fmt_micros(max_us))
+                }
+                None => format!("above {}", fmt_micros(prev_max_us)),
+            };
+            let percent = count as f64 / total as f64 * 100.;
+            println!("  {bound:>12}: {count} ({percent:.1}%)");
+        }
+    }
+}
+
+fn fmt_micros(us: u64) -> String {
+    format!("{} ms", fmt_rounded(us as f64 / 1000.))
+}
+
 fn print_output(output: Output) -> anyhow::Result<()> {
     let Output {
         name,
@@ -637,6 +988,56 @@ fn print_output(output: Output) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Prints the current settings of the output as an `output {}` config section.
+fn print_output_kdl(name: &OutputName, output: Output) -> anyhow::Result<()> {
+    let name = name.format_make_model_serial_or_connector();
+    println!("output {name:?} {{");
+
+    let (Some(current), Some(logical)) = (output.current_mode, output.logical) else {
+        println!("    off");
+        println!("}}");
+        return Ok(());
+    };
+
+    let mode = output
+        .modes
+        .get(current)
+        .context("invalid response: current mode does not exist")?;
+    let refresh = mode.refresh_rate as f64 / 1000.;
+    let custom = if output.is_custom_mode {
+        "custom=true "
+    } else {
+        ""
+    };
+    println!(
+        r#"    mode {custom}"{}x{}@{refresh:.3}""#,
+        mode.width, mode.height
+    );
+
+    println!("    scale {}", logical.scale);
+
+    let transform = match logical.transform {
+        Transform::Normal => "normal",
+        Transform::_90 => "90",
+        Transform::_180 => "180",
+        Transform::_270 => "270",
+        Transform::Flipped => "flipped",
+        Transform::Flipped90 => "flipped-90",
+        Transform::Flipped180 => "flipped-180",
+        Transform::Flipped270 => "flipped-270",
+    };
+    println!(r#"    transform "{transform}""#);
+
+    println!("    position x={} y={}", logical.x, logical.y);
+
+    if output.vrr_enabled {
+        println!("    variable-refresh-rate");
+    }
+
+    println!("}}");
+    Ok(())
+}
+
 fn print_window(window: &Window) {
     let focused = if window.is_focused { " (focused)" } else { "" };
     let urgent = if window.is_urgent { " (urgent)" } else { "" };
@@ -658,6 +1059,14 @@ fn print_window(window: &Window) {
         "  Is floating: {}",
         if window.is_floating { "yes" } else { "no" }
     );
+    println!(
+        "  Is fullscreen: {}",
+        if window.is_fullscreen { "yes" } else { "no" }
+    );
+    println!(
+        "  Is minimized: {}",
+        if window.is_minimized { "yes" } else { "no" }
+    );
 
     if let Some(pid) = window.pid {
         println!("  PID: {pid}");
@@ -671,6 +1080,12 @@ fn print_window(window: &Window) {
         println!("  Workspace ID: (none)");
     }
 
+    if let Some(output) = &window.output {
+        println!("  Output: \"{output}\"");
+    } else {
+        println!("  Output: (none)");
+    }
+
     let WindowLayout {
         pos_in_scrolling_layout,
         tile_size,
@@ -0,0 +1,175 @@
+//! Commands from the `hooks {}` config section.
+//!
+//! The hooks compare the layout and the outputs against what they saw on the previous refresh to
+//! tell which hooks to run. Minimized and swallowed windows count as open. This is independent of
+//! the IPC event stream, so the hooks run even without an IPC socket. The details go into `NIRI_*`
+//! environment variables of the spawned command.
+
+use std::collections::{HashMap, HashSet};
+
+use niri_config::Hook;
+
+use crate::layout::LayoutElement as _;
+use crate::niri::State;
+use crate::utils::spawning::spawn_with_env;
+use crate::utils::with_toplevel_role;
+
+/// What the hooks saw on the previous refresh.
+#[derive(Debug, Default)]
+pub struct HookState {
+    /// Open windows by id.
+    windows: HashMap<u64, HookWindow>,
+    /// Id of the focused workspace.
+    focused_workspace: Option<u64>,
+    /// Names of the connected outputs.
+    outputs: HashSet<String>,
+}
+
+/// Window details for the window hooks, kept around for when the window closes.
+#[derive(Debug)]
+struct HookWindow {
+    title: Option<String>,
+    app_id: Option<String>,
+    workspace_id: Option<u64>,
+    output: Option<String>,
+}
+
+impl State {
+    /// Runs the window and workspace hooks for the changes since the previous refresh.
+    pub fn refresh_hooks(&mut self) {
+        let _span = tracy_client::span!("State::refresh_hooks");
+
+        let config = self.niri.config.borrow();
+        let hooks = &config.hooks;
+        let hook_state = &mut self.niri.hook_state;
+        let layout = &self.niri.layout;
+
+        // Check for opened and closed windows.
+        let mut seen = HashSet::new();
+        layout.with_windows(|mapped, output, ws_id, _| {
+            let id = mapped.id().get();
+            seen.insert(id);
+
+            let workspace_id = ws_id.map(|id| id.get());
+            let output = output.map(|output| output.name());
+
+            if let Some(window) = hook_state.windows.get_mut(&id) {
+                with_toplevel_role(mapped.toplevel(), |role| {
+                    if window.title != role.title {
+                        window.title.clone_from(&role.title);
+                    }
+                    if window.app_id != role.app_id {
+                        window.app_id.clone_from(&role.app_id);
+                    }
+                });
+                // Interactively moved windows have no workspace, keep the last one for them.
+                if workspace_id.is_some() {
+                    window.workspace_id = workspace_id;
+                }
+                window.output = output;
+                return;
+            }
+
+            let window = with_toplevel_role(mapped.toplevel(), |role| HookWindow {
+                title: role.title.clone(),
+                app_id: role.app_id.clone(),
+                workspace_id,
+                output,
+            });
+            run(&hooks.on_window_open, window_env(id, &window));
+            hook_state.windows.insert(id, window);
+        });
+
+        // Minimized and swallowed windows are out of the layout, but they are still open.
+        let minimized = self.niri.minimized_windows.iter().map(|m| &m.mapped);
+        for mapped in minimized.chain(self.niri.swallowed_windows.values()) {
+            seen.insert(mapped.id().get());
+        }
+
+        hook_state.windows.retain(|id, window| {
+            if seen.contains(id) {
+                return true;
+            }
+
+            run(&hooks.on_window_close, window_env(*id, window));
+            false
+        });
+
+        // Check for a newly focused workspace.
+        let focused_ws_id = layout.active_workspace().map(|ws| ws.id().get());
+        if focused_ws_id != hook_state.focused_workspace {
+            hook_state.focused_workspace = focused_ws_id;
+
+            let focused = layout
+                .workspaces()
+                .find(|(_, _, ws)| Some(ws.id().get()) == focused_ws_id);
+            if let Some((mon, ws_idx, ws)) = focused {
+                let mut env = vec![
+                    (String::from("NIRI_WORKSPACE_ID"), ws.id().get().to_string()),
+                    (String::from("NIRI_WORKSPACE_IDX"), (ws_idx + 1).to_string()),
+                ];
+                if let Some(name) = ws.name() {
+                    env.push((String::from("NIRI_WORKSPACE_NAME"), name.clone()));
+                }
+                if let Some(mon) = mon {
+                    env.push((String::from("NIRI_OUTPUT"), mon.output_name().clone()));
+                }
+                run(&hooks.on_workspace_focus, env);
+            }
+        }
+    }
+
+    /// Runs the output hooks for the outputs connected or disconnected since the previous
+    /// refresh.
+    pub fn refresh_output_hooks(&mut self) {
+        let config = self.niri.config.borrow();
+        let hooks = &config.hooks;
+        let hook_state = &mut self.niri.hook_state;
+
+        let ipc_outputs = self.backend.ipc_outputs();
+        let ipc_outputs = ipc_outputs.lock().unwrap();
+
+        for output in ipc_outputs.values() {
+            if !hook_state.outputs.contains(&output.name) {
+                run(&hooks.on_output_connect, output_env(&output.name));
+                hook_state.outputs.insert(output.name.clone());
+            }
+        }
+
+        hook_state.outputs.retain(|name| {
+            if ipc_outputs.values().any(|output| output.name == *name) {
+                return true;
+            }
+
+            run(&hooks.on_output_disconnect, output_env(name));
+            false
+        });
+    }
+}
+
+fn run(hook: &Option<Hook>, env: Vec<(String, String)>) {
+    if let Some(hook) = hook {
+        spawn_with_env(hook.command.clone(), env, None);
+    }
+}
+
+fn window_env(id: u64, window: &HookWindow) -> Vec<(String, String)> {
+    let mut env = vec![(String::from("NIRI_WINDOW_ID"), id.to_string())];
+    if let Some(title) = &window.title {
+        env.push((String::from("NIRI_WINDOW_TITLE"), title.clone()));
+    }
+    if let Some(app_id) = &window.app_id {
+        env.push((String::from("NIRI_WINDOW_APP_ID"), app_id.clone()));
+    }
+    if let Some(workspace_id) = window.workspace_id {
+        env.push((String::from("NIRI_WORKSPACE_ID"), workspace_id.to_string()));
+    }
+    if let Some(output) = &window.output {
+        env.push((String::from("NIRI_OUTPUT"), output.clone()));
+    }
+    env
+}
+
+fn output_env(name: &str) -> Vec<(String, String)> {
+    vec![(String::from("NIRI_OUTPUT"), name.to_owned())]
+}
@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::os::unix::net::{UnixListener, UnixStream};
@@ -14,16 +14,19 @@ use calloop::io::Async;
 use directories::BaseDirs;
 use futures_util::io::{AsyncReadExt, BufReader};
 use futures_util::{select_biased, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, FutureExt as _};
-use niri_config::OutputName;
+use niri_config::gestures::{GestureOverrides, GestureTrigger, Gestures};
+use niri_config::{GestureDirection, GestureKind, OutputName};
 use niri_ipc::state::{EventStreamState, EventStreamStatePart as _};
 use niri_ipc::{
-    Action, Event, KeyboardLayouts, OutputConfigChanged, Overview, Reply, Request, Response,
-    Timestamp, WindowLayout, Workspace,
+    Action, Event, GestureBindChange, InputDevice, InputDeviceCapability, KeyboardLayouts,
+    OutputConfigChanged, Overview, Reply, Request, Response, StateDump, Timestamp, WatchMessage,
+    WatchResource, WindowLayout, Workspace,
 };
 use smithay::desktop::layer_map_for_output;
 use smithay::input::pointer::{
     CursorIcon, CursorImageStatus, Focus, GrabStartData as PointerGrabStartData,
 };
+use smithay::output::Output;
 use smithay::reexports::calloop::generic::Generic;
 use smithay::reexports::calloop::{Interest, LoopHandle, Mode, PostAction};
 use smithay::reexports::rustix::fs::unlink;
@@ -33,6 +36,7 @@ use smithay::wayland::shell::wlr_layer::{KeyboardInteractivity, Layer};
 use crate::backend::IpcOutputMap;
 use crate::input::pick_window_grab::PickWindowGrab;
 use crate::layout::workspace::WorkspaceId;
+use crate::layout::LayoutElement as _;
 use crate::niri::State;
 use crate::utils::{version, with_toplevel_role};
 use crate::window::Mapped;
@@ -41,6 +45,12 @@ use crate::window::Mapped;
 // number in our buffer, we drop that event stream client.
 const EVENT_STREAM_BUFFER_SIZE: usize = 64;
 
+/// Number of distinct progress steps reported in gesture update events.
+///
+/// Limits how many events a gesture sends as the fingers move, so that it doesn't fill up the event
+/// stream buffers.
+const GESTURE_PROGRESS_STEPS: f64 = 20.;
+
 pub struct IpcServer {
     /// Path to the IPC socket.
     ///
@@ -48,6 +58,10 @@ pub struct IpcServer {
     pub socket_path: Option<PathBuf>,
     event_streams: Rc<RefCell<Vec<EventStreamSender>>>,
     event_stream_state: Rc<RefCell<EventStreamState>>,
+    /// Sequence number of the last sent event.
+    event_seq: Rc<Cell<u64>>,
+    /// Direction and progress step of the last gesture update event.
+    last_gesture_update: Cell<Option<(GestureDirection, u8)>>,
 }
 
 struct ClientCtx {
@@ -56,17 +70,23 @@ struct ClientCtx {
     ipc_outputs: Arc<Mutex<IpcOutputMap>>,
     event_streams: Rc<RefCell<Vec<EventStreamSender>>>,
     event_stream_state: Rc<RefCell<EventStreamState>>,
+    event_seq: Rc<Cell<u64>>,
 }
 
 struct EventStreamClient {
-    events: Receiver<Event>,
+    events: Receiver<(u64, Event)>,
     disconnect: Receiver<()>,
     write: Box<dyn AsyncWrite + Unpin>,
+    /// For watch clients, the snapshot to write before the events, which are then written as
+    /// diffs.
+    snapshot: Option<WatchMessage>,
 }
 
 struct EventStreamSender {
-    events: Sender<Event>,
+    events: Sender<(u64, Event)>,
     disconnect: Sender<()>,
+    /// For watch clients, the watched resources.
+    resources: Option<Vec<WatchResource>>,
 }
 
 impl IpcServer {
@@ -109,14 +129,27 @@ impl IpcServer {
             socket_path,
             event_streams: Rc::new(RefCell::new(Vec::new())),
             event_stream_state: Rc::new(RefCell::new(EventStreamState::default())),
+            event_seq: Rc::new(Cell::new(0)),
+            last_gesture_update: Cell::new(None),
         })
     }
 
     fn send_event(&self, event: Event) {
+        let seq = self.event_seq.get() + 1;
+        self.event_seq.set(seq);
+
+        let resource = WatchResource::for_event(&event);
+
         let mut streams = self.event_streams.borrow_mut();
         let mut to_remove = Vec::new();
         for (idx, stream) in streams.iter_mut().enumerate() {
-            match stream.events.try_send(event.clone()) {
+            if let Some(resources) = &stream.resources {
+                if !resource.is_some_and(|r| resources.contains(&r)) {
+                    continue;
+                }
+            }
+
+            match stream.events.try_send((seq, event.clone())) {
                 Ok(()) => (),
                 Err(TrySendError::Closed(_)) => to_remove.push(idx),
                 Err(TrySendError::Full(_)) => {
@@ -172,6 +205,7 @@ fn on_new_ipc_client(state: &mut State, stream: UnixStream) {
         ipc_outputs: state.backend.ipc_outputs(),
         event_streams: ipc_server.event_streams.clone(),
         event_stream_state: ipc_server.event_stream_state.clone(),
+        event_seq: ipc_server.event_seq.clone(),
     };
 
     let future = async move {
@@ -204,9 +238,20 @@ async fn handle_client(ctx: ClientCtx, stream: Async<'static, UnixStream>) -> an
 
         let request = serde_json::from_slice(&buf)
             .context("error parsing request")
-            .map_err(|err| err.to_string());
+            // Include the serde error, which names the unknown request or action.
+            .map_err(|err| format!("{err:#}"));
         let requested_error = matches!(request, Ok(Request::ReturnError));
-        let requested_event_stream = matches!(request, Ok(Request::EventStream));
+        // Some(None) for the event stream, and Some(Some(resources)) for watching resources.
+        let requested_event_stream = match &request {
+            Ok(Request::EventStream) => Some(None),
+            Ok(Request::Watch { resources }) if resources.is_empty() => Some(Some(vec![
+                WatchResource::Windows,
+                WatchResource::Workspaces,
+                WatchResource::Outputs,
+            ])),
+            Ok(Request::Watch { resources }) => Some(Some(resources.clone())),
+            _ => None,
+        };
 
         let reply = match request {
             Ok(request) => process(&ctx, request).await,
@@ -224,15 +269,35 @@ async fn handle_client(ctx: ClientCtx, stream: Async<'static, UnixStream>) -> an
         buf.push(b'\n');
         write.write_all(&buf).await.context("error writing reply")?;
 
-        if requested_event_stream {
+        if let Some(resources) = requested_event_stream {
             let (events_tx, events_rx) = async_channel::bounded(EVENT_STREAM_BUFFER_SIZE);
             let (disconnect_tx, disconnect_rx) = async_channel::bounded(1);
 
+            // Watch clients get the initial state as a single snapshot.
+            let snapshot = resources.as_ref().map(|resources| {
+                let state = ctx.event_stream_state.borrow();
+                let mut events = Vec::new();
+                if resources.contains(&WatchResource::Workspaces) {
+                    events.extend(state.workspaces.replicate());
+                }
+                if resources.contains(&WatchResource::Windows) {
+                    events.extend(state.windows.replicate());
+                }
+                if resources.contains(&WatchResource::Outputs) {
+                    events.extend(state.outputs.replicate());
+                }
+                WatchMessage::Snapshot {
+                    seq: ctx.event_seq.get(),
+                    events,
+                }
+            });
+
             // Spawn a task for the client.
             let client = EventStreamClient {
                 events: events_rx,
                 disconnect: disconnect_rx,
                 write: Box::new(write) as _,
+                snapshot,
             };
             let future = async move {
                 if let Err(err) = handle_event_stream_client(client).await {
@@ -244,11 +309,12 @@ async fn handle_client(ctx: ClientCtx, stream: Async<'static, UnixStream>) -> an
             }
 
             // Send the initial state.
-            {
+            if resources.is_none() {
                 let state = ctx.event_stream_state.borrow();
+                let seq = ctx.event_seq.get();
                 for event in state.replicate() {
                     events_tx
-                        .try_send(event)
+                        .try_send((seq, event))
                         .expect("initial event burst had more events than buffer size");
                 }
             }
@@ -259,6 +325,7 @@ async fn handle_client(ctx: ClientCtx, stream: Async<'static, UnixStream>) -> an
                 let sender = EventStreamSender {
                     events: events_tx,
                     disconnect: disconnect_tx,
+                    resources,
                 };
                 streams.push(sender);
             }
@@ -328,6 +395,25 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
             let layers = result.map_err(|_| String::from("error getting layers info"))?;
             Response::Layers(layers)
         }
+        Request::Marks => {
+            let (tx, rx) = async_channel::bounded(1);
+            ctx.event_loop.insert_idle(move |state| {
+                let mut marks = Vec::new();
+                for (_, win) in state.niri.layout.windows() {
+                    for mark in win.marks() {
+                        marks.push(niri_ipc::WindowMark {
+                            mark: *mark,
+                            window_id: win.id().get(),
+                        });
+                    }
+                }
+
+                let _ = tx.send_blocking(marks);
+            });
+            let result = rx.recv().await;
+            let marks = result.map_err(|_| String::from("error getting marks"))?;
+            Response::Marks(marks)
+        }
         Request::KeyboardLayouts => {
             let state = ctx.event_stream_state.borrow();
             let layout = state.keyboard_layouts.keyboard_layouts.clone();
@@ -388,14 +474,45 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
                 // Make sure some logic like workspace clean-up has a chance to run before doing
                 // actions.
                 state.niri.advance_animations();
-                state.do_action(action, false);
-                let _ = tx.send_blocking(());
+                let res = validate_action_in_state(state, &action);
+                if res.is_ok() {
+                    state.do_action(action, false);
+                }
+                let _ = tx.send_blocking(res);
             });
 
             // Wait until the action has been processed before returning. This is important for a
             // few actions, for instance for DoScreenTransition this wait ensures that the screen
             // contents were sampled into the texture.
-            let _ = rx.recv().await;
+            if let Ok(res) = rx.recv().await {
+                res?;
+            }
+            Response::Handled
+        }
+        Request::Actions(actions) => {
+            for action in &actions {
+                validate_action(action)?;
+            }
+
+            let (tx, rx) = async_channel::bounded(1);
+
+            let actions: Vec<_> = actions.into_iter().map(niri_config::Action::from).collect();
+            ctx.event_loop.insert_idle(move |state| {
+                state.niri.advance_animations();
+                let res = actions
+                    .iter()
+                    .try_for_each(|action| validate_action_in_state(state, action));
+                if res.is_ok() {
+                    for action in actions {
+                        state.do_action(action, false);
+                    }
+                }
+                let _ = tx.send_blocking(res);
+            });
+
+            if let Ok(res) = rx.recv().await {
+                res?;
+            }
             Response::Handled
         }
         Request::Output { output, action } => {
@@ -445,19 +562,136 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
             Response::FocusedOutput(output)
         }
         Request::EventStream => Response::Handled,
+        Request::Watch { .. } => Response::Handled,
         Request::OverviewState => {
             let state = ctx.event_stream_state.borrow();
             let is_open = state.overview.is_open;
             Response::OverviewState(Overview { is_open })
         }
+        Request::InputLatency => {
+            let (tx, rx) = async_channel::bounded(1);
+            ctx.event_loop.insert_idle(move |state| {
+                let enabled = state.niri.config.borrow().debug.trace_input_latency;
+                let latency = state.niri.input_latency.to_ipc(enabled);
+                let _ = tx.send_blocking(latency);
+            });
+            let result = rx.recv().await;
+            let latency = result.map_err(|_| String::from("error getting input latency"))?;
+            Response::InputLatency(latency)
+        }
+        Request::GestureBinds => {
+            let (tx, rx) = async_channel::bounded(1);
+            ctx.event_loop.insert_idle(move |state| {
+                let config = state.niri.config.borrow();
+                let binds = make_ipc_gesture_binds(&config.gestures, &state.niri.gesture_overrides);
+                let _ = tx.send_blocking(binds);
+            });
+            let result = rx.recv().await;
+            let binds = result.map_err(|_| String::from("error getting gesture binds"))?;
+            Response::GestureBinds(binds)
+        }
+        Request::GestureBind { trigger, change } => {
+            let trigger = GestureTrigger::try_from(trigger).map_err(|err| err.to_string())?;
+            if let GestureBindChange::Rebind { action } = &change {
+                validate_action(action)?;
+            }
+
+            ctx.event_loop.insert_idle(move |state| {
+                let overrides = &mut state.niri.gesture_overrides;
+                match change {
+                    GestureBindChange::Disable => overrides.disable(trigger),
+                    GestureBindChange::Enable => overrides.enable(trigger),
+                    GestureBindChange::Rebind { action } => {
+                        overrides.rebind(trigger, niri_config::Action::from(action))
+                    }
+                    GestureBindChange::Reset => overrides.reset(trigger),
+                }
+            });
+
+            Response::Handled
+        }
+        Request::DumpState => {
+            let (tx, rx) = async_channel::bounded(1);
+            ctx.event_loop.insert_idle(move |state| {
+                let niri = &state.niri;
+                let devices = niri.devices.iter().map(make_ipc_input_device).collect();
+                let gestures = format!(
+                    "touchpad: {:#?}\nsequence: {:#?}\ntouchscreen: {:#?}",
+                    niri.touchpad_gesture, niri.gesture_sequence, niri.touchscreen_gesture,
+                );
+                let _ = tx.send_blocking((devices, gestures, niri.config_error.clone()));
+            });
+            let result = rx.recv().await;
+            let (input_devices, gestures, config_error) =
+                result.map_err(|_| String::from("error getting the compositor state"))?;
+
+            let ipc_outputs = ctx.ipc_outputs.lock().unwrap();
+            let outputs = ipc_outputs.values().cloned().collect();
+            drop(ipc_outputs);
+
+            let state = ctx.event_stream_state.borrow();
+            let workspaces = state.workspaces.workspaces.values().cloned().collect();
+            let windows = state
+                .windows
+                .windows
+                .values()
+                .cloned()
+                .map(|mut window| {
+                    window.title = None;
+                    window
+                })
+                .collect();
+
+            Response::StateDump(StateDump {
+                version: version(),
+                config_error,
+                outputs,
+                workspaces,
+                windows,
+                input_devices,
+                gestures,
+            })
+        }
+        Request::ResetGestureBinds => {
+            ctx.event_loop.insert_idle(move |state| {
+                state.niri.gesture_overrides = Default::default();
+            });
+
+            Response::Handled
+        }
     };
 
     Ok(response)
 }
 
-fn validate_action(action: &Action) -> Result<(), String> {
+fn make_ipc_input_device(device: &input::Device) -> InputDevice {
+    use input::DeviceCapability as Libinput;
+    use InputDeviceCapability as Ipc;
+
+    let capabilities = [
+        (Libinput::Keyboard, Ipc::Keyboard),
+        (Libinput::Pointer, Ipc::Pointer),
+        (Libinput::Touch, Ipc::Touch),
+        (Libinput::TabletTool, Ipc::TabletTool),
+        (Libinput::TabletPad, Ipc::TabletPad),
+        (Libinput::Gesture, Ipc::Gesture),
+        (Libinput::Switch, Ipc::Switch),
+    ];
+
+    InputDevice {
+        name: device.name().to_owned(),
+        capabilities: capabilities
+            .into_iter()
+            .filter(|(cap, _)| device.has_capability(*cap))
+            .map(|(_, cap)| cap)
+            .collect(),
+    }
+}
+
+pub fn validate_action(action: &Action) -> Result<(), String> {
     if let Action::Screenshot { path, .. }
     | Action::ScreenshotScreen { path, .. }
+    | Action::ScreenshotRegion { path, .. }
     | Action::ScreenshotWindow { path, .. } = action
     {
         if let Some(path) = path {
@@ -469,6 +703,50 @@ fn validate_action(action: &Action) -> Result<(), String> {
         }
     }
 
+    if let Action::ScreenshotRegion { region, .. } = action {
+        i32::try_from(region.width)
+            .map_err(|_| format!("region width is too large: {}", region.width))?;
+        i32::try_from(region.height)
+            .map_err(|_| format!("region height is too large: {}", region.height))?;
+    }
+
+    if let Action::SendKeys { keys, .. } = action {
+        for key in keys {
+            // Keys are sent to clients, so they must be keyboard keys rather than mouse buttons.
+            match key.parse::<niri_config::Key>() {
+                Ok(niri_config::Key {
+                    trigger: niri_config::Trigger::Keysym(_),
+                    ..
+                }) => (),
+                Ok(_) => return Err(format!("key must be a keyboard key: {key}")),
+                Err(err) => return Err(format!("invalid key {key}: {err}")),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates the parts of an action that depend on the current compositor state.
+fn validate_action_in_state(state: &mut State, action: &niri_config::Action) -> Result<(), String> {
+    match action {
+        niri_config::Action::SendKeys(keys) => {
+            state.resolve_keys(keys)?;
+        }
+        niri_config::Action::SendKeysById { id, keys } => {
+            let exists = state
+                .niri
+                .layout
+                .windows()
+                .any(|(_, m)| m.id().get() == *id);
+            if !exists {
+                return Err(format!("no window with id {id}"));
+            }
+            state.resolve_keys(keys)?;
+        }
+        _ => (),
+    }
+
     Ok(())
 }
 
@@ -477,10 +755,29 @@ async fn handle_event_stream_client(client: EventStreamClient) -> anyhow::Result
         events,
         disconnect,
         mut write,
+        snapshot,
     } = client;
 
-    while let Ok(event) = events.recv().await {
-        let mut buf = serde_json::to_vec(&event).context("error formatting event")?;
+    let watch = snapshot.is_some();
+    if let Some(snapshot) = snapshot {
+        let mut buf = serde_json::to_vec(&snapshot).context("error formatting snapshot")?;
+        buf.push(b'\n');
+
+        match write.write_all(&buf).await {
+            Ok(()) => (),
+            // Normal client disconnection.
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            res @ Err(_) => res.context("error writing snapshot")?,
+        }
+    }
+
+    while let Ok((seq, event)) = events.recv().await {
+        let mut buf = if watch {
+            serde_json::to_vec(&WatchMessage::Diff { seq, event })
+        } else {
+            serde_json::to_vec(&event)
+        }
+        .context("error formatting event")?;
         buf.push(b'\n');
 
         let res = select_biased! {
@@ -501,6 +798,7 @@ async fn handle_event_stream_client(client: EventStreamClient) -> anyhow::Result
 
 fn make_ipc_window(
     mapped: &Mapped,
+    output: Option<&Output>,
     workspace_id: Option<WorkspaceId>,
     layout: WindowLayout,
 ) -> niri_ipc::Window {
@@ -510,14 +808,67 @@ fn make_ipc_window(
         app_id: role.app_id.clone(),
         pid: mapped.credentials().map(|c| c.pid),
         workspace_id: workspace_id.map(|id| id.get()),
+        output: output.map(|output| output.name()),
         is_focused: mapped.is_focused(),
         is_floating: mapped.is_floating(),
+        is_fullscreen: mapped.sizing_mode().is_fullscreen(),
         is_urgent: mapped.is_urgent(),
+        is_minimized: false,
         layout,
         focus_timestamp: mapped.get_focus_timestamp().map(Timestamp::from),
     })
 }
 
+fn make_ipc_minimized_window(mapped: &Mapped) -> niri_ipc::Window {
+    let size = mapped.size();
+    let layout = WindowLayout {
+        pos_in_scrolling_layout: None,
+        tile_size: size.to_f64().into(),
+        window_size: size.into(),
+        tile_pos_in_workspace_view: None,
+        window_offset_in_tile: (0., 0.),
+    };
+
+    niri_ipc::Window {
+        is_focused: false,
+        is_minimized: true,
+        ..make_ipc_window(mapped, None, None, layout)
+    }
+}
+
+/// Lists the global gesture binds with the changes made over IPC.
+fn make_ipc_gesture_binds(
+    gestures: &Gestures,
+    overrides: &GestureOverrides,
+) -> Vec<niri_ipc::GestureBind> {
+    let mut triggers: Vec<GestureTrigger> = gestures.binds.0.iter().map(|b| b.trigger).collect();
+    let overridden = overrides.binds.0.iter().map(|b| b.trigger);
+    for trigger in overridden.chain(overrides.disabled.iter().copied()) {
+        if !triggers.contains(&trigger) {
+            triggers.push(trigger);
+        }
+    }
+
+    triggers
+        .into_iter()
+        .map(|trigger| {
+            let action = if overrides.disabled.contains(&trigger) {
+                None
+            } else {
+                let mut binds = overrides.binds.0.iter().chain(&gestures.binds.0);
+                let bind = binds.find(|bind| bind.trigger == trigger);
+                bind.map(|bind| format!("{:?}", bind.action))
+            };
+
+            niri_ipc::GestureBind {
+                trigger: trigger.into(),
+                action,
+                is_overridden: overrides.is_overridden(trigger),
+            }
+        })
+        .collect()
+}
+
 impl State {
     pub fn ipc_keyboard_layouts_changed(&mut self) {
         let keyboard = self.niri.seat.get_keyboard().unwrap();
@@ -690,7 +1041,7 @@ impl State {
         // Check for window changes.
         let mut seen = HashSet::new();
         let mut focused_id = None;
-        layout.with_windows(|mapped, _, ws_id, window_layout| {
+        layout.with_windows(|mapped, output, ws_id, window_layout| {
             let id = mapped.id().get();
             seen.insert(id);
 
@@ -699,21 +1050,25 @@ impl State {
             }
 
             let Some(ipc_win) = state.windows.get(&id) else {
-                let window = make_ipc_window(mapped, ws_id, window_layout);
+                let window = make_ipc_window(mapped, output, ws_id, window_layout);
                 events.push(Event::WindowOpenedOrChanged { window });
                 return;
             };
 
             let workspace_id = ws_id.map(|id| id.get());
-            let mut changed =
-                ipc_win.workspace_id != workspace_id || ipc_win.is_floating != mapped.is_floating();
+            let output_name = output.map(|output| output.name());
+            let mut changed = ipc_win.is_minimized
+                || ipc_win.workspace_id != workspace_id
+                || ipc_win.output != output_name
+                || ipc_win.is_floating != mapped.is_floating()
+                || ipc_win.is_fullscreen != mapped.sizing_mode().is_fullscreen();
 
             changed |= with_toplevel_role(mapped.toplevel(), |role| {
                 ipc_win.title != role.title || ipc_win.app_id != role.app_id
             });
 
             if changed {
-                let window = make_ipc_window(mapped, ws_id, window_layout);
+                let window = make_ipc_window(mapped, output, ws_id, window_layout);
                 events.push(Event::WindowOpenedOrChanged { window });
                 return;
             }
@@ -740,6 +1095,24 @@ impl State {
             }
         });
 
+        // Minimized windows stay open, so list them rather than report them closed.
+        for minimized in &self.niri.minimized_windows {
+            let mapped = &minimized.mapped;
+            let id = mapped.id().get();
+            seen.insert(id);
+
+            let changed = state.windows.get(&id).is_none_or(|ipc_win| {
+                !ipc_win.is_minimized
+                    || with_toplevel_role(mapped.toplevel(), |role| {
+                        ipc_win.title != role.title || ipc_win.app_id != role.app_id
+                    })
+            });
+            if changed {
+                let window = make_ipc_minimized_window(mapped);
+                events.push(Event::WindowOpenedOrChanged { window });
+            }
+        }
+
         // It might make sense to push layout changes after closed windows (since windows about to
         // be closed will occupy the same column/tile positions as the window that moved into this
         // vacated space), but also we are already pushing some layout changes in
@@ -775,6 +1148,32 @@ impl State {
         }
     }
 
+    pub fn ipc_refresh_outputs(&mut self) {
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+
+        let mut state = server.event_stream_state.borrow_mut();
+        let state = &mut state.outputs;
+
+        let ipc_outputs = self.backend.ipc_outputs();
+        let ipc_outputs = ipc_outputs.lock().unwrap();
+
+        // Check if the outputs changed as seen by the event stream.
+        let unchanged = state.outputs.len() == ipc_outputs.len()
+            && ipc_outputs
+                .values()
+                .all(|output| state.outputs.get(&output.name) == Some(output));
+        if unchanged {
+            return;
+        }
+
+        let outputs = ipc_outputs.values().cloned().collect();
+        let event = Event::OutputsChanged { outputs };
+        state.apply(event.clone());
+        server.send_event(event);
+    }
+
     pub fn ipc_refresh_overview(&mut self) {
         let Some(server) = &self.niri.ipc_server else {
             return;
@@ -793,6 +1192,24 @@ impl State {
         server.send_event(event);
     }
 
+    pub fn ipc_refresh_tablet_mode(&mut self) {
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+
+        let mut state = server.event_stream_state.borrow_mut();
+        let state = &mut state.tablet_mode;
+        let is_tablet_mode = self.niri.is_tablet_mode;
+
+        if state.is_tablet_mode == is_tablet_mode {
+            return;
+        }
+
+        let event = Event::TabletModeChanged { is_tablet_mode };
+        state.apply(event.clone());
+        server.send_event(event);
+    }
+
     pub fn ipc_config_loaded(&mut self, failed: bool) {
         let Some(server) = &self.niri.ipc_server else {
             return;
@@ -814,4 +1231,46 @@ impl State {
         state.apply(event.clone());
         server.send_event(event);
     }
+
+    pub fn ipc_gesture_begin(&mut self, kind: GestureKind, fingers: u8) {
+        if let Some(server) = &self.niri.ipc_server {
+            server.last_gesture_update.set(None);
+        }
+
+        self.ipc_gesture_event(Event::GestureBegin {
+            kind: kind.into(),
+            fingers,
+        });
+    }
+
+    pub fn ipc_gesture_update(&mut self, direction: GestureDirection, progress: f64) {
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+
+        // Only send an update when the progress moves to another step.
+        let step = (progress.clamp(0., 1.) * GESTURE_PROGRESS_STEPS).round() as u8;
+        if server.last_gesture_update.replace(Some((direction, step))) == Some((direction, step)) {
+            return;
+        }
+
+        self.ipc_gesture_event(Event::GestureUpdate {
+            direction: direction.into(),
+            progress: f64::from(step) / GESTURE_PROGRESS_STEPS,
+        });
+    }
+
+    pub fn ipc_gesture_end(&mut self, cancelled: bool) {
+        self.ipc_gesture_event(Event::GestureEnd { cancelled });
+    }
+
+    fn ipc_gesture_event(&mut self, event: Event) {
+        let Some(server) = &self.niri.ipc_server else {
+            return;
+        };
+        let mut state = server.event_stream_state.borrow_mut();
+
+        state.apply(event.clone());
+        server.send_event(event);
+    }
 }
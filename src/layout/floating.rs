@@ -453,6 +453,7 @@ impl<W: LayoutElement> FloatingSpace<W> {
         self.tiles.insert(idx, tile);
 
         self.bring_up_descendants_of(idx);
+        self.restack_always_on_top();
     }
 
     pub fn add_tile_above(&mut self, above: &W::Id, mut tile: Tile<W>, activate: bool) {
@@ -576,10 +577,36 @@ impl<W: LayoutElement> FloatingSpace<W> {
         self.raise_window(idx, 0);
         self.active_window_id = Some(id.clone());
         self.bring_up_descendants_of(0);
+        self.restack_always_on_top();
 
         true
     }
 
+    /// Moves the always-on-top tiles and their children above the other tiles.
+    ///
+    /// Preserves the stacking order within both groups.
+    pub fn restack_always_on_top(&mut self) {
+        let is_on_top = |tile: &Tile<W>| {
+            tile.is_always_on_top
+                || self.tiles.iter().any(|other| {
+                    other.is_always_on_top && tile.window().is_child_of(other.window())
+                })
+        };
+        let on_top: Vec<bool> = self.tiles.iter().map(is_on_top).collect();
+
+        // Nothing to do if the order is already right.
+        if on_top.windows(2).all(|w| w[0] || !w[1]) {
+            return;
+        }
+
+        let tiles = std::mem::take(&mut self.tiles);
+        let data = std::mem::take(&mut self.data);
+        let mut items: Vec<_> = zip(zip(tiles, data), on_top).collect();
+        // The sort is stable.
+        items.sort_by_key(|(_, on_top)| !on_top);
+        (self.tiles, self.data) = items.into_iter().map(|(item, _)| item).unzip();
+    }
+
     fn raise_window(&mut self, from_idx: usize, to_idx: usize) {
         assert!(to_idx <= from_idx);
 
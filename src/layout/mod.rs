@@ -457,6 +457,12 @@ pub struct RemovedTile<W: LayoutElement> {
     is_floating: bool,
 }
 
+impl<W: LayoutElement> RemovedTile<W> {
+    pub fn into_window(self) -> W {
+        self.tile.into_window()
+    }
+}
+
 /// Whether to activate a newly added window.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum ActivateWindow {
@@ -609,7 +615,7 @@ impl Options {
         Self {
             layout: config.layout.clone(),
             animations: config.animations.clone(),
-            gestures: config.gestures,
+            gestures: config.gestures.clone(),
             overview: config.overview,
             disable_resize_throttling: config.debug.disable_resize_throttling,
             disable_transactions: config.debug.disable_transactions,
@@ -1000,12 +1006,12 @@ impl<W: LayoutElement> Layout<W> {
                             ));
                         }
 
-                        (0, WorkspaceAddWindowTarget::Auto)
+                        (0, WorkspaceAddWindowTarget::NewWindow)
                     }
                     AddWindowTarget::Output(_) => panic!(),
                     AddWindowTarget::Workspace(ws_id) => {
                         let ws_idx = workspaces.iter().position(|ws| ws.id() == ws_id).unwrap();
-                        (ws_idx, WorkspaceAddWindowTarget::Auto)
+                        (ws_idx, WorkspaceAddWindowTarget::NewWindow)
                     }
                     AddWindowTarget::NextTo(next_to) => {
                         if self
@@ -2189,6 +2195,13 @@ impl<W: LayoutElement> Layout<W> {
         workspace.toggle_column_tabbed_display();
     }
 
+    pub fn toggle_column_accordion_display(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.toggle_column_accordion_display();
+    }
+
     pub fn set_column_display(&mut self, display: ColumnDisplay) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
@@ -2298,6 +2311,23 @@ impl<W: LayoutElement> Layout<W> {
         mon.resize_edges_under(pos_within_output)
     }
 
+    /// Returns the edges to resize when the position is on a window border.
+    pub fn border_resize_edges_under(
+        &self,
+        output: &Output,
+        pos_within_output: Point<f64, Logical>,
+    ) -> Option<ResizeEdge> {
+        if self
+            .interactive_moved_window_under(output, pos_within_output)
+            .is_some()
+        {
+            return None;
+        }
+
+        let mon = self.monitor_for_output(output)?;
+        mon.border_resize_edges_under(pos_within_output)
+    }
+
     pub fn workspace_under(
         &self,
         extended_bounds: bool,
@@ -3075,6 +3105,20 @@ impl<W: LayoutElement> Layout<W> {
         workspace.expand_column_to_available_width();
     }
 
+    pub fn distribute_column_widths(&mut self, by_window_count: bool) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.distribute_column_widths(by_window_count);
+    }
+
+    pub fn toggle_monocle(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.toggle_monocle();
+    }
+
     pub fn toggle_window_floating(&mut self, window: Option<&W::Id>) {
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
             if window.is_none() || window == Some(move_.tile.window().id()) {
@@ -3138,6 +3182,60 @@ impl<W: LayoutElement> Layout<W> {
         workspace.toggle_window_floating(window);
     }
 
+    pub fn toggle_window_pinned(&mut self, window: Option<&W::Id>) {
+        if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
+            if window.is_none() || window == Some(move_.tile.window().id()) {
+                move_.tile.is_pinned = !move_.tile.is_pinned;
+                if move_.tile.is_pinned && !move_.is_floating {
+                    self.toggle_window_floating(window);
+                }
+                return;
+            }
+        }
+
+        let workspace = if let Some(window) = window {
+            Some(
+                self.workspaces_mut()
+                    .find(|ws| ws.has_window(window))
+                    .unwrap(),
+            )
+        } else {
+            self.active_workspace_mut()
+        };
+
+        let Some(workspace) = workspace else {
+            return;
+        };
+        workspace.toggle_window_pinned(window);
+    }
+
+    pub fn toggle_window_always_on_top(&mut self, window: Option<&W::Id>) {
+        if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
+            if window.is_none() || window == Some(move_.tile.window().id()) {
+                move_.tile.is_always_on_top = !move_.tile.is_always_on_top;
+                if move_.tile.is_always_on_top && !move_.is_floating {
+                    self.toggle_window_floating(window);
+                }
+                return;
+            }
+        }
+
+        let workspace = if let Some(window) = window {
+            Some(
+                self.workspaces_mut()
+                    .find(|ws| ws.has_window(window))
+                    .unwrap(),
+            )
+        } else {
+            self.active_workspace_mut()
+        };
+
+        let Some(workspace) = workspace else {
+            return;
+        };
+        workspace.toggle_window_always_on_top(window);
+    }
+
     pub fn set_window_floating(&mut self, window: Option<&W::Id>, floating: bool) {
         if let Some(InteractiveMoveState::Moving(move_)) = &mut self.interactive_move {
             if window.is_none() || window == Some(move_.tile.window().id()) {
@@ -438,6 +438,11 @@ impl<W: LayoutElement> Monitor<W> {
         idx: usize,
         config: Option<niri_config::Animation>,
     ) {
+        // Bring the pinned windows along first, since that can add workspaces.
+        let id = self.workspaces[idx].id();
+        self.move_pinned_windows(self.active_workspace_idx, id);
+        let idx = self.workspaces.iter().position(|ws| ws.id() == id).unwrap();
+
         // FIXME: also compute and use current velocity.
         let current_idx = self.workspace_render_idx();
 
@@ -522,7 +527,14 @@ impl<W: LayoutElement> Monitor<W> {
         // monitor. So we can use any workspace, not necessarily the exact target workspace.
         let tile = self.workspaces[0].make_tile(window);
 
-        self.add_tile(
+        let (workspace_idx, target) = self.resolve_add_window_target(target);
+        let target = match target {
+            WorkspaceAddWindowTarget::Auto => WorkspaceAddWindowTarget::NewWindow,
+            target => target,
+        };
+
+        self.add_tile_to_workspace(
+            workspace_idx,
             tile,
             target,
             activate,
@@ -568,8 +580,32 @@ impl<W: LayoutElement> Monitor<W> {
         is_full_width: bool,
         is_floating: bool,
     ) {
-        let (mut workspace_idx, target) = self.resolve_add_window_target(target);
+        let (workspace_idx, target) = self.resolve_add_window_target(target);
+
+        self.add_tile_to_workspace(
+            workspace_idx,
+            tile,
+            target,
+            activate,
+            allow_to_activate_workspace,
+            width,
+            is_full_width,
+            is_floating,
+        );
+    }
 
+    #[allow(clippy::too_many_arguments)]
+    fn add_tile_to_workspace(
+        &mut self,
+        mut workspace_idx: usize,
+        tile: Tile<W>,
+        target: WorkspaceAddWindowTarget<W>,
+        activate: ActivateWindow,
+        allow_to_activate_workspace: bool,
+        width: ColumnWidth,
+        is_full_width: bool,
+        is_floating: bool,
+    ) {
         let workspace = &mut self.workspaces[workspace_idx];
 
         workspace.add_tile(tile, target, activate, width, is_full_width, is_floating);
@@ -594,6 +630,48 @@ impl<W: LayoutElement> Monitor<W> {
         }
     }
 
+    /// Moves the pinned windows from the workspace to the workspace with the given id.
+    fn move_pinned_windows(&mut self, from_idx: usize, to_id: WorkspaceId) {
+        let from = &self.workspaces[from_idx];
+        if from.id() == to_id {
+            return;
+        }
+
+        let pinned: Vec<_> = from.pinned_windows().cloned().collect();
+        if pinned.is_empty() {
+            return;
+        }
+
+        let from_id = from.id();
+        let active = from.active_window().map(|win| win.id().clone());
+
+        for id in pinned {
+            // Adding the tiles can insert workspaces, so look the source up every time.
+            let from = self.workspaces.iter_mut().find(|ws| ws.id() == from_id);
+            let removed = from.unwrap().remove_tile(&id, Transaction::new());
+
+            // Keep the focus on a pinned window.
+            let activate = if active.as_ref() == Some(&id) {
+                ActivateWindow::Yes
+            } else {
+                ActivateWindow::No
+            };
+
+            self.add_tile(
+                removed.tile,
+                MonitorAddWindowTarget::Workspace {
+                    id: to_id,
+                    column_idx: None,
+                },
+                activate,
+                false,
+                removed.width,
+                removed.is_full_width,
+                removed.is_floating,
+            );
+        }
+    }
+
     pub fn add_tile_to_column(
         &mut self,
         workspace_idx: usize,
@@ -1576,6 +1654,18 @@ impl<W: LayoutElement> Monitor<W> {
         ws.resize_edges_under(pos_within_output - geo.loc)
     }
 
+    pub fn border_resize_edges_under(
+        &self,
+        pos_within_output: Point<f64, Logical>,
+    ) -> Option<ResizeEdge> {
+        if self.overview_progress.is_some() {
+            return None;
+        }
+
+        let (ws, geo) = self.workspace_under(pos_within_output)?;
+        ws.border_resize_edges_under(pos_within_output - geo.loc)
+    }
+
     pub(super) fn insert_position(
         &self,
         pos_within_output: Point<f64, Logical>,
@@ -2002,6 +2092,7 @@ impl<W: LayoutElement> Monitor<W> {
             self.previous_workspace_id = Some(self.workspaces[self.active_workspace_idx].id());
         }
 
+        let prev_active_idx = self.active_workspace_idx;
         self.active_workspace_idx = new_idx;
         self.workspace_switch = Some(WorkspaceSwitch::Animation(Animation::new(
             self.clock.clone(),
@@ -2011,6 +2102,9 @@ impl<W: LayoutElement> Monitor<W> {
             self.options.animations.workspace_switch.0,
         )));
 
+        let id = self.workspaces[new_idx].id();
+        self.move_pinned_windows(prev_active_idx, id);
+
         true
     }
 
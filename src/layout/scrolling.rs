@@ -4,7 +4,9 @@ use std::rc::Rc;
 use std::time::Duration;
 
 use niri_config::utils::MergeWith as _;
-use niri_config::{CenterFocusedColumn, PresetSize, Struts};
+use niri_config::{
+    CenterFocusedColumn, FloatOrInt, NewWindowPlacement, OuterGaps, PresetSize, Struts,
+};
 use niri_ipc::{ColumnDisplay, SizeChange, WindowLayout};
 use ordered_float::NotNan;
 use smithay::backend::renderer::gles::GlesRenderer;
@@ -29,6 +31,9 @@ use crate::window::ResolvedWindowRules;
 /// Amount of touchpad movement to scroll the view for the width of one working area.
 const VIEW_GESTURE_WORKING_AREA_MOVEMENT: f64 = 1200.;
 
+/// Window height of the inactive windows in an accordion column, roughly enough for a title bar.
+const ACCORDION_COLLAPSED_WINDOW_HEIGHT: f64 = 48.;
+
 /// A scrollable-tiling space for windows.
 #[derive(Debug)]
 pub struct ScrollingSpace<W: LayoutElement> {
@@ -66,6 +71,9 @@ pub struct ScrollingSpace<W: LayoutElement> {
     /// View offset to restore after unfullscreening or unmaximizing.
     view_offset_to_restore: Option<f64>,
 
+    /// Whether every column takes the full width, showing one column at a time.
+    is_monocle: bool,
+
     /// Windows in the closing animation.
     closing_windows: Vec<ClosingWindow>,
 
@@ -89,8 +97,11 @@ pub struct ScrollingSpace<W: LayoutElement> {
     /// Clock for driving animations.
     clock: Clock,
 
-    /// Configurable properties of the layout.
+    /// Configurable properties of the layout, with smart gaps applied.
     options: Rc<Options>,
+
+    /// Configurable properties of the layout, as passed to the space.
+    base_options: Rc<Options>,
 }
 
 niri_render_elements! {
@@ -168,6 +179,11 @@ pub struct Column<W: LayoutElement> {
     /// Whether this column is full-width.
     is_full_width: bool,
 
+    /// Whether this column takes the full width because of the monocle mode.
+    ///
+    /// Unlike `is_full_width`, this doesn't go with the column to other workspaces.
+    is_monocle: bool,
+
     /// Whether this column is going to be fullscreen.
     ///
     /// This is the compositor-side fullscreen state, so it changes immediately upon
@@ -289,7 +305,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         clock: Clock,
         options: Rc<Options>,
     ) -> Self {
-        let working_area = compute_working_area(parent_area, scale, options.layout.struts);
+        let working_area = compute_working_area(parent_area, scale, outer_struts(&options.layout));
 
         Self {
             columns: Vec::new(),
@@ -299,13 +315,15 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             view_offset: ViewOffset::Static(0.),
             activate_prev_column_on_removal: None,
             view_offset_to_restore: None,
+            is_monocle: false,
             closing_windows: Vec::new(),
             view_size,
             working_area,
             parent_area,
             scale,
             clock,
-            options,
+            options: options.clone(),
+            base_options: options,
         }
     }
 
@@ -316,7 +334,9 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         scale: f64,
         options: Rc<Options>,
     ) {
-        let working_area = compute_working_area(parent_area, scale, options.layout.struts);
+        let base_options = options;
+        let options = smart_gaps_options(&base_options, self.columns.len());
+        let working_area = compute_working_area(parent_area, scale, outer_struts(&options.layout));
 
         for (column, data) in zip(&mut self.columns, &mut self.data) {
             column.update_config(view_size, working_area, parent_area, scale, options.clone());
@@ -328,6 +348,9 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.parent_area = parent_area;
         self.scale = scale;
         self.options = options;
+        self.base_options = base_options;
+
+        self.auto_balance_columns();
 
         // Apply always-center and such right away.
         if !self.columns.is_empty() && !self.view_offset.is_gesture() {
@@ -429,6 +452,13 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         Some(col.tiles[col.active_tile_idx].window())
     }
 
+    /// Returns the active window of the column to the left of the window's column.
+    pub fn window_left_of(&self, window: &W::Id) -> Option<&W> {
+        let col_idx = self.columns.iter().position(|col| col.contains(window))?;
+        let col = &self.columns[col_idx.checked_sub(1)?];
+        Some(col.tiles[col.active_tile_idx].window())
+    }
+
     pub fn active_window_mut(&mut self) -> Option<&mut W> {
         if self.columns.is_empty() {
             return None;
@@ -821,7 +851,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             .unwrap();
 
         // Find the column containing the position.
-        let (col_idx, _) = self
+        let (col_idx, col_start) = self
             .column_xs(self.data.iter().copied())
             .enumerate()
             .take_while(|(_, col_x)| *col_x <= x)
@@ -833,9 +863,31 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return InsertPosition::NewColumn(closest_col_idx);
         }
 
-        // Find the closest gap between tiles.
         let col = &self.columns[col_idx];
 
+        // Dropping onto the middle of a window stacks it in that window's column, above or below
+        // depending on the half of the window.
+        let col_width = self.data[col_idx].width;
+        let from_edge = f64::min(x - col_start, col_start + col_width - x);
+        if from_edge > col_width / 4. {
+            let tile_idx = if col.display_mode == ColumnDisplay::Tabbed {
+                col.active_tile_idx
+            } else {
+                col.tile_offsets()
+                    .take(col.tiles.len())
+                    .take_while(|tile_off| tile_off.y <= y)
+                    .count()
+                    .saturating_sub(1)
+            };
+
+            let tile_y = col.tile_offset(tile_idx).y;
+            let middle = tile_y + col.data[tile_idx].size.h / 2.;
+            let tile_idx = if y < middle { tile_idx } else { tile_idx + 1 };
+            return InsertPosition::InColumn(col_idx, tile_idx);
+        }
+
+        // Find the closest gap between tiles.
+
         let (closest_tile_idx, tile_y) = if col.display_mode == ColumnDisplay::Tabbed {
             // In tabbed mode, there's only one tile visible, and we want to check its top and
             // bottom.
@@ -886,6 +938,38 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.add_column(col_idx, column, activate, anim_config);
     }
 
+    /// Adds the tile of a newly opened window according to the new window placement.
+    pub fn add_new_window_tile(
+        &mut self,
+        tile: Tile<W>,
+        activate: bool,
+        width: ColumnWidth,
+        is_full_width: bool,
+    ) {
+        if self.columns.is_empty() {
+            self.add_tile(None, tile, activate, width, is_full_width, None);
+            return;
+        }
+
+        let placement = tile
+            .window()
+            .rules()
+            .new_window_placement
+            .unwrap_or(self.options.layout.new_window_placement);
+
+        let col_idx = match placement {
+            NewWindowPlacement::RightOfFocused => self.active_column_idx + 1,
+            NewWindowPlacement::LeftOfFocused => self.active_column_idx,
+            NewWindowPlacement::End => self.columns.len(),
+            NewWindowPlacement::FocusedColumn => {
+                self.add_tile_to_column(self.active_column_idx, None, tile, activate);
+                return;
+            }
+        };
+
+        self.add_tile(Some(col_idx), tile, activate, width, is_full_width, None);
+    }
+
     pub fn add_tile_to_column(
         &mut self,
         col_idx: usize,
@@ -915,6 +999,11 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         }
 
         let target_column = &mut self.columns[col_idx];
+        if target_column.display_mode == ColumnDisplay::Accordion {
+            // The active tile index could've shifted after the tile sizes were computed.
+            target_column.update_tile_sizes(true);
+        }
+
         if target_column.display_mode == ColumnDisplay::Tabbed {
             if target_column.active_tile_idx == tile_idx {
                 // Fade out the previously active tile.
@@ -983,6 +1072,10 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             self.scale,
             self.options.clone(),
         );
+        if column.is_monocle != self.is_monocle {
+            column.is_monocle = self.is_monocle;
+            column.update_tile_sizes(false);
+        }
         self.data.insert(idx, ColumnData::new(&column));
         self.columns.insert(idx, column);
 
@@ -990,6 +1083,9 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             self.active_column_idx += 1;
         }
 
+        self.update_smart_gaps();
+        self.auto_balance_columns();
+
         // Animate movement of other columns.
         let offset = self.column_x(idx + 1) - self.column_x(idx);
         let config = anim_config.unwrap_or(self.options.animations.window_movement.0);
@@ -1178,6 +1274,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
         let column = self.columns.remove(column_idx);
         self.data.remove(column_idx);
+        self.update_smart_gaps();
 
         // Stop interactive resize.
         if let Some(resize) = &self.interactive_resize {
@@ -1204,6 +1301,8 @@ impl<W: LayoutElement> ScrollingSpace<W> {
             return column;
         }
 
+        self.auto_balance_columns();
+
         let view_config = anim_config.unwrap_or(self.options.animations.horizontal_view_movement.0);
 
         if column_idx < self.active_column_idx {
@@ -2066,6 +2165,9 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         let target_tile_idx = self.columns[target_column_idx].active_tile_idx;
         let source_column_drained = self.columns[source_column_idx].tiles.len() == 1;
 
+        let source_height = self.columns[source_column_idx].data[source_tile_idx].height;
+        let target_height = self.columns[target_column_idx].data[target_tile_idx].height;
+
         // capture the original positions of the tiles
         let (mut source_pt, mut target_pt) = (
             self.columns[source_column_idx].render_offset()
@@ -2138,6 +2240,24 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.columns[source_column_idx].active_tile_idx = source_tile_idx;
         self.columns[target_column_idx].active_tile_idx = target_tile_idx;
 
+        // carry the window heights over, so that both windows keep their sizes; only one window
+        // in a column can be non-auto-height though
+        for (col_idx, tile_idx, height) in [
+            (source_column_idx, source_tile_idx, target_height),
+            (target_column_idx, target_tile_idx, source_height),
+        ] {
+            let col = &mut self.columns[col_idx];
+            let other_non_auto = col.data.iter().enumerate().any(|(idx, data)| {
+                idx != tile_idx && !matches!(data.height, WindowHeight::Auto { .. })
+            });
+            if matches!(height, WindowHeight::Auto { .. }) || !other_non_auto {
+                col.data[tile_idx].height = height;
+            }
+
+            col.update_tile_sizes(true);
+            self.data[col_idx].update(col);
+        }
+
         // Animations
         self.columns[target_column_idx].tiles[target_tile_idx]
             .animate_move_from(source_pt - target_pt);
@@ -2163,13 +2283,27 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
         let col = &mut self.columns[self.active_column_idx];
         let display = match col.display_mode {
-            ColumnDisplay::Normal => ColumnDisplay::Tabbed,
+            ColumnDisplay::Normal | ColumnDisplay::Accordion => ColumnDisplay::Tabbed,
             ColumnDisplay::Tabbed => ColumnDisplay::Normal,
         };
 
         self.set_column_display(display);
     }
 
+    pub fn toggle_column_accordion_display(&mut self) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let col = &mut self.columns[self.active_column_idx];
+        let display = match col.display_mode {
+            ColumnDisplay::Normal | ColumnDisplay::Tabbed => ColumnDisplay::Accordion,
+            ColumnDisplay::Accordion => ColumnDisplay::Normal,
+        };
+
+        self.set_column_display(display);
+    }
+
     pub fn set_column_display(&mut self, display: ColumnDisplay) {
         if self.columns.is_empty() {
             return;
@@ -2603,6 +2737,18 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         cancel_resize_for_column(&mut self.interactive_resize, col);
     }
 
+    pub fn toggle_monocle(&mut self) {
+        self.is_monocle = !self.is_monocle;
+
+        for (col, data) in zip(&mut self.columns, &mut self.data) {
+            col.is_monocle = self.is_monocle;
+            col.update_tile_sizes(true);
+            data.update(col);
+
+            cancel_resize_for_column(&mut self.interactive_resize, col);
+        }
+    }
+
     pub fn set_window_width(&mut self, window: Option<&W::Id>, change: SizeChange) {
         if self.columns.is_empty() {
             return;
@@ -2820,6 +2966,83 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         self.animate_view_offset_to_column(None, self.active_column_idx, None);
     }
 
+    /// Sets the width of every column to a share of the working area.
+    ///
+    /// With `by_window_count`, each column gets a share proportional to its number of windows,
+    /// otherwise all columns get the same share.
+    pub fn distribute_column_widths(&mut self, by_window_count: bool) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let total: usize = if by_window_count {
+            self.columns.iter().map(|col| col.tiles.len()).sum()
+        } else {
+            self.columns.len()
+        };
+
+        for col in &mut self.columns {
+            let share = if by_window_count { col.tiles.len() } else { 1 };
+            let proportion = share as f64 / total as f64;
+            col.set_column_width(SizeChange::SetProportion(proportion * 100.), None, true);
+
+            cancel_resize_for_column(&mut self.interactive_resize, col);
+        }
+    }
+
+    /// Drops or restores gaps, borders and focus rings as the number of columns changes.
+    fn update_smart_gaps(&mut self) {
+        let options = smart_gaps_options(&self.base_options, self.columns.len());
+        if options == self.options {
+            return;
+        }
+
+        // Smart gaps also drop the outer gaps, which changes the working area.
+        let struts = outer_struts(&options.layout);
+        let working_area = compute_working_area(self.parent_area, self.scale, struts);
+
+        for (column, data) in zip(&mut self.columns, &mut self.data) {
+            column.update_config(
+                self.view_size,
+                working_area,
+                self.parent_area,
+                self.scale,
+                options.clone(),
+            );
+            data.update(column);
+        }
+
+        self.working_area = working_area;
+        self.options = options;
+    }
+
+    /// Gives the columns equal widths if automatic column balancing is enabled.
+    ///
+    /// Up to the configured number of columns share the working area; past that, columns keep
+    /// the width that fits that many on screen.
+    fn auto_balance_columns(&mut self) {
+        let Some(max_columns) = self.options.layout.auto_balance_columns else {
+            return;
+        };
+
+        let count = self.columns.len().min(usize::from(max_columns.max(1)));
+        let width = ColumnWidth::Proportion(1. / count as f64);
+
+        for (col, data) in zip(&mut self.columns, &mut self.data) {
+            if col.width == width {
+                continue;
+            }
+
+            // Full-width and maximized columns get the new width once they go back to normal.
+            col.width = width;
+            col.preset_width_idx = None;
+            col.update_tile_sizes(true);
+            data.update(col);
+
+            cancel_resize_for_column(&mut self.interactive_resize, col);
+        }
+    }
+
     pub fn set_fullscreen(&mut self, window: &W::Id, is_fullscreen: bool) -> bool {
         let mut col_idx = self
             .columns
@@ -3728,7 +3951,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
     #[cfg(test)]
     pub fn options(&self) -> &Rc<Options> {
-        &self.options
+        &self.base_options
     }
 
     #[cfg(test)]
@@ -3750,7 +3973,16 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         assert_eq!(self.columns.len(), self.data.len());
         assert_eq!(
             self.working_area,
-            compute_working_area(self.parent_area, self.scale, self.options.layout.struts)
+            compute_working_area(
+                self.parent_area,
+                self.scale,
+                outer_struts(&self.options.layout)
+            )
+        );
+        assert_eq!(
+            self.options,
+            smart_gaps_options(&self.base_options, self.columns.len()),
+            "options must match the smart gaps state"
         );
 
         if !self.columns.is_empty() {
@@ -3758,6 +3990,7 @@ impl<W: LayoutElement> ScrollingSpace<W> {
 
             for (column, data) in zip(&self.columns, &self.data) {
                 assert!(Rc::ptr_eq(&self.options, &column.options));
+                assert_eq!(self.is_monocle, column.is_monocle);
                 assert_eq!(self.clock, column.clock);
                 assert_eq!(self.scale, column.scale);
                 column.verify_invariants();
@@ -3964,6 +4197,7 @@ impl<W: LayoutElement> Column<W> {
             width,
             preset_width_idx,
             is_full_width,
+            is_monocle: false,
             is_pending_maximized: false,
             is_pending_fullscreen: false,
             display_mode,
@@ -4284,6 +4518,11 @@ impl<W: LayoutElement> Column<W> {
 
         self.tiles[idx].ensure_alpha_animates_to_1();
 
+        // In accordion display mode, the newly active tile expands.
+        if self.display_mode == ColumnDisplay::Accordion {
+            self.update_tile_sizes(true);
+        }
+
         true
     }
 
@@ -4435,6 +4674,7 @@ impl<W: LayoutElement> Column<W> {
         }
 
         let is_tabbed = self.display_mode == ColumnDisplay::Tabbed;
+        let is_accordion = self.display_mode == ColumnDisplay::Accordion && self.tiles.len() > 1;
 
         let min_size: Vec<_> = self
             .tiles
@@ -4474,7 +4714,7 @@ impl<W: LayoutElement> Column<W> {
             .unwrap_or(f64::from(i32::MAX));
         let max_width = f64::max(max_width, min_width);
 
-        let width = if self.is_full_width {
+        let width = if self.is_full_width || self.is_monocle {
             ColumnWidth::Proportion(1.)
         } else {
             self.width
@@ -4490,7 +4730,7 @@ impl<W: LayoutElement> Column<W> {
         // If there are multiple windows in a column, clamp the non-auto window's height according
         // to other windows' min sizes.
         let mut max_non_auto_window_height = None;
-        if self.tiles.len() > 1 && !is_tabbed {
+        if self.tiles.len() > 1 && !is_tabbed && !is_accordion {
             if let Some(non_auto_idx) = self
                 .data
                 .iter()
@@ -4577,6 +4817,21 @@ impl<W: LayoutElement> Column<W> {
             // The following logic will apply individual min/max height, etc.
         }
 
+        // In accordion display mode, collapse the inactive tiles and give the rest of the height
+        // to the active tile, ignoring the window heights.
+        if is_accordion {
+            for (tile_idx, (h, tile)) in zip(&mut heights, &self.tiles).enumerate() {
+                *h = if tile_idx == self.active_tile_idx {
+                    WindowHeight::auto_1()
+                } else {
+                    let window_height = ACCORDION_COLLAPSED_WINDOW_HEIGHT;
+                    WindowHeight::Fixed(tile.tile_height_for_window_height(window_height))
+                };
+            }
+
+            // The following logic will apply individual min/max height, etc.
+        }
+
         let gaps_left = self.options.layout.gaps * (self.tiles.len() + 1) as f64;
         let mut height_left = working_size.h - gaps_left;
         let mut auto_tiles_left = self.tiles.len();
@@ -5111,6 +5366,16 @@ impl<W: LayoutElement> Column<W> {
             return;
         }
 
+        // Normal and accordion display only differ in the tile heights, which animate by
+        // themselves.
+        let was_tabbed = self.display_mode == ColumnDisplay::Tabbed;
+        let is_tabbed = display == ColumnDisplay::Tabbed;
+        if was_tabbed == is_tabbed {
+            self.display_mode = display;
+            self.update_tile_sizes(true);
+            return;
+        }
+
         // Animate the movement.
         //
         // We're doing some shortcuts here because we know that currently normal vs. tabbed can
@@ -5129,7 +5394,7 @@ impl<W: LayoutElement> Column<W> {
             let mut y_delta = pos.y - prev_origin.y;
 
             // Invert the Y motion when transitioning *to* normal display mode.
-            if display == ColumnDisplay::Normal {
+            if !is_tabbed {
                 y_delta *= -1.;
             }
 
@@ -5142,17 +5407,13 @@ impl<W: LayoutElement> Column<W> {
         for (idx, tile) in self.tiles.iter_mut().enumerate() {
             let is_active = idx == self.active_tile_idx;
             if !is_active {
-                let (from, to) = if display == ColumnDisplay::Tabbed {
-                    (1., 0.)
-                } else {
-                    (0., 1.)
-                };
+                let (from, to) = if is_tabbed { (1., 0.) } else { (0., 1.) };
                 tile.animate_alpha(from, to, self.options.animations.window_movement.0);
             }
         }
 
         // Animate the appearance of the tab indicator.
-        if display == ColumnDisplay::Tabbed {
+        if is_tabbed {
             self.tab_indicator.start_open_animation(
                 self.clock.clone(),
                 self.options.animations.window_movement.0,
@@ -5516,6 +5777,47 @@ fn compute_working_area(
     working_area
 }
 
+/// Returns the struts with the outer gaps folded in.
+///
+/// Windows keep the regular gaps from the working area edges, so an outer gap that differs from
+/// the regular gaps amounts to a strut adjusted by the difference.
+fn outer_struts(layout: &niri_config::Layout) -> Struts {
+    let outer = |strut: FloatOrInt<-65535, 65535>, gap: Option<FloatOrInt<0, 65535>>| {
+        FloatOrInt(strut.0 + gap.map_or(0., |gap| gap.0 - layout.gaps))
+    };
+
+    let Struts {
+        left,
+        right,
+        top,
+        bottom,
+    } = layout.struts;
+    let gaps = layout.outer_gaps;
+    Struts {
+        left: outer(left, gaps.left),
+        right: outer(right, gaps.right),
+        top: outer(top, gaps.top),
+        bottom: outer(bottom, gaps.bottom),
+    }
+}
+
+/// Returns the options to use for the given number of columns, taking smart gaps into account.
+///
+/// This counts all columns on the workspace rather than the visible ones. Dropping the gaps
+/// changes the column widths, so counting visible columns could flip back and forth.
+fn smart_gaps_options(options: &Rc<Options>, column_count: usize) -> Rc<Options> {
+    if !options.layout.smart_gaps || column_count != 1 {
+        return options.clone();
+    }
+
+    let mut options = Options::clone(options);
+    options.layout.gaps = 0.;
+    options.layout.outer_gaps = OuterGaps::default();
+    options.layout.border.off = true;
+    options.layout.focus_ring.off = true;
+    Rc::new(options)
+}
+
 fn compute_toplevel_bounds(
     border_config: niri_config::Border,
     working_area_size: Size<f64, Logical>,
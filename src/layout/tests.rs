@@ -3,8 +3,8 @@ use std::cell::{Cell, OnceCell, RefCell};
 use niri_config::utils::{Flag, MergeWith as _};
 use niri_config::workspace::WorkspaceName;
 use niri_config::{
-    CenterFocusedColumn, FloatOrInt, OutputName, Struts, TabIndicatorLength, TabIndicatorPosition,
-    WorkspaceReference,
+    CenterFocusedColumn, FloatOrInt, NewWindowPlacement, OuterGaps, OutputName, Struts,
+    TabIndicatorLength, TabIndicatorPosition, WorkspaceReference,
 };
 use proptest::prelude::*;
 use proptest_derive::Arbitrary;
@@ -395,7 +395,11 @@ fn arbitrary_scroll_direction() -> impl Strategy<Value = ScrollDirection> {
 }
 
 fn arbitrary_column_display() -> impl Strategy<Value = ColumnDisplay> {
-    prop_oneof![Just(ColumnDisplay::Normal), Just(ColumnDisplay::Tabbed)]
+    prop_oneof![
+        Just(ColumnDisplay::Normal),
+        Just(ColumnDisplay::Tabbed),
+        Just(ColumnDisplay::Accordion)
+    ]
 }
 
 #[derive(Debug, Clone, Arbitrary)]
@@ -504,6 +508,7 @@ enum Op {
     ExpelWindowFromColumn,
     SwapWindowInDirection(#[proptest(strategy = "arbitrary_scroll_direction()")] ScrollDirection),
     ToggleColumnTabbedDisplay,
+    ToggleColumnAccordionDisplay,
     SetColumnDisplay(#[proptest(strategy = "arbitrary_column_display()")] ColumnDisplay),
     CenterColumn,
     CenterWindow {
@@ -607,6 +612,9 @@ enum Op {
         id: Option<usize>,
     },
     ExpandColumnToAvailableWidth,
+    EqualizeColumnWidths,
+    DistributeColumnWidths,
+    ToggleMonocle,
     ToggleWindowFloating {
         #[proptest(strategy = "proptest::option::of(1..=5usize)")]
         id: Option<usize>,
@@ -616,6 +624,14 @@ enum Op {
         id: Option<usize>,
         floating: bool,
     },
+    ToggleWindowPinned {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+    },
+    ToggleWindowAlwaysOnTop {
+        #[proptest(strategy = "proptest::option::of(1..=5usize)")]
+        id: Option<usize>,
+    },
     FocusFloating,
     FocusTiling,
     SwitchFocusFloatingTiling,
@@ -1166,6 +1182,7 @@ impl Op {
             Op::ExpelWindowFromColumn => layout.expel_from_column(),
             Op::SwapWindowInDirection(direction) => layout.swap_window_in_direction(direction),
             Op::ToggleColumnTabbedDisplay => layout.toggle_column_tabbed_display(),
+            Op::ToggleColumnAccordionDisplay => layout.toggle_column_accordion_display(),
             Op::SetColumnDisplay(display) => layout.set_column_display(display),
             Op::CenterColumn => layout.center_column(),
             Op::CenterWindow { id } => {
@@ -1341,6 +1358,9 @@ impl Op {
                 layout.reset_window_height(id.as_ref());
             }
             Op::ExpandColumnToAvailableWidth => layout.expand_column_to_available_width(),
+            Op::EqualizeColumnWidths => layout.distribute_column_widths(false),
+            Op::DistributeColumnWidths => layout.distribute_column_widths(true),
+            Op::ToggleMonocle => layout.toggle_monocle(),
             Op::ToggleWindowFloating { id } => {
                 let id = id.filter(|id| layout.has_window(id));
                 layout.toggle_window_floating(id.as_ref());
@@ -1349,6 +1369,14 @@ impl Op {
                 let id = id.filter(|id| layout.has_window(id));
                 layout.set_window_floating(id.as_ref(), floating);
             }
+            Op::ToggleWindowPinned { id } => {
+                let id = id.filter(|id| layout.has_window(id));
+                layout.toggle_window_pinned(id.as_ref());
+            }
+            Op::ToggleWindowAlwaysOnTop { id } => {
+                let id = id.filter(|id| layout.has_window(id));
+                layout.toggle_window_always_on_top(id.as_ref());
+            }
             Op::FocusFloating => {
                 layout.focus_floating();
             }
@@ -2174,6 +2202,45 @@ fn large_max_size() {
     check_ops_with_options(options, ops);
 }
 
+#[test]
+fn border_resize_edges() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+    ];
+
+    let mut options = Options::default();
+    options.layout.border.off = false;
+    options.layout.border.width = 4.;
+
+    let layout = check_ops_with_options(options, ops);
+    let output = layout.outputs().next().unwrap().clone();
+
+    let mon = layout.monitor_for_output(&output).unwrap();
+    let (tile, tile_pos, _) = mon
+        .active_workspace_ref()
+        .tiles_with_render_positions()
+        .next()
+        .unwrap();
+    let size = tile.tile_size();
+
+    let edges = |x: f64, y: f64| {
+        let pos = tile_pos + Point::from((x, y));
+        layout.border_resize_edges_under(&output, pos)
+    };
+
+    assert_eq!(edges(size.w / 2., size.h / 2.), None);
+    assert_eq!(edges(size.w - 2., size.h / 2.), Some(ResizeEdge::RIGHT));
+    assert_eq!(edges(size.w / 2., 2.), Some(ResizeEdge::TOP));
+    assert_eq!(edges(2., size.h - 2.), Some(ResizeEdge::BOTTOM_LEFT));
+    assert_eq!(
+        edges(size.w - 10., size.h - 2.),
+        Some(ResizeEdge::BOTTOM_RIGHT)
+    );
+}
+
 #[test]
 fn workspace_cleanup_during_switch() {
     let ops = [
@@ -2544,6 +2611,37 @@ fn start_interactive_move_then_remove_window() {
     check_ops(ops);
 }
 
+#[test]
+fn interactive_move_onto_window_middle_stacks() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::Communicate(1),
+    ];
+
+    let layout = check_ops(ops);
+    let scrolling = layout.active_workspace().unwrap().scrolling();
+
+    // The column starts at the gap and takes half of the remaining width.
+    let middle_x = 16. + 616. / 2.;
+    assert_eq!(
+        scrolling.insert_position(Point::from((middle_x, 100.))),
+        InsertPosition::InColumn(0, 0)
+    );
+    assert_eq!(
+        scrolling.insert_position(Point::from((middle_x, 600.))),
+        InsertPosition::InColumn(0, 1)
+    );
+
+    // Near the column edge, the window goes into a new column.
+    assert_eq!(
+        scrolling.insert_position(Point::from((20., 360.))),
+        InsertPosition::NewColumn(0)
+    );
+}
+
 #[test]
 fn interactive_move_onto_empty_output() {
     let ops = [
@@ -3332,6 +3430,357 @@ fn removing_window_above_preserves_focused_window() {
     assert_eq!(win.0.id, 1);
 }
 
+#[test]
+fn distribute_column_widths() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::EqualizeColumnWidths,
+        Op::Communicate(1),
+        Op::Communicate(2),
+        Op::Communicate(3),
+    ];
+
+    let mut layout = check_ops(ops);
+
+    let width = |layout: &Layout<TestWindow>, id: usize| {
+        let (_, win) = layout.windows().find(|(_, win)| win.id() == &id).unwrap();
+        f64::from(win.requested_size().unwrap().w)
+    };
+    assert_eq!(width(&layout, 1), width(&layout, 2));
+    assert_eq!(width(&layout, 2), width(&layout, 3));
+
+    check_ops_on_layout(&mut layout, [Op::DistributeColumnWidths]);
+
+    // The column with two windows takes twice the space, including its gap.
+    let gap = layout.options.layout.gaps;
+    let single = width(&layout, 1) + gap;
+    let double = width(&layout, 2) + gap;
+    assert!(
+        (double - 2. * single).abs() <= 1.,
+        "{double} vs 2 × {single}"
+    );
+    assert_eq!(width(&layout, 2), width(&layout, 3));
+}
+
+#[test]
+fn monocle_restores_column_widths() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ToggleMonocle,
+    ];
+
+    let mut layout = check_ops(ops);
+
+    let width = |layout: &Layout<TestWindow>, id: usize| {
+        let (_, win) = layout.windows().find(|(_, win)| win.id() == &id).unwrap();
+        win.requested_size().unwrap().w
+    };
+    let full = width(&layout, 1);
+    assert_eq!(width(&layout, 2), full);
+
+    // New columns take the full width too.
+    check_ops_on_layout(
+        &mut layout,
+        [Op::AddWindow {
+            params: TestWindowParams::new(3),
+        }],
+    );
+    assert_eq!(width(&layout, 3), full);
+
+    check_ops_on_layout(&mut layout, [Op::ToggleMonocle]);
+    assert!(width(&layout, 1) < full);
+    assert_eq!(width(&layout, 1), width(&layout, 2));
+    assert_eq!(width(&layout, 2), width(&layout, 3));
+}
+
+#[test]
+fn auto_balance_columns() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::Communicate(1),
+    ];
+
+    let options = Options {
+        layout: niri_config::Layout {
+            auto_balance_columns: Some(2),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut layout = check_ops_with_options(options, ops);
+
+    let width = |layout: &Layout<TestWindow>, id: usize| {
+        let (_, win) = layout.windows().find(|(_, win)| win.id() == &id).unwrap();
+        win.requested_size().unwrap().w
+    };
+    let full = width(&layout, 1);
+
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::AddWindow {
+                params: TestWindowParams::new(2),
+            },
+            Op::Communicate(1),
+            Op::Communicate(2),
+        ],
+    );
+    let half = width(&layout, 1);
+    assert!(half < full);
+    assert_eq!(width(&layout, 2), half);
+
+    // Past the maximum count, columns keep their width.
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::AddWindow {
+                params: TestWindowParams::new(3),
+            },
+            Op::Communicate(3),
+        ],
+    );
+    assert_eq!(width(&layout, 3), half);
+
+    check_ops_on_layout(
+        &mut layout,
+        [Op::CloseWindow(2), Op::CloseWindow(3), Op::Communicate(1)],
+    );
+    assert_eq!(width(&layout, 1), full);
+}
+
+#[test]
+fn auto_balance_columns_on_config_change() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::Communicate(1),
+        Op::Communicate(2),
+    ];
+
+    let mut layout = check_ops(ops);
+
+    let width = |layout: &Layout<TestWindow>, id: usize| {
+        let (_, win) = layout.windows().find(|(_, win)| win.id() == &id).unwrap();
+        win.requested_size().unwrap().w
+    };
+    let before = width(&layout, 1);
+
+    // Enabling the option balances the existing columns right away.
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::UpdateConfig {
+                layout_config: Box::new(niri_config::LayoutPart {
+                    auto_balance_columns: Some(1),
+                    ..Default::default()
+                }),
+            },
+            Op::Communicate(1),
+            Op::Communicate(2),
+        ],
+    );
+    assert!(width(&layout, 1) > before);
+    assert_eq!(width(&layout, 1), width(&layout, 2));
+}
+
+#[test]
+fn smart_gaps_single_column() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::MaximizeColumn,
+        Op::Communicate(1),
+    ];
+
+    let options = Options {
+        layout: niri_config::Layout {
+            smart_gaps: true,
+            border: niri_config::Border {
+                off: false,
+                width: 5.,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut layout = check_ops_with_options(options, ops);
+
+    let width = |layout: &Layout<TestWindow>, id: usize| {
+        let (_, win) = layout.windows().find(|(_, win)| win.id() == &id).unwrap();
+        win.requested_size().unwrap().w
+    };
+
+    // No gaps and no border around the single column.
+    assert_eq!(width(&layout, 1), 1280);
+
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::AddWindow {
+                params: TestWindowParams::new(2),
+            },
+            Op::Communicate(1),
+        ],
+    );
+    assert_eq!(width(&layout, 1), 1280 - 16 * 2 - 5 * 2);
+
+    check_ops_on_layout(&mut layout, [Op::CloseWindow(2), Op::Communicate(1)]);
+    assert_eq!(width(&layout, 1), 1280);
+}
+
+#[test]
+fn outer_gaps_replace_gaps_at_edges() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::MaximizeColumn,
+        Op::Communicate(1),
+    ];
+
+    let options = Options {
+        layout: niri_config::Layout {
+            outer_gaps: OuterGaps {
+                left: Some(FloatOrInt(0.)),
+                right: Some(FloatOrInt(0.)),
+                top: Some(FloatOrInt(8.)),
+                bottom: None,
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let layout = check_ops_with_options(options, ops);
+
+    let (_, win) = layout.windows().find(|(_, win)| win.id() == &1).unwrap();
+    assert_eq!(
+        win.requested_size().unwrap(),
+        Size::from((1280, 720 - 8 - 16))
+    );
+}
+
+#[test]
+fn new_window_placement() {
+    let check = |placement, expected: &[&[usize]]| {
+        let ops = [
+            Op::AddOutput(1),
+            Op::AddWindow {
+                params: TestWindowParams::new(1),
+            },
+            Op::AddWindow {
+                params: TestWindowParams::new(2),
+            },
+            Op::FocusColumnLeft,
+            Op::AddWindow {
+                params: TestWindowParams::new(3),
+            },
+        ];
+
+        let options = Options {
+            layout: niri_config::Layout {
+                new_window_placement: placement,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let layout = check_ops_with_options(options, ops);
+
+        let scrolling = layout.active_workspace().unwrap().scrolling();
+        let columns: Vec<Vec<usize>> = scrolling
+            .columns()
+            .map(|col| col.tiles().map(|(tile, _)| *tile.window().id()).collect())
+            .collect();
+        assert_eq!(columns, expected, "{placement:?}");
+    };
+
+    check(NewWindowPlacement::RightOfFocused, &[&[1], &[3], &[2]]);
+    check(NewWindowPlacement::LeftOfFocused, &[&[3], &[2], &[1]]);
+    check(NewWindowPlacement::End, &[&[1], &[2], &[3]]);
+    check(NewWindowPlacement::FocusedColumn, &[&[1, 2, 3]]);
+}
+
+#[test]
+fn swap_window_keeps_height() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::FocusColumnLeft,
+        Op::SetWindowHeight {
+            id: None,
+            change: SizeChange::SetFixed(200),
+        },
+        Op::SwapWindowInDirection(ScrollDirection::Right),
+        Op::Communicate(1),
+        Op::Communicate(2),
+        Op::Communicate(3),
+    ];
+
+    let mut layout = check_ops(ops);
+
+    let height = |layout: &Layout<TestWindow>, id: usize| {
+        let (_, win) = layout.windows().find(|(_, win)| win.id() == &id).unwrap();
+        win.requested_size().unwrap().h
+    };
+    assert_eq!(height(&layout, 2), 200);
+    assert_eq!(height(&layout, 1), height(&layout, 3));
+
+    // Within a column, moving a window already swaps it with its neighbor and keeps the heights.
+    check_ops_on_layout(
+        &mut layout,
+        [
+            Op::FocusColumnLeft,
+            Op::MoveWindowUp,
+            Op::Communicate(1),
+            Op::Communicate(3),
+        ],
+    );
+    let columns: Vec<Vec<usize>> = layout
+        .active_workspace()
+        .unwrap()
+        .scrolling()
+        .columns()
+        .map(|col| col.tiles().map(|(tile, _)| *tile.window().id()).collect())
+        .collect();
+    assert_eq!(columns, [vec![3, 1], vec![2]]);
+}
+
 #[test]
 fn preset_column_width_fixed_correct_with_border() {
     let ops = [
@@ -3515,6 +3964,61 @@ fn move_column_to_workspace_focus_false_on_floating_window() {
     assert_eq!(monitors[0].active_workspace_idx, 0);
 }
 
+#[test]
+fn pinned_window_follows_workspace_switch() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::ToggleWindowPinned { id: None },
+        Op::FocusWorkspaceDown,
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::FocusWorkspaceDown,
+    ];
+
+    let layout = check_ops(ops);
+
+    let MonitorSet::Normal { monitors, .. } = layout.monitor_set else {
+        unreachable!()
+    };
+
+    let mon = &monitors[0];
+    let ws = &mon.workspaces[mon.active_workspace_idx];
+    assert!(ws.has_window(&1));
+    assert!(ws.is_floating(&1));
+    assert!(!ws.has_window(&2));
+}
+
+#[test]
+fn always_on_top_window_stays_above_floating_windows() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::ToggleWindowAlwaysOnTop { id: None },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::ToggleWindowFloating { id: Some(2) },
+        Op::FocusWindow(2),
+    ];
+
+    let layout = check_ops(ops);
+
+    let ws = layout.active_workspace().unwrap();
+    let ids: Vec<_> = ws
+        .floating()
+        .tiles()
+        .map(|tile| *tile.window().id())
+        .collect();
+    assert_eq!(ids, [1, 2]);
+    assert_eq!(ws.active_window().map(|win| *win.id()), Some(2));
+}
+
 #[test]
 fn restore_to_floating_persists_across_fullscreen_maximize() {
     let ops = [
@@ -3691,6 +4195,49 @@ fn expel_pending_left_from_fullscreen_tabbed_column() {
     check_ops(ops);
 }
 
+#[test]
+fn accordion_column_collapses_inactive_windows() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(0),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::ConsumeOrExpelWindowLeft { id: None },
+        Op::ToggleColumnAccordionDisplay,
+    ];
+
+    let check_heights = |layout: &Layout<TestWindow>| {
+        let active = layout.focus().unwrap().0.id;
+        for (_, win) in layout.windows() {
+            let height = win.requested_size().unwrap().h;
+            if win.0.id == active {
+                assert!(height > 48);
+            } else {
+                assert_eq!(height, 48);
+            }
+        }
+    };
+
+    let mut layout = check_ops(ops);
+    assert_eq!(layout.focus().unwrap().0.id, 1);
+    check_heights(&layout);
+
+    layout.focus_up();
+    assert_eq!(layout.focus().unwrap().0.id, 0);
+    check_heights(&layout);
+
+    // Switching back to normal display splits the height evenly again.
+    layout.toggle_column_accordion_display();
+    let heights: Vec<_> = layout
+        .windows()
+        .map(|(_, win)| win.requested_size().unwrap().h)
+        .collect();
+    assert_eq!(heights[0], heights[1]);
+}
+
 fn parent_id_causes_loop(layout: &Layout<TestWindow>, id: usize, mut parent_id: usize) -> bool {
     if parent_id == id {
         return true;
@@ -3752,6 +4299,16 @@ fn arbitrary_struts() -> impl Strategy<Value = Struts> {
         })
 }
 
+fn arbitrary_outer_gaps() -> impl Strategy<Value = OuterGaps> {
+    let gap = || prop::option::of(arbitrary_spacing().prop_map(FloatOrInt));
+    (gap(), gap(), gap(), gap()).prop_map(|(left, right, top, bottom)| OuterGaps {
+        left,
+        right,
+        top,
+        bottom,
+    })
+}
+
 fn arbitrary_center_focused_column() -> impl Strategy<Value = CenterFocusedColumn> {
     prop_oneof![
         Just(CenterFocusedColumn::Never),
@@ -3760,6 +4317,15 @@ fn arbitrary_center_focused_column() -> impl Strategy<Value = CenterFocusedColum
     ]
 }
 
+fn arbitrary_new_window_placement() -> impl Strategy<Value = NewWindowPlacement> {
+    prop_oneof![
+        Just(NewWindowPlacement::RightOfFocused),
+        Just(NewWindowPlacement::LeftOfFocused),
+        Just(NewWindowPlacement::End),
+        Just(NewWindowPlacement::FocusedColumn),
+    ]
+}
+
 fn arbitrary_tab_indicator_position() -> impl Strategy<Value = TabIndicatorPosition> {
     prop_oneof![
         Just(TabIndicatorPosition::Left),
@@ -3839,6 +4405,7 @@ prop_compose! {
 prop_compose! {
     fn arbitrary_layout_part()(
         gaps in prop::option::of(arbitrary_spacing().prop_map(FloatOrInt)),
+        outer_gaps in prop::option::of(arbitrary_outer_gaps()),
         struts in prop::option::of(arbitrary_struts()),
         focus_ring in prop::option::of(arbitrary_focus_ring()),
         border in prop::option::of(arbitrary_border()),
@@ -3847,13 +4414,18 @@ prop_compose! {
         center_focused_column in prop::option::of(arbitrary_center_focused_column()),
         always_center_single_column in prop::option::of(any::<bool>().prop_map(Flag)),
         empty_workspace_above_first in prop::option::of(any::<bool>().prop_map(Flag)),
+        smart_gaps in prop::option::of(any::<bool>().prop_map(Flag)),
+        new_window_placement in prop::option::of(arbitrary_new_window_placement()),
     ) -> niri_config::LayoutPart {
         niri_config::LayoutPart {
             gaps,
+            outer_gaps,
             struts,
             center_focused_column,
             always_center_single_column,
             empty_workspace_above_first,
+            smart_gaps,
+            new_window_placement,
             focus_ring,
             border,
             shadow,
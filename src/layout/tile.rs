@@ -30,9 +30,12 @@ use crate::render_helpers::solid_color::{SolidColorBuffer, SolidColorRenderEleme
 use crate::render_helpers::RenderTarget;
 use crate::utils::transaction::Transaction;
 use crate::utils::{
-    baba_is_float_offset, round_logical_in_physical, round_logical_in_physical_max1,
+    baba_is_float_offset, round_logical_in_physical, round_logical_in_physical_max1, ResizeEdge,
 };
 
+/// Length along the border from a window corner that resizes both edges of that corner.
+const BORDER_RESIZE_CORNER_SIZE: f64 = 24.;
+
 /// Toplevel window with decorations.
 #[derive(Debug)]
 pub struct Tile<W: LayoutElement> {
@@ -60,6 +63,12 @@ pub struct Tile<W: LayoutElement> {
     /// Whether the tile should float upon unfullscreening.
     pub(super) restore_to_floating: bool,
 
+    /// Whether the tile follows the workspace switches on its monitor.
+    pub(super) is_pinned: bool,
+
+    /// Whether the tile stays above the other floating tiles.
+    pub(super) is_always_on_top: bool,
+
     /// The size that the window should assume when going floating.
     ///
     /// This is generally the last size the window had when it was floating. It can be unknown if
@@ -184,6 +193,7 @@ impl<W: LayoutElement> Tile<W> {
         let focus_ring_config = options.layout.focus_ring.merged_with(&rules.focus_ring);
         let shadow_config = options.layout.shadow.merged_with(&rules.shadow);
         let sizing_mode = window.sizing_mode();
+        let is_always_on_top = rules.always_on_top == Some(true);
 
         Self {
             window,
@@ -193,6 +203,8 @@ impl<W: LayoutElement> Tile<W> {
             sizing_mode,
             fullscreen_backdrop: SolidColorBuffer::new((0., 0.), [0., 0., 0., 1.]),
             restore_to_floating: false,
+            is_pinned: false,
+            is_always_on_top,
             floating_window_size: None,
             floating_pos: None,
             floating_preset_width_idx: None,
@@ -658,6 +670,10 @@ impl<W: LayoutElement> Tile<W> {
         &mut self.window
     }
 
+    pub fn into_window(self) -> W {
+        self.window
+    }
+
     pub fn sizing_mode(&self) -> SizingMode {
         self.sizing_mode
     }
@@ -883,6 +899,53 @@ impl<W: LayoutElement> Tile<W> {
         }
     }
 
+    /// Returns the edges to resize when the point is on the border outside the window.
+    ///
+    /// Returns empty edges when the point is not on the border.
+    pub fn border_resize_edges(&self, point: Point<f64, Logical>) -> ResizeEdge {
+        let point = point - self.bob_offset();
+        if self.is_in_input_region(point) || !self.is_in_activation_region(point) {
+            return ResizeEdge::empty();
+        }
+
+        let loc = self.window_loc();
+        let size = self.window_size();
+        let corner = BORDER_RESIZE_CORNER_SIZE;
+
+        let on_left = point.x < loc.x;
+        let on_right = loc.x + size.w <= point.x;
+        let on_top = point.y < loc.y;
+        let on_bottom = loc.y + size.h <= point.y;
+
+        // Near the corners, the border resizes both edges.
+        let mut edges = ResizeEdge::empty();
+        if on_left || on_right {
+            edges |= if on_left {
+                ResizeEdge::LEFT
+            } else {
+                ResizeEdge::RIGHT
+            };
+            if point.y < loc.y + corner {
+                edges |= ResizeEdge::TOP;
+            } else if loc.y + size.h - corner <= point.y {
+                edges |= ResizeEdge::BOTTOM;
+            }
+        }
+        if on_top || on_bottom {
+            edges |= if on_top {
+                ResizeEdge::TOP
+            } else {
+                ResizeEdge::BOTTOM
+            };
+            if point.x < loc.x + corner {
+                edges |= ResizeEdge::LEFT;
+            } else if loc.x + size.w - corner <= point.x {
+                edges |= ResizeEdge::RIGHT;
+            }
+        }
+        edges
+    }
+
     pub fn request_tile_size(
         &mut self,
         mut size: Size<f64, Logical>,
@@ -185,6 +185,10 @@ pub enum WorkspaceAddWindowTarget<'a, W: LayoutElement> {
     /// No particular preference.
     #[default]
     Auto,
+    /// No particular preference, for a newly opened window.
+    ///
+    /// Same as `Auto`, but follows the new window placement in the scrolling layout.
+    NewWindow,
     /// As a new column at this index.
     NewColumnAt(usize),
     /// Next to this existing window.
@@ -463,6 +467,10 @@ impl<W: LayoutElement> Workspace<W> {
         self.floating.has_window(id)
     }
 
+    pub fn window_left_of(&self, id: &W::Id) -> Option<&W> {
+        self.scrolling.window_left_of(id)
+    }
+
     pub fn current_output(&self) -> Option<&Output> {
         self.output.as_ref()
     }
@@ -610,7 +618,7 @@ impl<W: LayoutElement> Workspace<W> {
         tile.restore_to_floating = is_floating;
 
         match target {
-            WorkspaceAddWindowTarget::Auto => {
+            WorkspaceAddWindowTarget::Auto | WorkspaceAddWindowTarget::NewWindow => {
                 // Don't steal focus from an active fullscreen window.
                 let activate = activate.map_smart(|| !self.is_active_pending_fullscreen());
 
@@ -622,6 +630,13 @@ impl<W: LayoutElement> Workspace<W> {
                     if activate || self.scrolling.is_empty() {
                         self.floating_is_active = FloatingActive::Yes;
                     }
+                } else if matches!(target, WorkspaceAddWindowTarget::NewWindow) {
+                    self.scrolling
+                        .add_new_window_tile(tile, activate, width, is_full_width);
+
+                    if activate {
+                        self.floating_is_active = FloatingActive::No;
+                    }
                 } else {
                     self.scrolling
                         .add_tile(None, tile, activate, width, is_full_width, None);
@@ -1141,6 +1156,13 @@ impl<W: LayoutElement> Workspace<W> {
         self.scrolling.toggle_column_tabbed_display();
     }
 
+    pub fn toggle_column_accordion_display(&mut self) {
+        if self.floating_is_active.get() {
+            return;
+        }
+        self.scrolling.toggle_column_accordion_display();
+    }
+
     pub fn set_column_display(&mut self, display: ColumnDisplay) {
         if self.floating_is_active.get() {
             return;
@@ -1254,6 +1276,14 @@ impl<W: LayoutElement> Workspace<W> {
         self.scrolling.expand_column_to_available_width();
     }
 
+    pub fn distribute_column_widths(&mut self, by_window_count: bool) {
+        self.scrolling.distribute_column_widths(by_window_count);
+    }
+
+    pub fn toggle_monocle(&mut self) {
+        self.scrolling.toggle_monocle();
+    }
+
     pub fn set_fullscreen(&mut self, window: &W::Id, is_fullscreen: bool) {
         let mut restore_to_floating = false;
         if self.floating.has_window(window) {
@@ -1444,6 +1474,55 @@ impl<W: LayoutElement> Workspace<W> {
         tile.animate_move_from(render_pos - new_render_pos);
     }
 
+    pub fn toggle_window_pinned(&mut self, id: Option<&W::Id>) {
+        let Some(id) = id
+            .cloned()
+            .or_else(|| self.active_window().map(|win| win.id().clone()))
+        else {
+            return;
+        };
+
+        let tile = self
+            .tiles_mut()
+            .find(|tile| *tile.window().id() == id)
+            .unwrap();
+        tile.is_pinned = !tile.is_pinned;
+
+        // Only floating windows follow the workspace switches.
+        if tile.is_pinned {
+            self.set_window_floating(Some(&id), true);
+        }
+    }
+
+    pub fn toggle_window_always_on_top(&mut self, id: Option<&W::Id>) {
+        let Some(id) = id
+            .cloned()
+            .or_else(|| self.active_window().map(|win| win.id().clone()))
+        else {
+            return;
+        };
+
+        let tile = self
+            .tiles_mut()
+            .find(|tile| *tile.window().id() == id)
+            .unwrap();
+        tile.is_always_on_top = !tile.is_always_on_top;
+
+        // Only floating windows can go above other windows.
+        if tile.is_always_on_top {
+            self.set_window_floating(Some(&id), true);
+        }
+        self.floating.restack_always_on_top();
+    }
+
+    /// Returns the pinned windows that should follow a workspace switch.
+    pub fn pinned_windows(&self) -> impl Iterator<Item = &W::Id> + '_ {
+        self.floating
+            .tiles()
+            .filter(|tile| tile.is_pinned)
+            .map(|tile| tile.window().id())
+    }
+
     pub fn set_window_floating(&mut self, id: Option<&W::Id>, floating: bool) {
         if id.map_or(self.floating_is_active.get(), |id| {
             self.floating.has_window(id)
@@ -1789,6 +1868,22 @@ impl<W: LayoutElement> Workspace<W> {
             })
     }
 
+    pub fn border_resize_edges_under(&self, pos: Point<f64, Logical>) -> Option<ResizeEdge> {
+        self.tiles_with_render_positions()
+            .find_map(|(tile, tile_pos, visible)| {
+                if !visible {
+                    return None;
+                }
+
+                let pos_within_tile = pos - tile_pos;
+                tile.hit(pos_within_tile)?;
+
+                let edges = tile.border_resize_edges(pos_within_tile);
+                Some((!edges.is_empty()).then_some(edges))
+            })
+            .flatten()
+    }
+
     pub fn descendants_added(&mut self, id: &W::Id) -> bool {
         self.floating.descendants_added(id)
     }
@@ -148,9 +148,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config_path = config_path(cli.config);
     env::remove_var("NIRI_CONFIG");
     let (config_created_at, config_load_result) = config_path.load_or_create();
-    let config_errored = config_load_result.config.is_err();
+    let mut config_error = None;
     let mut config = config_load_result.config.unwrap_or_else(|err| {
         warn!("{err:?}");
+        config_error = Some(format!("{err:?}"));
         Config::load_default()
     });
     let config_includes = config_load_result.includes;
@@ -253,7 +254,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Show the config error notification right away if needed.
-    if config_errored {
+    if let Some(err) = config_error {
+        state.niri.config_error = Some(err);
         state.niri.config_error_notification.show();
         state.ipc_config_loaded(true);
     } else if let Some(path) = config_created_at {
@@ -14,6 +14,7 @@ use _server_decoration::server::org_kde_kwin_server_decoration_manager::Mode as
 use anyhow::{bail, ensure, Context};
 use calloop::futures::Scheduler;
 use niri_config::debug::PreviewRender;
+use niri_config::gestures::{Corner, GestureOverrides, HotCorners};
 use niri_config::{
     Config, FloatOrInt, Key, Modifiers, OutputName, TrackLayout, WarpMouseToFocusMode,
     WorkspaceReference, Xkb,
@@ -125,15 +126,31 @@ use crate::dbus::gnome_shell_introspect::{self, IntrospectToNiri, NiriToIntrospe
 use crate::dbus::gnome_shell_screenshot::{NiriToScreenshot, ScreenshotToNiri};
 #[cfg(feature = "xdp-gnome-screencast")]
 use crate::dbus::mutter_screen_cast::{self, ScreenCastToNiri};
+#[cfg(feature = "dbus")]
+use crate::dbus::niri_compositor::{
+    self, CompositorChange, CompositorSignalState, CompositorToNiri,
+};
 use crate::frame_clock::FrameClock;
 use crate::handlers::{configure_lock_surface, XDG_ACTIVATION_TOKEN_TIMEOUT};
+use crate::input::accessibility_keys::AccessibilityKeys;
+use crate::input::double_press::DoublePressTracker;
+use crate::input::gesture_sequence::GestureSequenceTracker;
+use crate::input::hot_corner::HotCornerTracker;
+use crate::input::latency::InputLatencyTracker;
+use crate::input::modifier_tap::ModifierTapTracker;
 use crate::input::pick_color_grab::PickColorGrab;
+use crate::input::resize_mode::{self, ResizeMode};
 use crate::input::scroll_swipe_gesture::ScrollSwipeGesture;
 use crate::input::scroll_tracker::ScrollTracker;
+use crate::input::tablet_pad::TabletPadTracker;
+use crate::input::touch_edge_swipe::TouchEdgeSwipe;
+use crate::input::touchpad_gesture::TouchpadGesture;
+use crate::input::touchscreen_gesture::TouchscreenGesture;
 use crate::input::{
     apply_libinput_settings, mods_with_finger_scroll_binds, mods_with_mouse_binds,
     mods_with_wheel_binds, TabletData,
 };
+use crate::ipc::hooks::HookState;
 use crate::ipc::server::IpcServer;
 use crate::layer::mapped::LayerSurfaceRenderElement;
 use crate::layer::MappedLayer;
@@ -154,6 +171,7 @@ use crate::pw_utils::{Cast, PipeWire};
 #[cfg(feature = "xdp-gnome-screencast")]
 use crate::pw_utils::{CastSizeChange, CursorData, PwToNiri};
 use crate::render_helpers::debug::draw_opaque_regions;
+use crate::render_helpers::offscreen::OffscreenRenderElement;
 use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
 use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::solid_color::{SolidColorBuffer, SolidColorRenderElement};
@@ -163,10 +181,16 @@ use crate::render_helpers::{
     encompassing_geo, render_to_dmabuf, render_to_encompassing_texture, render_to_shm,
     render_to_texture, render_to_vec, shaders, RenderTarget,
 };
+use crate::ui::bind_mode_indicator::BindModeIndicator;
+use crate::ui::color_picker_loupe::{self, ColorPickerLoupe};
 use crate::ui::config_error_notification::ConfigErrorNotification;
 use crate::ui::exit_confirm_dialog::{ExitConfirmDialog, ExitConfirmDialogRenderElement};
+use crate::ui::gesture_debug_overlay::GestureDebugOverlay;
+use crate::ui::gesture_progress::GestureProgress;
 use crate::ui::hotkey_overlay::HotkeyOverlay;
+use crate::ui::magnifier::Magnifier;
 use crate::ui::mru::{MruCloseRequest, WindowMruUi, WindowMruUiRenderElement};
+use crate::ui::resize_mode_hint::ResizeModeHint;
 use crate::ui::screen_transition::{self, ScreenTransition};
 use crate::ui::screenshot_ui::{OutputScreenshot, ScreenshotUi, ScreenshotUiRenderElement};
 use crate::utils::scale::{closest_representable_scale, guess_monitor_scale};
@@ -180,7 +204,9 @@ use crate::utils::{
     send_scale_transform, write_png_rgba8, xwayland,
 };
 use crate::window::mapped::{MappedId, WindowCastRenderElements};
-use crate::window::{InitialConfigureState, Mapped, ResolvedWindowRules, Unmapped, WindowRef};
+use crate::window::{
+    InitialConfigureState, Mapped, MinimizedWindow, ResolvedWindowRules, Unmapped, WindowRef,
+};
 
 const CLEAR_COLOR_LOCKED: [f32; 4] = [0.3, 0.1, 0.1, 1.];
 
@@ -219,6 +245,9 @@ pub struct Niri {
     /// Whether the at-startup=true window rules are active.
     pub is_at_startup: bool,
 
+    /// Active window rule profile, matched by the profile="..." window rules.
+    pub window_rule_profile: Option<String>,
+
     /// Clock for driving animations.
     pub clock: Clock,
 
@@ -236,6 +265,12 @@ pub struct Niri {
     // Windows which don't have a buffer attached yet.
     pub unmapped_windows: HashMap<WlSurface, Unmapped>,
 
+    /// Windows swallowed by the windows of their child processes, keyed by the child window.
+    pub swallowed_windows: HashMap<Window, Mapped>,
+
+    /// Minimized windows, in the order they were minimized.
+    pub minimized_windows: Vec<MinimizedWindow>,
+
     /// Layer surfaces which don't have a buffer attached yet.
     pub unmapped_layer_surfaces: HashSet<WlSurface>,
 
@@ -263,6 +298,11 @@ pub struct Niri {
     /// Libinput guarantees that the lid switch starts in open state, and if it was closed during
     /// startup, libinput will immediately send a closed event.
     pub is_lid_closed: bool,
+    /// Whether a convertible laptop is in tablet mode.
+    ///
+    /// Like with the lid switch, libinput sends an event right away if the laptop starts in
+    /// tablet mode.
+    pub is_tablet_mode: bool,
 
     pub devices: HashSet<input::Device>,
     pub tablets: HashMap<input::Device, TabletData>,
@@ -325,6 +365,15 @@ pub struct Niri {
     pub suppressed_buttons: HashSet<u32>,
     pub bind_cooldown_timers: HashMap<Key, RegistrationToken>,
     pub bind_repeat_timer: Option<RegistrationToken>,
+    /// Active bind mode, if any; `None` is the default mode with the regular binds.
+    pub bind_mode: Option<String>,
+    /// Active keyboard resize mode, if any.
+    pub resize_mode: Option<ResizeMode>,
+    pub accessibility_keys: AccessibilityKeys,
+    pub modifier_tap: ModifierTapTracker,
+    pub double_press: DoublePressTracker,
+    pub tablet_pad: TabletPadTracker,
+    pub input_latency: InputLatencyTracker,
     pub keyboard_focus: KeyboardFocus,
     pub layer_shell_on_demand_focus: Option<LayerSurface>,
     pub idle_inhibiting_surfaces: HashSet<WlSurface>,
@@ -364,9 +413,19 @@ pub struct Niri {
     /// Used for limiting the notify to once per iteration, so that it's not spammed with high
     /// resolution mice.
     pub notified_activity_this_iteration: bool,
-    pub pointer_inside_hot_corner: bool,
+    pub hot_corner: HotCornerTracker,
     pub tablet_cursor_location: Option<Point<f64, Logical>>,
     pub gesture_swipe_3f_cumulative: Option<(f64, f64)>,
+    /// Ongoing touchpad gesture handled by the config gesture binds.
+    pub touchpad_gesture: Option<TouchpadGesture>,
+    /// Gesture sequence in progress, and the timer that abandons it.
+    pub gesture_sequence: GestureSequenceTracker,
+    pub gesture_sequence_timer: Option<RegistrationToken>,
+    /// Gesture bind changes made over IPC, kept across config reloads.
+    pub gesture_overrides: GestureOverrides,
+    /// Touchscreen contacts and the multi-finger gesture recognized from them.
+    pub touchscreen_gesture: TouchscreenGesture,
+    pub touch_edge_swipe: TouchEdgeSwipe,
     pub overview_scroll_swipe_gesture: ScrollSwipeGesture,
     pub vertical_wheel_tracker: ScrollTracker,
     pub horizontal_wheel_tracker: ScrollTracker,
@@ -383,8 +442,16 @@ pub struct Niri {
 
     pub screenshot_ui: ScreenshotUi,
     pub config_error_notification: ConfigErrorNotification,
+    /// Error from the last config load, if it failed.
+    pub config_error: Option<String>,
     pub hotkey_overlay: HotkeyOverlay,
     pub exit_confirm_dialog: ExitConfirmDialog,
+    pub gesture_debug_overlay: GestureDebugOverlay,
+    pub gesture_progress: GestureProgress,
+    pub bind_mode_indicator: BindModeIndicator,
+    pub resize_mode_hint: ResizeModeHint,
+    pub magnifier: Magnifier,
+    pub color_picker_loupe: ColorPickerLoupe,
 
     pub window_mru_ui: WindowMruUi,
     pub pending_mru_commit: Option<PendingMruCommit>,
@@ -403,8 +470,11 @@ pub struct Niri {
     pub a11y: A11y,
     #[cfg(feature = "dbus")]
     pub inhibit_power_key_fd: Option<zbus::zvariant::OwnedFd>,
+    #[cfg(feature = "dbus")]
+    pub compositor_signal_state: CompositorSignalState,
 
     pub ipc_server: Option<IpcServer>,
+    pub hook_state: HookState,
     pub ipc_outputs_changed: bool,
 
     pub satellite: Option<Satellite>,
@@ -542,8 +612,8 @@ pub struct PointContents {
     pub window: Option<(Window, HitType)>,
     // If surface belongs to a layer surface, this is that layer surface.
     pub layer: Option<LayerSurface>,
-    // Pointer is over a hot corner.
-    pub hot_corner: bool,
+    // Hot corner under point.
+    pub hot_corner: Option<Corner>,
 }
 
 #[derive(Debug, Default)]
@@ -736,6 +806,10 @@ impl State {
             self.niri.display_handle.flush_clients().unwrap();
         }
 
+        if self.niri.input_latency.has_unflushed() {
+            self.niri.input_latency.flushed(get_monotonic_time());
+        }
+
         #[cfg(feature = "dbus")]
         self.niri.update_locked_hint();
 
@@ -757,6 +831,16 @@ impl State {
         self.backend.on_output_config_changed(&mut self.niri);
     }
 
+    pub fn set_tablet_mode(&mut self, is_tablet_mode: bool) {
+        if self.niri.is_tablet_mode == is_tablet_mode {
+            return;
+        }
+
+        debug!("tablet mode {}", if is_tablet_mode { "on" } else { "off" });
+        self.niri.is_tablet_mode = is_tablet_mode;
+        self.ipc_refresh_tablet_mode();
+    }
+
     fn refresh(&mut self) {
         let _span = tracy_client::span!("State::refresh");
 
@@ -794,6 +878,9 @@ impl State {
         self.niri.refresh_window_rules();
         self.refresh_ipc_outputs();
         self.ipc_refresh_layout();
+        self.refresh_hooks();
+        #[cfg(feature = "dbus")]
+        self.niri.refresh_compositor_signals();
         self.ipc_refresh_keyboard_layout_index();
 
         // Needs to be called after updating the keyboard focus.
@@ -955,6 +1042,28 @@ impl State {
         self.niri.queue_redraw_all();
     }
 
+    /// Set a mark on the window with this id, taking it away from the window that had it before.
+    pub fn set_window_mark(&mut self, id: u64, mark: char) {
+        let exists = self
+            .niri
+            .layout
+            .workspaces()
+            .any(|(_, _, ws)| ws.windows().any(|win| win.id().get() == id));
+        if !exists {
+            return;
+        }
+
+        for ws in self.niri.layout.workspaces_mut() {
+            for win in ws.windows_mut() {
+                if win.id().get() == id {
+                    win.add_mark(mark);
+                } else {
+                    win.remove_mark(mark);
+                }
+            }
+        }
+    }
+
     pub fn confirm_mru(&mut self) {
         if let Some(window) = self.niri.close_mru(MruCloseRequest::Confirm) {
             self.focus_window(&window);
@@ -1374,12 +1483,13 @@ impl State {
         }
     }
 
-    pub fn reload_config(&mut self, config: Result<Config, ()>) {
+    pub fn reload_config(&mut self, config: Result<Config, String>) {
         let _span = tracy_client::span!("State::reload_config");
 
         let mut config = match config {
             Ok(config) => config,
-            Err(()) => {
+            Err(err) => {
+                self.niri.config_error = Some(err);
                 self.niri.config_error_notification.show();
                 self.niri.queue_redraw_all();
 
@@ -1390,6 +1500,7 @@ impl State {
             }
         };
 
+        self.niri.config_error = None;
         self.niri.config_error_notification.hide();
 
         // Find & orphan removed named workspaces.
@@ -1463,6 +1574,7 @@ impl State {
             || config.input.trackpoint != old_config.input.trackpoint
             || config.input.tablet != old_config.input.tablet
             || config.input.touch != old_config.input.touch
+            || config.input.devices != old_config.input.devices
         {
             libinput_config_changed = true;
         }
@@ -1482,6 +1594,15 @@ impl State {
         }
 
         let binds_changed = config.binds != old_config.binds;
+
+        // Go back to the regular binds if the active mode is gone.
+        if let Some(name) = &self.niri.bind_mode {
+            if config.bind_modes.find(name).is_none() {
+                self.niri.bind_mode = None;
+                self.niri.queue_redraw_all();
+            }
+        }
+
         let new_mod_key = self.backend.mod_key(&config);
         if new_mod_key != self.backend.mod_key(&old_config) || binds_changed {
             self.niri
@@ -1545,6 +1666,11 @@ impl State {
             output_config_changed = true;
         }
 
+        // Start over with fresh statistics every time the tracing is turned on.
+        if config.debug.trace_input_latency && !old_config.debug.trace_input_latency {
+            self.niri.input_latency.reset();
+        }
+
         // FIXME: move backdrop rendering into layout::Monitor, then this will become unnecessary.
         if config.overview.backdrop_color != old_config.overview.backdrop_color {
             output_config_changed = true;
@@ -1909,6 +2035,9 @@ impl State {
 
         let new_config = self.backend.ipc_outputs().lock().unwrap().clone();
         self.niri.output_management_state.notify_changes(new_config);
+
+        self.refresh_output_hooks();
+        self.ipc_refresh_outputs();
     }
 
     pub fn open_screenshot_ui(&mut self, show_pointer: bool, path: Option<String>) {
@@ -2416,6 +2545,30 @@ impl State {
         }
     }
 
+    #[cfg(feature = "dbus")]
+    pub fn on_compositor_msg(&mut self, msg: CompositorToNiri) {
+        let layout = &self.niri.layout;
+        match msg {
+            CompositorToNiri::GetFocusedWindow(tx) => {
+                let window = compositor_focused_window(layout).unwrap_or_default();
+                if let Err(err) = tx.send_blocking(window) {
+                    warn!("error sending focused window to the compositor interface: {err:?}");
+                }
+            }
+            CompositorToNiri::GetWorkspaces(tx) => {
+                let workspaces = compositor_workspaces(layout);
+                if let Err(err) = tx.send_blocking(workspaces) {
+                    warn!("error sending workspaces to the compositor interface: {err:?}");
+                }
+            }
+            CompositorToNiri::DoAction(action) => {
+                // Same as for IPC actions, let workspace clean-up and such run first.
+                self.niri.advance_animations();
+                self.do_action(niri_config::Action::from(action), false);
+            }
+        }
+    }
+
     #[cfg(feature = "dbus")]
     pub fn on_login1_msg(&mut self, msg: Login1ToNiri) {
         let Login1ToNiri::LidClosedChanged(is_closed) = msg;
@@ -2481,7 +2634,11 @@ impl Niri {
         let compositor_state = CompositorState::new_v6::<State>(&display_handle);
         let xdg_shell_state = XdgShellState::new_with_capabilities::<State>(
             &display_handle,
-            [WmCapabilities::Fullscreen, WmCapabilities::Maximize],
+            [
+                WmCapabilities::Fullscreen,
+                WmCapabilities::Maximize,
+                WmCapabilities::Minimize,
+            ],
         );
         let xdg_decoration_state =
             XdgDecorationState::new_with_filter::<State, _>(&display_handle, |client| {
@@ -2726,6 +2883,7 @@ impl Niri {
             is_session_instance,
             start_time: Instant::now(),
             is_at_startup: true,
+            window_rule_profile: None,
             clock: animation_clock,
 
             layout,
@@ -2733,6 +2891,8 @@ impl Niri {
             sorted_outputs: Vec::default(),
             output_state: HashMap::new(),
             unmapped_windows: HashMap::new(),
+            swallowed_windows: HashMap::new(),
+            minimized_windows: Vec::new(),
             unmapped_layer_surfaces: HashSet::new(),
             mapped_layer_surfaces: HashMap::new(),
             root_surface: HashMap::new(),
@@ -2741,6 +2901,7 @@ impl Niri {
             blocker_cleared_rx,
             monitors_active: true,
             is_lid_closed: false,
+            is_tablet_mode: false,
 
             devices: HashSet::new(),
             tablets: HashMap::new(),
@@ -2784,6 +2945,13 @@ impl Niri {
             suppressed_buttons: HashSet::new(),
             bind_cooldown_timers: HashMap::new(),
             bind_repeat_timer: Option::default(),
+            bind_mode: None,
+            resize_mode: None,
+            accessibility_keys: AccessibilityKeys::default(),
+            modifier_tap: ModifierTapTracker::default(),
+            double_press: DoublePressTracker::default(),
+            tablet_pad: TabletPadTracker::default(),
+            input_latency: InputLatencyTracker::default(),
             presentation_state,
             security_context_state,
             gamma_control_manager_state,
@@ -2808,9 +2976,15 @@ impl Niri {
             pointer_inactivity_timer: None,
             pointer_inactivity_timer_got_reset: false,
             notified_activity_this_iteration: false,
-            pointer_inside_hot_corner: false,
+            hot_corner: HotCornerTracker::default(),
             tablet_cursor_location: None,
             gesture_swipe_3f_cumulative: None,
+            touchpad_gesture: None,
+            gesture_sequence: GestureSequenceTracker::default(),
+            gesture_overrides: GestureOverrides::default(),
+            gesture_sequence_timer: None,
+            touchscreen_gesture: TouchscreenGesture::default(),
+            touch_edge_swipe: TouchEdgeSwipe::default(),
             overview_scroll_swipe_gesture: ScrollSwipeGesture::new(),
             vertical_wheel_tracker: ScrollTracker::new(120),
             horizontal_wheel_tracker: ScrollTracker::new(120),
@@ -2827,8 +3001,15 @@ impl Niri {
 
             screenshot_ui,
             config_error_notification,
+            config_error: None,
             hotkey_overlay,
             exit_confirm_dialog,
+            gesture_debug_overlay: GestureDebugOverlay::default(),
+            gesture_progress: GestureProgress::default(),
+            bind_mode_indicator: BindModeIndicator::default(),
+            resize_mode_hint: ResizeModeHint::default(),
+            magnifier: Magnifier::default(),
+            color_picker_loupe: ColorPickerLoupe::default(),
 
             window_mru_ui,
             pending_mru_commit: None,
@@ -2847,8 +3028,11 @@ impl Niri {
             a11y,
             #[cfg(feature = "dbus")]
             inhibit_power_key_fd: None,
+            #[cfg(feature = "dbus")]
+            compositor_signal_state: CompositorSignalState::default(),
 
             ipc_server,
+            hook_state: HookState::default(),
             ipc_outputs_changed: false,
 
             satellite: None,
@@ -3129,6 +3313,7 @@ impl Niri {
         self.global_space.unmap_output(output);
         self.reposition_outputs(None);
         self.gamma_control_manager_state.output_removed(output);
+        self.input_latency.output_removed(output);
 
         let state = self.output_state.remove(output).unwrap();
 
@@ -3279,17 +3464,30 @@ impl Niri {
         Some((output, pos_within_output))
     }
 
-    fn is_inside_hot_corner(&self, output: &Output, pos: Point<f64, Logical>) -> bool {
+    /// Returns the hot corners settings for the output.
+    pub fn hot_corners(&self, output: &Output) -> HotCorners {
+        self.with_hot_corners(output, HotCorners::clone)
+    }
+
+    fn with_hot_corners<T>(&self, output: &Output, f: impl FnOnce(&HotCorners) -> T) -> T {
         let config = self.config.borrow();
         let hot_corners = output
             .user_data()
             .get::<OutputName>()
             .and_then(|name| config.outputs.find(name))
-            .and_then(|c| c.hot_corners)
-            .unwrap_or(config.gestures.hot_corners);
+            .and_then(|c| c.hot_corners.as_ref())
+            .unwrap_or(&config.gestures.hot_corners);
+        f(hot_corners)
+    }
 
-        if hot_corners.off {
-            return false;
+    /// Returns the enabled hot corner at the position, if any.
+    fn hot_corner_at(&self, output: &Output, pos: Point<f64, Logical>) -> Option<Corner> {
+        let in_fullscreen = self.with_hot_corners(output, |c| c.in_fullscreen);
+        if !in_fullscreen {
+            let mon = self.layout.monitor_for_output(output)?;
+            if mon.render_above_top_layer() {
+                return None;
+            }
         }
 
         // Use size from the ceiled output geometry, since that's what we currently use for pointer
@@ -3301,25 +3499,18 @@ impl Niri {
             Rectangle::new(corner, Size::new(1., 1.)).contains(pos)
         };
 
-        if hot_corners.top_right && contains(Point::new(size.w - 1., 0.)) {
-            return true;
-        }
-        if hot_corners.bottom_left && contains(Point::new(0., size.h - 1.)) {
-            return true;
-        }
-        if hot_corners.bottom_right && contains(Point::new(size.w - 1., size.h - 1.)) {
-            return true;
-        }
-
-        // If the user didn't explicitly set any corners, we default to top-left.
-        if (hot_corners.top_left
-            || !(hot_corners.top_right || hot_corners.bottom_right || hot_corners.bottom_left))
-            && contains(Point::new(0., 0.))
-        {
-            return true;
-        }
-
-        false
+        let corners = [
+            (Corner::TopLeft, Point::new(0., 0.)),
+            (Corner::TopRight, Point::new(size.w - 1., 0.)),
+            (Corner::BottomLeft, Point::new(0., size.h - 1.)),
+            (Corner::BottomRight, Point::new(size.w - 1., size.h - 1.)),
+        ];
+        self.with_hot_corners(output, |hot_corners| {
+            corners
+                .into_iter()
+                .find(|(corner, loc)| contains(*loc) && hot_corners.action(*corner).is_some())
+                .map(|(corner, _)| corner)
+        })
     }
 
     pub fn is_sticky_obscured_under(
@@ -3360,15 +3551,15 @@ impl Niri {
             return true;
         }
 
+        if self.hot_corner_at(output, pos_within_output).is_some() {
+            return true;
+        }
+
         let mon = self.layout.monitor_for_output(output).unwrap();
         if mon.render_above_top_layer() {
             return false;
         }
 
-        if self.is_inside_hot_corner(output, pos_within_output) {
-            return true;
-        }
-
         if layer_popup_under(Layer::Top) || layer_toplevel_under(Layer::Top) {
             return true;
         }
@@ -3629,6 +3820,11 @@ impl Niri {
 
         let is_overview_open = self.layout.is_overview_open();
 
+        if let Some(corner) = self.hot_corner_at(output, pos_within_output) {
+            rv.hot_corner = Some(corner);
+            return rv;
+        }
+
         // When rendering above the top layer, we put the regular monitor elements first.
         // Otherwise, we will render all layer-shell pop-ups and the top layer on top.
         if mon.render_above_top_layer() {
@@ -3642,11 +3838,6 @@ impl Niri {
                 .or_else(|| layer_toplevel_under(Layer::Bottom))
                 .or_else(|| layer_toplevel_under(Layer::Background));
         } else {
-            if self.is_inside_hot_corner(output, pos_within_output) {
-                rv.hot_corner = true;
-                return rv;
-            }
-
             under = under
                 .or_else(|| layer_popup_under(Layer::Top))
                 .or_else(|| layer_toplevel_under(Layer::Top));
@@ -4240,11 +4431,12 @@ impl Niri {
 
         let config = self.config.borrow();
         let window_rules = &config.window_rules;
+        let profile = self.window_rule_profile.as_deref();
 
         let mut windows = vec![];
         let mut outputs = HashSet::new();
         self.layout.with_windows_mut(|mapped, output| {
-            if mapped.recompute_window_rules_if_needed(window_rules, self.is_at_startup) {
+            if mapped.recompute_window_rules_if_needed(window_rules, self.is_at_startup, profile) {
                 windows.push(mapped.window.clone());
 
                 if let Some(output) = output {
@@ -4429,7 +4621,96 @@ impl Niri {
             self.render_pointer(renderer, output, &mut |elem| push(elem.into()));
         }
 
-        // Next, the screen transition texture.
+        // Next, the color picker loupe.
+        if target == RenderTarget::Output && self.pick_color.is_some() {
+            self.render_color_picker_loupe(renderer, output, push);
+        }
+
+        // Next, the gesture debug overlay.
+        if self.gesture_debug_overlay.is_open() {
+            let text = self.gesture_debug_text();
+            let size = output_size(output);
+            let scale = output_scale.x;
+            if let Some(element) = self
+                .gesture_debug_overlay
+                .render(renderer, size, scale, &text)
+            {
+                push(element.into());
+            }
+        }
+
+        // Next, the gesture progress indicator on the output under the cursor.
+        if target == RenderTarget::Output && self.config.borrow().gestures.progress_indicator {
+            let progress = self.touchpad_gesture.as_ref().and_then(|g| g.progress());
+            if let Some((direction, progress)) = progress {
+                if self.output_under_cursor().as_ref() == Some(output) {
+                    let size = output_size(output);
+                    let scale = output_scale.x;
+                    if let Some(element) = self
+                        .gesture_progress
+                        .render(renderer, size, scale, direction, progress)
+                    {
+                        push(element.into());
+                    }
+                }
+            }
+        }
+
+        // Next, the name of the active bind mode.
+        if target == RenderTarget::Output {
+            if let Some(name) = &self.bind_mode {
+                let size = output_size(output);
+                let scale = output_scale.x;
+                if let Some(element) = self.bind_mode_indicator.render(renderer, size, scale, name)
+                {
+                    push(element.into());
+                }
+            }
+        }
+
+        // Next, the hint of the keyboard resize mode on the output of its window.
+        if target == RenderTarget::Output {
+            if let Some(mode) = &self.resize_mode {
+                let window = self.layout.windows().find(|(_, m)| m.id() == mode.window);
+                if let Some((Some(window_output), mapped)) = window {
+                    if window_output == output {
+                        let text =
+                            resize_mode::hint_text(mapped, &self.config.borrow().resize_mode);
+                        let size = output_size(output);
+                        let scale = output_scale.x;
+                        if let Some(element) =
+                            self.resize_mode_hint.render(renderer, size, scale, &text)
+                        {
+                            push(element.into());
+                        }
+                    }
+                }
+            }
+        }
+
+        // Everything else goes through the magnifier when it's zoomed in on this output.
+        if target == RenderTarget::Output {
+            if let Some(zoom) = self.magnifier.zoom(output) {
+                if self.render_magnified(renderer, output, zoom, push) {
+                    return;
+                }
+            }
+        }
+
+        self.render_contents(renderer, output, target, push);
+    }
+
+    /// Renders the output contents under the pointer and the debug overlays.
+    fn render_contents<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output: &Output,
+        target: RenderTarget,
+        push: &mut dyn FnMut(OutputRenderElements<R>),
+    ) {
+        let output_scale = Scale::from(output.current_scale().fractional_scale());
+
+        // The screen transition texture goes on top.
         {
             let state = self.output_state.get(output).unwrap();
             if let Some(transition) = &state.screen_transition {
@@ -4623,6 +4904,107 @@ impl Niri {
         push(backdrop);
     }
 
+    /// Renders the output contents zoomed in around the cursor.
+    ///
+    /// Returns `false` if rendering to the offscreen failed.
+    fn render_magnified<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output: &Output,
+        zoom: f64,
+        push: &mut dyn FnMut(OutputRenderElements<R>),
+    ) -> bool {
+        let output_scale = Scale::from(output.current_scale().fractional_scale());
+        let output_geo = Rectangle::from_size(output_size(output));
+        let crop = output_geo.to_physical_precise_round(output_scale);
+
+        // Crop to the output to keep the offscreen texture no bigger than the output.
+        let renderer = renderer.as_gles_renderer();
+        let mut elements = Vec::new();
+        self.render_contents(renderer, output, RenderTarget::Output, &mut |elem| {
+            if let Some(elem) = CropRenderElement::from_element(elem, output_scale, crop) {
+                elements.push(elem);
+            }
+        });
+
+        let offscreen = self.magnifier.offscreen();
+        match offscreen.render(renderer, output_scale, &elements) {
+            Ok((elem, _sync, data)) => {
+                self.magnifier.set_offscreen_data(Some(data));
+
+                let pointer = self.seat.get_pointer().unwrap();
+                let global_geo = self.global_space.output_geometry(output).unwrap();
+                let cursor = pointer.current_location() - global_geo.loc.to_f64();
+                let cursor = output_geo.contains(cursor).then_some(cursor);
+                let focus = self.magnifier.focus(cursor);
+
+                let origin = focus.to_physical_precise_round(output_scale);
+                push(RescaleRenderElement::from_element(elem, origin, zoom).into());
+                true
+            }
+            Err(err) => {
+                warn!("error rendering output to offscreen for the magnifier: {err:?}");
+                false
+            }
+        }
+    }
+
+    /// Renders the color picker loupe around the cursor, if the cursor is on this output.
+    fn render_color_picker_loupe<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output: &Output,
+        push: &mut dyn FnMut(OutputRenderElements<R>),
+    ) {
+        let output_scale = Scale::from(output.current_scale().fractional_scale());
+        let output_geo = Rectangle::from_size(output_size(output));
+
+        let pointer = self.seat.get_pointer().unwrap();
+        let global_geo = self.global_space.output_geometry(output).unwrap();
+        let cursor = pointer.current_location() - global_geo.loc.to_f64();
+        if !output_geo.contains(cursor) {
+            return;
+        }
+
+        let crop = output_geo.to_physical_precise_round(output_scale);
+        let renderer = renderer.as_gles_renderer();
+        let mut elements = Vec::new();
+        self.render_contents(renderer, output, RenderTarget::Output, &mut |elem| {
+            if let Some(elem) = CropRenderElement::from_element(elem, output_scale, crop) {
+                elements.push(elem);
+            }
+        });
+
+        let loupe = &self.color_picker_loupe;
+        let elem = match loupe.offscreen().render(renderer, output_scale, &elements) {
+            Ok((elem, _sync, _data)) => elem,
+            Err(err) => {
+                warn!("error rendering output to offscreen for the color picker: {err:?}");
+                return;
+            }
+        };
+
+        let origin = cursor.to_physical_precise_round(output_scale);
+        let elem = RescaleRenderElement::from_element(elem, origin, color_picker_loupe::ZOOM);
+
+        let geo = ColorPickerLoupe::geometry(cursor);
+        let crop = geo.to_physical_precise_round(output_scale);
+        if let Some(elem) = CropRenderElement::from_element(elem, output_scale, crop) {
+            push(elem.into());
+        }
+
+        let border_loc = ColorPickerLoupe::border_loc(geo);
+        let border =
+            SolidColorRenderElement::from_buffer(loupe.border(), border_loc, 1., Kind::Unspecified);
+        push(border.into());
+    }
+
+    pub fn reset_magnifier(&mut self) {
+        if let Some(output) = self.magnifier.reset() {
+            self.queue_redraw(&output);
+        }
+    }
+
     fn layers_in_render_order<'a>(
         &'a self,
         layer_map: &'a LayerMap,
@@ -4715,6 +5097,10 @@ impl Niri {
             res = backend.render(self, output, target_presentation_time);
         }
 
+        if res == RenderResult::Submitted {
+            self.input_latency.rendered(output);
+        }
+
         let is_locked = self.is_locked();
         let state = self.output_state.get_mut(output).unwrap();
 
@@ -4877,6 +5263,21 @@ impl Niri {
             );
         }
 
+        // Surfaces seen through the magnifier are visible whenever its offscreen is.
+        let magnified_states;
+        let render_element_states = match &*self.magnifier.offscreen_data() {
+            Some(data)
+                if self.magnifier.zoom(output).is_some()
+                    && render_element_states.element_was_presented(data.id.clone()) =>
+            {
+                let mut states = render_element_states.clone();
+                states.states.extend(data.states.states.clone());
+                magnified_states = states;
+                &magnified_states
+            }
+            _ => render_element_states,
+        };
+
         // We're only updating the current output's windows and layer surfaces. This should be fine
         // as in niri they can only be rendered on a single output at a time.
         //
@@ -5692,6 +6093,54 @@ impl Niri {
         self.queue_redraw_all();
     }
 
+    /// Switches to the bind mode with this name, or back to the regular binds for `default`.
+    pub fn switch_bind_mode(&mut self, name: &str) {
+        let mode = if name == "default" {
+            None
+        } else if self.config.borrow().bind_modes.find(name).is_some() {
+            Some(name.to_owned())
+        } else {
+            warn!("bind mode {name:?} doesn't exist");
+            return;
+        };
+
+        if self.bind_mode != mode {
+            self.bind_mode = mode;
+            self.queue_redraw_all();
+        }
+    }
+
+    pub fn debug_toggle_gesture_overlay(&mut self) {
+        self.gesture_debug_overlay.toggle();
+        self.queue_redraw_all();
+    }
+
+    /// Describes the state of all gesture recognizers for the gesture debug overlay.
+    fn gesture_debug_text(&self) -> String {
+        let mut text = String::from("Touchpad: ");
+        match &self.touchpad_gesture {
+            Some(gesture) => text += &gesture.debug_text(),
+            None => text += "none",
+        }
+        if let Some((cx, cy)) = self.gesture_swipe_3f_cumulative {
+            text += &format!("\nBuilt-in 3-finger swipe: cumulative ({cx:.1}, {cy:.1})");
+        }
+
+        text += "\nTouchscreen: ";
+        match self.touchscreen_gesture.debug_text() {
+            Some(gesture) => text += &gesture,
+            None => text += "none",
+        }
+
+        text += "\nSequence: ";
+        match self.gesture_sequence.debug_text() {
+            Some(steps) => text += &steps,
+            None => text += "none",
+        }
+
+        text
+    }
+
     pub fn capture_screenshots<'a>(
         &'a self,
         renderer: &'a mut GlesRenderer,
@@ -5768,10 +6217,15 @@ impl Niri {
         })
     }
 
+    /// Takes a screenshot of the output.
+    ///
+    /// If `region` is set, only that part of the output is taken. The region is in output-local
+    /// logical coordinates.
     pub fn screenshot(
         &mut self,
         renderer: &mut GlesRenderer,
         output: &Output,
+        region: Option<Rectangle<i32, Logical>>,
         write_to_disk: bool,
         include_pointer: bool,
         path: Option<String>,
@@ -5785,23 +6239,37 @@ impl Niri {
         let size = transform.transform_size(size);
 
         let scale = Scale::from(output.current_scale().fractional_scale());
+        let geo = match region {
+            Some(region) => {
+                let region = region.to_physical_precise_round(scale);
+                let geo = region
+                    .intersection(Rectangle::from_size(size))
+                    .context("region is outside the output")?;
+                ensure!(!geo.is_empty(), "region is empty");
+                geo
+            }
+            None => Rectangle::from_size(size),
+        };
+
         let elements = self.render::<GlesRenderer>(
             renderer,
             output,
             include_pointer,
             RenderTarget::ScreenCapture,
         );
-        let elements = elements.iter().rev();
+        let elements = elements.iter().rev().map(|elem| {
+            RelocateRenderElement::from_element(elem, geo.loc.upscale(-1), Relocate::Relative)
+        });
         let pixels = render_to_vec(
             renderer,
-            size,
+            geo.size,
             scale,
             Transform::Normal,
             Fourcc::Abgr8888,
             elements,
         )?;
 
-        self.save_screenshot(size, pixels, write_to_disk, path)
+        self.save_screenshot(geo.size, pixels, write_to_disk, path)
             .context("error saving screenshot")
     }
 
@@ -5868,6 +6336,23 @@ impl Niri {
             .context("error saving screenshot")
     }
 
+    /// Copies the color to the clipboard in the hex format.
+    pub fn copy_color(&mut self, color: niri_ipc::PickedColor) {
+        let [r, g, b] = color.rgb.map(|v| (v.clamp(0., 1.) * 255.).round() as u8);
+        let hex = format!("#{r:02x}{g:02x}{b:02x}");
+
+        set_data_device_selection(
+            &self.display_handle,
+            &self.seat,
+            vec![
+                String::from("text/plain;charset=utf-8"),
+                String::from("text/plain"),
+                String::from("UTF8_STRING"),
+            ],
+            Arc::from(hex.into_bytes()),
+        );
+    }
+
     pub fn save_screenshot(
         &self,
         size: Size<i32, Physical>,
@@ -6362,6 +6847,74 @@ impl Niri {
         root.clone()
     }
 
+    /// Queues the org.niri.Compositor signals for the changes since the previous refresh.
+    #[cfg(feature = "dbus")]
+    pub fn refresh_compositor_signals(&mut self) {
+        use crate::utils::with_toplevel_role;
+
+        let _span = tracy_client::span!("Niri::refresh_compositor_signals");
+
+        let Some(signals) = self
+            .dbus
+            .as_ref()
+            .and_then(|dbus| dbus.compositor_signals.as_ref())
+        else {
+            return;
+        };
+
+        let seen = &mut self.compositor_signal_state;
+        let layout = &self.layout;
+        let focused_ws_id = layout.active_workspace().map(|ws| ws.id());
+
+        // Compare in place to avoid allocating on every refresh.
+        let mut workspaces_changed = false;
+        let mut seen_workspaces = seen.workspaces.iter();
+        for (mon, ws_idx, ws) in layout.workspaces() {
+            let Some(props) = seen_workspaces.next() else {
+                workspaces_changed = true;
+                break;
+            };
+
+            if props.id != ws.id().get()
+                || props.idx != u8::try_from(ws_idx + 1).unwrap_or(u8::MAX)
+                || props.name.as_ref() != ws.name()
+                || props.output.as_ref() != mon.map(|mon| mon.output_name())
+                || props.is_active != mon.is_some_and(|mon| mon.active_workspace_idx() == ws_idx)
+                || props.is_focused != (Some(ws.id()) == focused_ws_id)
+            {
+                workspaces_changed = true;
+                break;
+            }
+        }
+        workspaces_changed |= seen_workspaces.next().is_some();
+
+        let focused_window_changed = match (layout.focus(), &seen.focused_window) {
+            (None, None) => false,
+            (Some(mapped), Some(props)) => {
+                props.id != Some(mapped.id().get())
+                    || props.workspace_id != focused_ws_id.map(|id| id.get())
+                    || with_toplevel_role(mapped.toplevel(), |role| {
+                        props.title != role.title || props.app_id != role.app_id
+                    })
+            }
+            _ => true,
+        };
+
+        // Workspaces go first, so that the focused window signal can refer to a known workspace.
+        if workspaces_changed {
+            seen.workspaces = compositor_workspaces(layout);
+            if let Err(err) = signals.send(CompositorChange::Workspaces) {
+                warn!("error sending Workspaces change to the signal emitter: {err:?}");
+            }
+        }
+        if focused_window_changed {
+            seen.focused_window = compositor_focused_window(layout);
+            if let Err(err) = signals.send(CompositorChange::FocusedWindow) {
+                warn!("error sending FocusedWindow change to the signal emitter: {err:?}");
+            }
+        }
+    }
+
     #[cfg(feature = "dbus")]
     pub fn on_ipc_outputs_changed(&self) {
         let _span = tracy_client::span!("Niri::on_ipc_outputs_changed");
@@ -6545,15 +7098,17 @@ impl Niri {
                     window_rules,
                     WindowRef::Unmapped(unmapped),
                     self.is_at_startup,
+                    self.window_rule_profile.as_deref(),
                 );
                 if let InitialConfigureState::Configured { rules, .. } = &mut unmapped.state {
                     *rules = new_rules;
                 }
             }
 
+            let profile = self.window_rule_profile.as_deref();
             let mut windows = vec![];
             self.layout.with_windows_mut(|mapped, _| {
-                if mapped.recompute_window_rules(window_rules, self.is_at_startup) {
+                if mapped.recompute_window_rules(window_rules, self.is_at_startup, profile) {
                     windows.push(mapped.window.clone());
                 }
             });
@@ -6564,12 +7119,54 @@ impl Niri {
             changed
         };
 
+        self.revoke_disallowed_keyboard_shortcuts_inhibitors();
+
         if changed {
             // FIXME: granular.
             self.queue_redraw_all();
         }
     }
 
+    /// Switches the active window rule profile and re-evaluates the rules of existing windows.
+    pub fn set_window_rule_profile(&mut self, profile: Option<String>) {
+        if self.window_rule_profile == profile {
+            return;
+        }
+
+        self.window_rule_profile = profile;
+        self.recompute_window_rules();
+    }
+
+    /// Returns whether the window rules allow this surface to inhibit keyboard shortcuts.
+    pub fn allows_keyboard_shortcuts_inhibit(&self, surface: &WlSurface) -> bool {
+        let rules = if let Some((mapped, _)) = self.layout.find_window_and_output(surface) {
+            Some(mapped.rules())
+        } else if let Some(unmapped) = self.unmapped_windows.get(surface) {
+            match &unmapped.state {
+                InitialConfigureState::Configured { rules, .. } => Some(rules),
+                InitialConfigureState::NotConfigured { .. } => None,
+            }
+        } else {
+            None
+        };
+
+        rules
+            .and_then(|rules| rules.allow_keyboard_shortcuts_inhibit)
+            .unwrap_or(true)
+    }
+
+    /// Inactivates the inhibitors that the window rules no longer allow.
+    ///
+    /// Inhibitors that became allowed stay inactive, since they could have been turned off on
+    /// purpose with the toggle action.
+    fn revoke_disallowed_keyboard_shortcuts_inhibitors(&self) {
+        for (surface, inhibitor) in &self.keyboard_shortcuts_inhibiting_surfaces {
+            if inhibitor.is_active() && !self.allows_keyboard_shortcuts_inhibit(surface) {
+                inhibitor.inactivate();
+            }
+        }
+    }
+
     pub fn recompute_layer_rules(&mut self) {
         let _span = tracy_client::span!("Niri::recompute_layer_rules");
 
@@ -6703,6 +7300,39 @@ impl ClientData for ClientState {
     fn disconnected(&self, _client_id: ClientId, _reason: DisconnectReason) {}
 }
 
+#[cfg(feature = "dbus")]
+fn compositor_focused_window(layout: &Layout<Mapped>) -> Option<niri_compositor::WindowProperties> {
+    use crate::utils::with_toplevel_role;
+
+    let workspace_id = layout.active_workspace().map(|ws| ws.id().get());
+    layout.focus().map(|mapped| {
+        with_toplevel_role(mapped.toplevel(), |role| {
+            niri_compositor::WindowProperties {
+                id: Some(mapped.id().get()),
+                title: role.title.clone(),
+                app_id: role.app_id.clone(),
+                workspace_id,
+            }
+        })
+    })
+}
+
+#[cfg(feature = "dbus")]
+fn compositor_workspaces(layout: &Layout<Mapped>) -> Vec<niri_compositor::WorkspaceProperties> {
+    let focused_ws_id = layout.active_workspace().map(|ws| ws.id());
+    layout
+        .workspaces()
+        .map(|(mon, ws_idx, ws)| niri_compositor::WorkspaceProperties {
+            id: ws.id().get(),
+            idx: u8::try_from(ws_idx + 1).unwrap_or(u8::MAX),
+            name: ws.name().cloned(),
+            output: mon.map(|mon| mon.output_name().clone()),
+            is_active: mon.is_some_and(|mon| mon.active_workspace_idx() == ws_idx),
+            is_focused: Some(ws.id()) == focused_ws_id,
+        })
+        .collect()
+}
+
 fn scale_relocate_crop<E: Element>(
     elem: E,
     output_scale: Scale<f64>,
@@ -6756,6 +7386,8 @@ niri_render_elements! {
         WindowMruUi = WindowMruUiRenderElement<R>,
         ExitConfirmDialog = ExitConfirmDialogRenderElement,
         Texture = PrimaryGpuTextureRenderElement,
+        Magnified = RescaleRenderElement<OffscreenRenderElement>,
+        Loupe = CropRenderElement<RescaleRenderElement<OffscreenRenderElement>>,
         // Used for the CPU-rendered panels.
         RelocatedMemoryBuffer = RelocateRenderElement<MemoryRenderBufferRenderElement<R>>,
     }
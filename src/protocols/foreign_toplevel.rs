@@ -39,12 +39,14 @@ pub trait ForeignToplevelHandler {
     fn unset_fullscreen(&mut self, wl_surface: WlSurface);
     fn set_maximized(&mut self, wl_surface: WlSurface);
     fn unset_maximized(&mut self, wl_surface: WlSurface);
+    fn set_minimized(&mut self, wl_surface: WlSurface);
+    fn unset_minimized(&mut self, wl_surface: WlSurface);
 }
 
 struct ToplevelData {
     title: Option<String>,
     app_id: Option<String>,
-    states: ArrayVec<u32, 3>,
+    states: ArrayVec<u32, 4>,
     output: Option<Output>,
     instances: HashMap<ZwlrForeignToplevelHandleV1, Vec<WlOutput>>,
     // FIXME: parent.
@@ -85,6 +87,11 @@ pub fn refresh(state: &mut State) {
             return true;
         }
 
+        let mut minimized = state.niri.minimized_windows.iter();
+        if minimized.any(|minimized| minimized.mapped.toplevel().wl_surface() == surface) {
+            return true;
+        }
+
         for instance in data.instances.keys() {
             instance.closed();
         }
@@ -109,11 +116,25 @@ pub fn refresh(state: &mut State) {
             if state.niri.keyboard_focus.surface() == Some(wl_surface) {
                 focused = Some((mapped.window.clone(), output.cloned()));
             } else {
-                refresh_toplevel(protocol_state, wl_surface, role, cur, output, false);
+                refresh_toplevel(protocol_state, wl_surface, role, cur, output, false, false);
             }
         });
     });
 
+    // Minimized windows stay listed so that taskbars can restore them.
+    for minimized in &state.niri.minimized_windows {
+        let toplevel = minimized.mapped.toplevel();
+        let wl_surface = toplevel.wl_surface();
+        with_toplevel_role_and_current(toplevel, |role, cur| {
+            let Some(cur) = cur else {
+                error!("mapped must have had initial commit");
+                return;
+            };
+
+            refresh_toplevel(protocol_state, wl_surface, role, cur, None, false, true);
+        });
+    }
+
     // Finally, refresh the focused window.
     if let Some((window, output)) = focused {
         let toplevel = window.toplevel().expect("no X11 support");
@@ -124,7 +145,15 @@ pub fn refresh(state: &mut State) {
                 return;
             };
 
-            refresh_toplevel(protocol_state, wl_surface, role, cur, output.as_ref(), true);
+            refresh_toplevel(
+                protocol_state,
+                wl_surface,
+                role,
+                cur,
+                output.as_ref(),
+                true,
+                false,
+            );
         });
     }
 }
@@ -161,8 +190,9 @@ fn refresh_toplevel(
     current: &ToplevelState,
     output: Option<&Output>,
     has_focus: bool,
+    is_minimized: bool,
 ) {
-    let states = to_state_vec(&current.states, has_focus);
+    let states = to_state_vec(&current.states, has_focus, is_minimized);
 
     match protocol_state.toplevels.entry(wl_surface.clone()) {
         Entry::Occupied(entry) => {
@@ -394,8 +424,10 @@ where
             zwlr_foreign_toplevel_handle_v1::Request::UnsetMaximized => {
                 state.unset_maximized(surface)
             }
-            zwlr_foreign_toplevel_handle_v1::Request::SetMinimized => (),
-            zwlr_foreign_toplevel_handle_v1::Request::UnsetMinimized => (),
+            zwlr_foreign_toplevel_handle_v1::Request::SetMinimized => state.set_minimized(surface),
+            zwlr_foreign_toplevel_handle_v1::Request::UnsetMinimized => {
+                state.unset_minimized(surface)
+            }
             zwlr_foreign_toplevel_handle_v1::Request::Activate { .. } => {
                 state.activate(surface);
             }
@@ -427,7 +459,11 @@ where
     }
 }
 
-fn to_state_vec(states: &ToplevelStateSet, has_focus: bool) -> ArrayVec<u32, 3> {
+fn to_state_vec(
+    states: &ToplevelStateSet,
+    has_focus: bool,
+    is_minimized: bool,
+) -> ArrayVec<u32, 4> {
     let mut rv = ArrayVec::new();
     if states.contains(xdg_toplevel::State::Maximized) {
         rv.push(zwlr_foreign_toplevel_handle_v1::State::Maximized as u32);
@@ -447,6 +483,9 @@ fn to_state_vec(states: &ToplevelStateSet, has_focus: bool) -> ArrayVec<u32, 3>
     if has_focus {
         rv.push(zwlr_foreign_toplevel_handle_v1::State::Activated as u32);
     }
+    if is_minimized {
+        rv.push(zwlr_foreign_toplevel_handle_v1::State::Minimized as u32);
+    }
 
     rv
 }
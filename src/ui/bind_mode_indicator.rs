@@ -0,0 +1,46 @@
+use pangocairo::pango::Alignment;
+use smithay::utils::{Logical, Size};
+
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::ui::text_panel::{Anchor, TextPanel, TextPanelStyle};
+
+const STYLE: TextPanelStyle = TextPanelStyle {
+    anchor: Anchor::Top,
+    margin: 16,
+    padding: 8,
+    font: "sans 14px",
+    bold: true,
+    alignment: Alignment::Left,
+    border_color: [1., 0.6, 0.2],
+};
+
+/// Indicator showing the name of the active bind mode.
+pub struct BindModeIndicator {
+    panel: TextPanel,
+}
+
+impl BindModeIndicator {
+    pub fn new() -> Self {
+        Self {
+            panel: TextPanel::new(STYLE),
+        }
+    }
+
+    /// Renders the indicator at the top center of an output of this size.
+    pub fn render<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output_size: Size<f64, Logical>,
+        scale: f64,
+        name: &str,
+    ) -> Option<PrimaryGpuTextureRenderElement> {
+        self.panel.render(renderer, output_size, scale, name)
+    }
+}
+
+impl Default for BindModeIndicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,54 @@
+use smithay::utils::{Logical, Point, Rectangle, Size};
+
+use crate::render_helpers::offscreen::OffscreenBuffer;
+use crate::render_helpers::solid_color::SolidColorBuffer;
+
+/// How much the loupe zooms in.
+pub const ZOOM: f64 = 8.;
+
+/// Size of the loupe in logical pixels, without the border.
+const SIZE: f64 = 120.;
+
+const BORDER_WIDTH: f64 = 2.;
+const BORDER_COLOR: [f32; 4] = [1., 1., 1., 1.];
+
+/// Magnifying loupe shown around the cursor while picking a color.
+///
+/// Like the magnifier, the output is rendered to an offscreen and scaled around the cursor, but
+/// only a small square around the cursor is shown.
+#[derive(Debug)]
+pub struct ColorPickerLoupe {
+    offscreen: OffscreenBuffer,
+    border: SolidColorBuffer,
+}
+
+impl ColorPickerLoupe {
+    pub fn offscreen(&self) -> &OffscreenBuffer {
+        &self.offscreen
+    }
+
+    pub fn border(&self) -> &SolidColorBuffer {
+        &self.border
+    }
+
+    /// Returns the area of the loupe, centered on the cursor.
+    pub fn geometry(cursor: Point<f64, Logical>) -> Rectangle<f64, Logical> {
+        let size = Size::from((SIZE, SIZE));
+        Rectangle::new(cursor - Point::from((SIZE / 2., SIZE / 2.)), size)
+    }
+
+    /// Returns the location of the border behind the loupe with this geometry.
+    pub fn border_loc(geo: Rectangle<f64, Logical>) -> Point<f64, Logical> {
+        geo.loc - Point::from((BORDER_WIDTH, BORDER_WIDTH))
+    }
+}
+
+impl Default for ColorPickerLoupe {
+    fn default() -> Self {
+        let border_size = SIZE + BORDER_WIDTH * 2.;
+        Self {
+            offscreen: OffscreenBuffer::default(),
+            border: SolidColorBuffer::new((border_size, border_size), BORDER_COLOR),
+        }
+    }
+}
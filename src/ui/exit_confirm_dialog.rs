@@ -21,6 +21,7 @@ use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::solid_color::{SolidColorBuffer, SolidColorRenderElement};
 use crate::render_helpers::texture::{TextureBuffer, TextureRenderElement};
 use crate::utils::{output_size, to_physical_precise_round};
+use crate::window::mapped::MappedId;
 
 const KEY_NAME: &str = "Enter";
 const PADDING: i32 = 16;
@@ -30,6 +31,7 @@ const BACKDROP_COLOR: [f32; 4] = [0., 0., 0., 0.4];
 
 pub struct ExitConfirmDialog {
     state: State,
+    confirmation: Confirmation,
     buffers: RefCell<HashMap<NotNan<f64>, Option<MemoryBuffer>>>,
 
     clock: Clock,
@@ -43,6 +45,24 @@ niri_render_elements! {
     }
 }
 
+/// What the dialog asks to confirm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Confirmation {
+    /// Exiting niri.
+    Exit,
+    /// Killing the process of a window that doesn't respond to closing.
+    ForceClose(ForceCloseTarget),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForceCloseTarget {
+    pub window: MappedId,
+    pub pid: i32,
+    pub title: Option<String>,
+    /// Name of the process, to make it clear what the kill affects.
+    pub process_name: Option<String>,
+}
+
 struct OutputData {
     backdrop: SolidColorBuffer,
 }
@@ -56,17 +76,13 @@ enum State {
 
 impl ExitConfirmDialog {
     pub fn new(clock: Clock, config: Rc<RefCell<Config>>) -> Self {
-        let buffer = match render(1.) {
-            Ok(x) => Some(x),
-            Err(err) => {
-                warn!("error creating the exit confirm dialog: {err:?}");
-                None
-            }
-        };
+        let confirmation = Confirmation::Exit;
+        let buffers = fallback_buffers(&confirmation);
 
         Self {
             state: State::Hidden,
-            buffers: RefCell::new(HashMap::from([(NotNan::new(1.).unwrap(), buffer)])),
+            confirmation,
+            buffers,
             clock,
             config,
         }
@@ -98,7 +114,12 @@ impl ExitConfirmDialog {
     }
 
     /// Returns true if the dialog will be shown (even if it is already shown).
-    pub fn show(&mut self) -> bool {
+    pub fn show(&mut self, confirmation: Confirmation) -> bool {
+        if self.confirmation != confirmation {
+            self.buffers = fallback_buffers(&confirmation);
+            self.confirmation = confirmation;
+        }
+
         if !self.can_show() {
             return false;
         }
@@ -125,6 +146,10 @@ impl ExitConfirmDialog {
         matches!(self.state, State::Showing(_) | State::Visible)
     }
 
+    pub fn confirmation(&self) -> &Confirmation {
+        &self.confirmation
+    }
+
     pub fn advance_animations(&mut self) {
         match &mut self.state {
             State::Hidden => (),
@@ -173,7 +198,7 @@ impl ExitConfirmDialog {
 
         let buffer = buffers
             .entry(NotNan::new(scale).unwrap())
-            .or_insert_with(|| render(scale).ok());
+            .or_insert_with(|| render(&self.confirmation, scale).ok());
         let buffer = buffer.as_ref().unwrap_or(&fallback);
 
         let size = buffer.logical_size();
@@ -222,10 +247,24 @@ impl ExitConfirmDialog {
     }
 }
 
-fn render(scale: f64) -> anyhow::Result<MemoryBuffer> {
+fn fallback_buffers(
+    confirmation: &Confirmation,
+) -> RefCell<HashMap<NotNan<f64>, Option<MemoryBuffer>>> {
+    let buffer = match render(confirmation, 1.) {
+        Ok(x) => Some(x),
+        Err(err) => {
+            warn!("error creating the exit confirm dialog: {err:?}");
+            None
+        }
+    };
+
+    RefCell::new(HashMap::from([(NotNan::new(1.).unwrap(), buffer)]))
+}
+
+fn render(confirmation: &Confirmation, scale: f64) -> anyhow::Result<MemoryBuffer> {
     let _span = tracy_client::span!("exit_confirm_dialog::render");
 
-    let markup = text(true);
+    let markup = text(confirmation, true);
 
     let padding: i32 = to_physical_precise_round(scale, PADDING);
 
@@ -282,24 +321,52 @@ fn render(scale: f64) -> anyhow::Result<MemoryBuffer> {
     Ok(buffer)
 }
 
-fn text(markup: bool) -> String {
+fn text(confirmation: &Confirmation, markup: bool) -> String {
     let key = if markup {
         format!("<span face='mono' bgcolor='#2C2C2C'> {KEY_NAME} </span>")
     } else {
         String::from(KEY_NAME)
     };
 
+    let question = match confirmation {
+        Confirmation::Exit => String::from("Are you sure you want to exit niri?"),
+        Confirmation::ForceClose(target) => {
+            let title = target.title.as_deref().unwrap_or("Unknown");
+            let process = match &target.process_name {
+                Some(name) => format!("{name}, PID {}", target.pid),
+                None => format!("PID {}", target.pid),
+            };
+            let window = format!("{title}\n({process})");
+            let window = if markup {
+                format!("<b>{}</b>", pango::glib::markup_escape_text(&window))
+            } else {
+                window
+            };
+
+            format!(
+                "Force close this window by killing its process?\n\n\
+                 {window}\n\n\
+                 Unsaved data will be lost."
+            )
+        }
+    };
+
     format!(
-        "Are you sure you want to exit niri?\n\n\
+        "{question}\n\n\
          Press {key} to confirm."
     )
 }
 
 #[cfg(feature = "dbus")]
-pub fn a11y_node() -> accesskit::Node {
+pub fn a11y_node(confirmation: &Confirmation) -> accesskit::Node {
+    let label = match confirmation {
+        Confirmation::Exit => "Exit niri",
+        Confirmation::ForceClose(_) => "Force close window",
+    };
+
     let mut node = accesskit::Node::new(accesskit::Role::AlertDialog);
-    node.set_label("Exit niri");
-    node.set_description(text(false));
+    node.set_label(label);
+    node.set_description(text(confirmation, false));
     node.set_modal();
     node
 }
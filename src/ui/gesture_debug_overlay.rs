@@ -0,0 +1,60 @@
+use pangocairo::pango::Alignment;
+use smithay::utils::{Logical, Size};
+
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::ui::text_panel::{Anchor, TextPanel, TextPanelStyle};
+
+const STYLE: TextPanelStyle = TextPanelStyle {
+    anchor: Anchor::TopLeft,
+    margin: 8,
+    padding: 8,
+    font: "monospace 12px",
+    bold: false,
+    alignment: Alignment::Left,
+    border_color: [0.3, 0.6, 1.],
+};
+
+/// Overlay showing the live state of the gesture recognizers.
+pub struct GestureDebugOverlay {
+    is_open: bool,
+    panel: TextPanel,
+}
+
+impl GestureDebugOverlay {
+    pub fn new() -> Self {
+        Self {
+            is_open: false,
+            panel: TextPanel::new(STYLE),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_open = !self.is_open;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    /// Renders the overlay at the top left corner of an output of this size.
+    pub fn render<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output_size: Size<f64, Logical>,
+        scale: f64,
+        text: &str,
+    ) -> Option<PrimaryGpuTextureRenderElement> {
+        if !self.is_open {
+            return None;
+        }
+
+        self.panel.render(renderer, output_size, scale, text)
+    }
+}
+
+impl Default for GestureDebugOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,168 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use niri_config::GestureDirection;
+use ordered_float::NotNan;
+use pangocairo::cairo::{self, ImageSurface};
+use pangocairo::pango::FontDescription;
+use smithay::backend::renderer::element::Kind;
+use smithay::backend::renderer::gles::{GlesRenderer, GlesTexture};
+use smithay::reexports::gbm::Format as Fourcc;
+use smithay::utils::{Logical, Point, Size, Transform};
+
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::render_helpers::texture::{TextureBuffer, TextureRenderElement};
+use crate::utils::to_physical_precise_round;
+
+const SIZE: i32 = 64;
+const MARGIN: i32 = 48;
+const ARC_WIDTH: i32 = 4;
+const FONT: &str = "sans 24px";
+
+/// Number of distinct progress steps that the indicator shows.
+///
+/// Limits how often the indicator is rendered again as the fingers move.
+const STEPS: f64 = 40.;
+
+/// Indicator showing how far the gesture bind in progress is from triggering.
+///
+/// Shows an arrow for the direction that the gesture is heading in, surrounded by an arc that
+/// fills up as the gesture approaches its threshold.
+#[derive(Default)]
+pub struct GestureProgress {
+    /// Direction and progress step of the rendered buffers; they are rendered again on change.
+    key: RefCell<Option<(GestureDirection, u8)>>,
+    buffers: RefCell<HashMap<NotNan<f64>, Option<TextureBuffer<GlesTexture>>>>,
+}
+
+impl GestureProgress {
+    /// Renders the indicator at the bottom center of an output of this size.
+    pub fn render<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output_size: Size<f64, Logical>,
+        scale: f64,
+        direction: GestureDirection,
+        progress: f64,
+    ) -> Option<PrimaryGpuTextureRenderElement> {
+        let step = (progress.clamp(0., 1.) * STEPS).round() as u8;
+
+        let mut buffers = self.buffers.borrow_mut();
+        let mut key = self.key.borrow_mut();
+        if *key != Some((direction, step)) {
+            *key = Some((direction, step));
+            buffers.clear();
+        }
+
+        let progress = f64::from(step) / STEPS;
+        let buffer = buffers
+            .entry(NotNan::new(scale).unwrap())
+            .or_insert_with(|| {
+                render(renderer.as_gles_renderer(), scale, direction, progress).ok()
+            });
+        let buffer = buffer.clone()?;
+
+        let size = f64::from(SIZE);
+        let location = Point::from((
+            (output_size.w - size) / 2.,
+            output_size.h - size - f64::from(MARGIN),
+        ));
+        let location = location.to_physical_precise_round(scale).to_logical(scale);
+
+        let elem = TextureRenderElement::from_texture_buffer(
+            buffer,
+            location,
+            1.,
+            None,
+            None,
+            Kind::Unspecified,
+        );
+        Some(PrimaryGpuTextureRenderElement(elem))
+    }
+}
+
+/// Returns the symbol drawn in the middle of the indicator.
+fn symbol(direction: GestureDirection) -> &'static str {
+    match direction {
+        GestureDirection::Left => "←",
+        GestureDirection::Right => "→",
+        GestureDirection::Up => "↑",
+        GestureDirection::Down => "↓",
+        GestureDirection::UpLeft => "↖",
+        GestureDirection::UpRight => "↗",
+        GestureDirection::DownLeft => "↙",
+        GestureDirection::DownRight => "↘",
+        GestureDirection::In => "→←",
+        GestureDirection::Out => "←→",
+        GestureDirection::RotateCw => "↻",
+        GestureDirection::RotateCcw => "↺",
+    }
+}
+
+fn render(
+    renderer: &mut GlesRenderer,
+    scale: f64,
+    direction: GestureDirection,
+    progress: f64,
+) -> anyhow::Result<TextureBuffer<GlesTexture>> {
+    let _span = tracy_client::span!("gesture_progress::render");
+
+    let size: i32 = to_physical_precise_round(scale, SIZE);
+    // Keep the arc width even to avoid blurry edges.
+    let arc_width = (f64::from(ARC_WIDTH) / 2. * scale).round() * 2.;
+
+    let surface = ImageSurface::create(cairo::Format::ARgb32, size, size)?;
+    let cr = cairo::Context::new(&surface)?;
+
+    let center = f64::from(size) / 2.;
+    cr.arc(center, center, center, 0., 2. * PI);
+    cr.set_source_rgba(0.1, 0.1, 0.1, 0.8);
+    cr.fill()?;
+
+    let radius = center - arc_width;
+    cr.set_line_width(arc_width);
+    cr.arc(center, center, radius, 0., 2. * PI);
+    cr.set_source_rgba(1., 1., 1., 0.2);
+    cr.stroke()?;
+
+    // Fill the arc clockwise, starting from the top.
+    if progress > 0. {
+        let start = -PI / 2.;
+        cr.arc(center, center, radius, start, start + progress * 2. * PI);
+        cr.set_source_rgb(0.3, 0.6, 1.);
+        cr.stroke()?;
+    }
+
+    let mut font = FontDescription::from_string(FONT);
+    font.set_absolute_size(to_physical_precise_round(scale, font.size()));
+
+    let layout = pangocairo::functions::create_layout(&cr);
+    layout.context().set_round_glyph_positions(false);
+    layout.set_font_description(Some(&font));
+    layout.set_text(symbol(direction));
+
+    let (width, height) = layout.pixel_size();
+    cr.move_to(
+        center - f64::from(width) / 2.,
+        center - f64::from(height) / 2.,
+    );
+    cr.set_source_rgb(1., 1., 1.);
+    pangocairo::functions::show_layout(&cr, &layout);
+    drop(cr);
+
+    let data = surface.take_data().unwrap();
+    let buffer = TextureBuffer::from_memory(
+        renderer,
+        &data,
+        Fourcc::Argb8888,
+        (size, size),
+        false,
+        scale,
+        Transform::Normal,
+        Vec::new(),
+    )?;
+
+    Ok(buffer)
+}
@@ -285,7 +285,7 @@ fn collect_actions(config: &Config) -> Vec<&Action> {
             && (bind.key.modifiers.contains(Modifiers::COMPOSITOR)
                 || bind.key.modifiers.contains(Modifiers::SUPER))
             // Also filter out wheel and touchpad scroll binds.
-            && matches!(bind.key.trigger, Trigger::Keysym(_))
+            && matches!(bind.key.trigger, Trigger::Keysym(_) | Trigger::ModifierTap)
     }) {
         let action = &bind.action;
 
@@ -460,6 +460,8 @@ fn action_name(action: &Action) -> String {
         Action::Quit(_) => String::from("Exit niri"),
         Action::ShowHotkeyOverlay => String::from("Show Important Hotkeys"),
         Action::CloseWindow => String::from("Close Focused Window"),
+        Action::ForceCloseWindow(false) => String::from("Force Close Focused Window"),
+        Action::ForceCloseWindow(true) => String::from("Force Close Window Under Cursor"),
         Action::FocusColumnLeft => String::from("Focus Column to the Left"),
         Action::FocusColumnRight => String::from("Focus Column to the Right"),
         Action::MoveColumnLeft => String::from("Move Column Left"),
@@ -560,6 +562,7 @@ fn key_name(screen_reader: bool, mod_key: ModKey, key: &Key) -> String {
         Trigger::TouchpadScrollUp => String::from("Touchpad Scroll Up"),
         Trigger::TouchpadScrollLeft => String::from("Touchpad Scroll Left"),
         Trigger::TouchpadScrollRight => String::from("Touchpad Scroll Right"),
+        Trigger::ModifierTap => String::from("Tap"),
     };
     name.push_str(&pretty);
 
@@ -0,0 +1,92 @@
+use std::cell::{Cell, Ref, RefCell};
+
+use smithay::output::Output;
+use smithay::utils::{Logical, Point};
+
+use crate::render_helpers::offscreen::{OffscreenBuffer, OffscreenData};
+
+/// How far the magnifier can zoom in.
+const MAX_ZOOM: f64 = 8.;
+
+/// Accessibility magnifier that zooms in on an output around the cursor.
+///
+/// The output is rendered to an offscreen, which is then scaled around the cursor. Since the
+/// cursor is the fixed point of the zoom, whatever is under the cursor on screen is also under it
+/// for input, so input needs no transformation.
+#[derive(Debug, Default)]
+pub struct Magnifier {
+    /// Zoomed in output.
+    output: Option<Output>,
+    zoom: f64,
+    /// Zoom at the start of the pinch in progress.
+    gesture_start: Option<f64>,
+    /// Last cursor position on the zoomed in output, relative to the output.
+    focus: Cell<Point<f64, Logical>>,
+    offscreen: OffscreenBuffer,
+    /// Data of the last render to the offscreen, to find the surfaces visible through it.
+    offscreen_data: RefCell<Option<OffscreenData>>,
+}
+
+impl Magnifier {
+    /// Returns the zoom of the output, if it is zoomed in.
+    pub fn zoom(&self, output: &Output) -> Option<f64> {
+        (self.output.as_ref() == Some(output) && self.zoom > 1.).then_some(self.zoom)
+    }
+
+    pub fn gesture_begin(&mut self, output: Output) {
+        if self.output.as_ref() != Some(&output) {
+            self.output = Some(output);
+            self.zoom = 1.;
+        }
+
+        self.gesture_start = Some(self.zoom.max(1.));
+    }
+
+    /// Updates the zoom from the current absolute pinch scale.
+    ///
+    /// Returns the output to redraw.
+    pub fn gesture_update(&mut self, scale: f64) -> Option<&Output> {
+        let start = self.gesture_start?;
+        self.zoom = (start * scale).clamp(1., MAX_ZOOM);
+        self.output.as_ref()
+    }
+
+    pub fn gesture_end(&mut self) {
+        self.gesture_start = None;
+
+        if self.zoom <= 1. {
+            self.output = None;
+            self.offscreen_data.replace(None);
+        }
+    }
+
+    /// Zooms back out; returns the output to redraw.
+    pub fn reset(&mut self) -> Option<Output> {
+        self.gesture_start = None;
+        self.zoom = 1.;
+        self.offscreen_data.replace(None);
+        self.output.take()
+    }
+
+    /// Returns the point to zoom around, given the cursor position relative to the output.
+    ///
+    /// Keeps the last point when the cursor moves to a different output.
+    pub fn focus(&self, cursor: Option<Point<f64, Logical>>) -> Point<f64, Logical> {
+        if let Some(cursor) = cursor {
+            self.focus.set(cursor);
+        }
+        self.focus.get()
+    }
+
+    pub fn offscreen(&self) -> &OffscreenBuffer {
+        &self.offscreen
+    }
+
+    pub fn set_offscreen_data(&self, data: Option<OffscreenData>) {
+        self.offscreen_data.replace(data);
+    }
+
+    pub fn offscreen_data(&self) -> Ref<'_, Option<OffscreenData>> {
+        self.offscreen_data.borrow()
+    }
+}
@@ -1,6 +1,13 @@
+pub mod bind_mode_indicator;
+pub mod color_picker_loupe;
 pub mod config_error_notification;
 pub mod exit_confirm_dialog;
+pub mod gesture_debug_overlay;
+pub mod gesture_progress;
 pub mod hotkey_overlay;
+pub mod magnifier;
 pub mod mru;
+pub mod resize_mode_hint;
 pub mod screen_transition;
 pub mod screenshot_ui;
+pub mod text_panel;
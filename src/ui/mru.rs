@@ -1841,6 +1841,7 @@ fn make_preset_opened_binds() -> Vec<Bind> {
             action,
             repeat: true,
             cooldown: None,
+            double_press: None,
             allow_when_locked: false,
             allow_inhibiting: false,
             hotkey_overlay_title: None,
@@ -0,0 +1,46 @@
+use pangocairo::pango::Alignment;
+use smithay::utils::{Logical, Size};
+
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::ui::text_panel::{Anchor, TextPanel, TextPanelStyle};
+
+const STYLE: TextPanelStyle = TextPanelStyle {
+    anchor: Anchor::Bottom,
+    margin: 64,
+    padding: 12,
+    font: "sans 14px",
+    bold: false,
+    alignment: Alignment::Center,
+    border_color: [0.5, 0.8, 1.0],
+};
+
+/// Hint shown in the keyboard resize mode, with the window size and the resize steps.
+pub struct ResizeModeHint {
+    panel: TextPanel,
+}
+
+impl ResizeModeHint {
+    pub fn new() -> Self {
+        Self {
+            panel: TextPanel::new(STYLE),
+        }
+    }
+
+    /// Renders the hint at the bottom center of an output of this size.
+    pub fn render<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output_size: Size<f64, Logical>,
+        scale: f64,
+        text: &str,
+    ) -> Option<PrimaryGpuTextureRenderElement> {
+        self.panel.render(renderer, output_size, scale, text)
+    }
+}
+
+impl Default for ResizeModeHint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
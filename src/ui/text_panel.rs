@@ -0,0 +1,172 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use ordered_float::NotNan;
+use pangocairo::cairo::{self, ImageSurface};
+use pangocairo::pango::{Alignment, FontDescription, Weight};
+use smithay::backend::renderer::element::Kind;
+use smithay::backend::renderer::gles::{GlesRenderer, GlesTexture};
+use smithay::reexports::gbm::Format as Fourcc;
+use smithay::utils::{Logical, Point, Size, Transform};
+
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::render_helpers::texture::{TextureBuffer, TextureRenderElement};
+use crate::utils::to_physical_precise_round;
+
+const BORDER: i32 = 4;
+
+/// Panel with some text on a dark background with a colored border.
+///
+/// Shared by the small overlays that show a bit of text on top of everything else.
+pub struct TextPanel {
+    style: TextPanelStyle,
+    /// Text of the rendered buffers; they are rendered again when the text changes.
+    text: RefCell<String>,
+    buffers: RefCell<HashMap<NotNan<f64>, Option<TextureBuffer<GlesTexture>>>>,
+}
+
+/// Where a panel goes on the output, and how it looks.
+#[derive(Debug, Clone, Copy)]
+pub struct TextPanelStyle {
+    pub anchor: Anchor,
+    /// Distance from the anchored output edges, in logical pixels.
+    pub margin: i32,
+    /// Distance from the text to the panel edges, in logical pixels.
+    pub padding: i32,
+    pub font: &'static str,
+    pub bold: bool,
+    pub alignment: Alignment,
+    pub border_color: [f64; 3],
+}
+
+/// Output edge or corner that a panel is anchored to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    Bottom,
+}
+
+impl TextPanel {
+    pub fn new(style: TextPanelStyle) -> Self {
+        Self {
+            style,
+            text: RefCell::new(String::new()),
+            buffers: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Renders the panel on an output of this size.
+    pub fn render<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output_size: Size<f64, Logical>,
+        scale: f64,
+        text: &str,
+    ) -> Option<PrimaryGpuTextureRenderElement> {
+        let mut buffers = self.buffers.borrow_mut();
+        let mut cached_text = self.text.borrow_mut();
+        if *cached_text != text {
+            *cached_text = text.to_owned();
+            buffers.clear();
+        }
+
+        let buffer = buffers
+            .entry(NotNan::new(scale).unwrap())
+            .or_insert_with(|| render(renderer.as_gles_renderer(), scale, &self.style, text).ok());
+        let buffer = buffer.clone()?;
+
+        let size = buffer.logical_size();
+        let margin = f64::from(self.style.margin);
+        let location = match self.style.anchor {
+            Anchor::TopLeft => Point::from((margin, margin)),
+            Anchor::Top => Point::from(((output_size.w - size.w) / 2., margin)),
+            Anchor::Bottom => Point::from((
+                (output_size.w - size.w) / 2.,
+                output_size.h - size.h - margin,
+            )),
+        };
+        let location = location.to_physical_precise_round(scale).to_logical(scale);
+
+        let elem = TextureRenderElement::from_texture_buffer(
+            buffer,
+            location,
+            1.,
+            None,
+            None,
+            Kind::Unspecified,
+        );
+        Some(PrimaryGpuTextureRenderElement(elem))
+    }
+}
+
+fn render(
+    renderer: &mut GlesRenderer,
+    scale: f64,
+    style: &TextPanelStyle,
+    text: &str,
+) -> anyhow::Result<TextureBuffer<GlesTexture>> {
+    let _span = tracy_client::span!("text_panel::render");
+
+    let padding: i32 = to_physical_precise_round(scale, style.padding);
+
+    let mut font = FontDescription::from_string(style.font);
+    if style.bold {
+        font.set_weight(Weight::Bold);
+    }
+    font.set_absolute_size(to_physical_precise_round(scale, font.size()));
+
+    let surface = ImageSurface::create(cairo::Format::ARgb32, 0, 0)?;
+    let cr = cairo::Context::new(&surface)?;
+    let layout = pangocairo::functions::create_layout(&cr);
+    layout.context().set_round_glyph_positions(false);
+    layout.set_font_description(Some(&font));
+    layout.set_alignment(style.alignment);
+    layout.set_text(text);
+
+    let (mut width, mut height) = layout.pixel_size();
+    width += padding * 2;
+    height += padding * 2;
+
+    let surface = ImageSurface::create(cairo::Format::ARgb32, width, height)?;
+    let cr = cairo::Context::new(&surface)?;
+    cr.set_source_rgba(0.1, 0.1, 0.1, 0.9);
+    cr.paint()?;
+
+    cr.move_to(padding.into(), padding.into());
+    let layout = pangocairo::functions::create_layout(&cr);
+    layout.context().set_round_glyph_positions(false);
+    layout.set_font_description(Some(&font));
+    layout.set_alignment(style.alignment);
+    layout.set_text(text);
+
+    cr.set_source_rgb(1., 1., 1.);
+    pangocairo::functions::show_layout(&cr, &layout);
+
+    cr.move_to(0., 0.);
+    cr.line_to(width.into(), 0.);
+    cr.line_to(width.into(), height.into());
+    cr.line_to(0., height.into());
+    cr.line_to(0., 0.);
+    let [r, g, b] = style.border_color;
+    cr.set_source_rgb(r, g, b);
+    // Keep the border width even to avoid blurry edges.
+    cr.set_line_width((f64::from(BORDER) / 2. * scale).round() * 2.);
+    cr.stroke()?;
+    drop(cr);
+
+    let data = surface.take_data().unwrap();
+    let buffer = TextureBuffer::from_memory(
+        renderer,
+        &data,
+        Fourcc::Argb8888,
+        (width, height),
+        false,
+        scale,
+        Transform::Normal,
+        Vec::new(),
+    )?;
+
+    Ok(buffer)
+}
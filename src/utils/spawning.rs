@@ -64,6 +64,15 @@ pub fn restore_nofile_rlimit() {
 
 /// Spawns the command to run independently of the compositor.
 pub fn spawn<T: AsRef<OsStr> + Send + 'static>(command: Vec<T>, token: Option<XdgActivationToken>) {
+    spawn_with_env(command, Vec::new(), token);
+}
+
+/// Spawns the command with extra environment variables.
+pub fn spawn_with_env<T: AsRef<OsStr> + Send + 'static>(
+    command: Vec<T>,
+    env: Vec<(String, String)>,
+    token: Option<XdgActivationToken>,
+) {
     let _span = tracy_client::span!();
 
     if command.is_empty() {
@@ -75,7 +84,7 @@ pub fn spawn<T: AsRef<OsStr> + Send + 'static>(command: Vec<T>, token: Option<Xd
         .name("Command Spawner".to_owned())
         .spawn(move || {
             let (command, args) = command.split_first().unwrap();
-            spawn_sync(command, args, token);
+            spawn_sync(command, args, &env, token);
         });
 
     if let Err(err) = res {
@@ -96,6 +105,7 @@ pub fn spawn_sh(command: String, token: Option<XdgActivationToken>) {
 fn spawn_sync(
     command: impl AsRef<OsStr>,
     args: impl IntoIterator<Item = impl AsRef<OsStr>>,
+    extra_env: &[(String, String)],
     token: Option<XdgActivationToken>,
 ) {
     let _span = tracy_client::span!();
@@ -146,6 +156,8 @@ fn spawn_sync(
     }
     drop(env);
 
+    process.envs(extra_env.iter().cloned());
+
     if let Some(token) = token.as_ref() {
         process.env("XDG_ACTIVATION_TOKEN", token.as_str());
         process.env("DESKTOP_STARTUP_ID", token.as_str());
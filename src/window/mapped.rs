@@ -101,6 +101,12 @@ pub struct Mapped {
     /// Whether this window should ignore opacity set through window rules.
     ignore_opacity_window_rule: bool,
 
+    /// Tags toggled through actions, flipping whether the window has them from the window rules.
+    toggled_tags: Vec<String>,
+
+    /// Marks set on this window through actions.
+    marks: Vec<char>,
+
     /// Buffer to draw instead of the window when it should be blocked out.
     block_out_buffer: RefCell<SolidColorBuffer>,
 
@@ -269,6 +275,8 @@ impl Mapped {
             is_floating: false,
             is_window_cast_target: false,
             ignore_opacity_window_rule: false,
+            toggled_tags: Vec::new(),
+            marks: Vec::new(),
             block_out_buffer: RefCell::new(SolidColorBuffer::new((0., 0.), [0., 0., 0., 1.])),
             animate_next_configure: false,
             animate_serials: Vec::new(),
@@ -298,10 +306,16 @@ impl Mapped {
     }
 
     /// Recomputes the resolved window rules and returns whether they changed.
-    pub fn recompute_window_rules(&mut self, rules: &[WindowRule], is_at_startup: bool) -> bool {
+    pub fn recompute_window_rules(
+        &mut self,
+        rules: &[WindowRule],
+        is_at_startup: bool,
+        profile: Option<&str>,
+    ) -> bool {
         self.need_to_recompute_rules = false;
 
-        let new_rules = ResolvedWindowRules::compute(rules, WindowRef::Mapped(self), is_at_startup);
+        let new_rules =
+            ResolvedWindowRules::compute(rules, WindowRef::Mapped(self), is_at_startup, profile);
         if new_rules == self.rules {
             return false;
         }
@@ -320,12 +334,13 @@ impl Mapped {
         &mut self,
         rules: &[WindowRule],
         is_at_startup: bool,
+        profile: Option<&str>,
     ) -> bool {
         if !self.need_to_recompute_rules {
             return false;
         }
 
-        self.recompute_window_rules(rules, is_at_startup)
+        self.recompute_window_rules(rules, is_at_startup, profile)
     }
 
     pub fn set_needs_configure(&mut self) {
@@ -364,6 +379,38 @@ impl Mapped {
         self.ignore_opacity_window_rule = !self.ignore_opacity_window_rule;
     }
 
+    pub fn has_tag(&self, tag: &str) -> bool {
+        let from_rules = self.rules.tags.iter().any(|t| t == tag);
+        let toggled = self.toggled_tags.iter().any(|t| t == tag);
+        from_rules != toggled
+    }
+
+    pub fn toggle_tag(&mut self, tag: &str) {
+        if let Some(idx) = self.toggled_tags.iter().position(|t| t == tag) {
+            self.toggled_tags.remove(idx);
+        } else {
+            self.toggled_tags.push(tag.to_owned());
+        }
+    }
+
+    pub fn marks(&self) -> &[char] {
+        &self.marks
+    }
+
+    pub fn has_mark(&self, mark: char) -> bool {
+        self.marks.contains(&mark)
+    }
+
+    pub fn add_mark(&mut self, mark: char) {
+        if !self.has_mark(mark) {
+            self.marks.push(mark);
+        }
+    }
+
+    pub fn remove_mark(&mut self, mark: char) {
+        self.marks.retain(|m| *m != mark);
+    }
+
     pub fn set_is_focused(&mut self, is_focused: bool) {
         if self.is_focused == is_focused {
             return;
@@ -0,0 +1,177 @@
+//! Window minimization.
+//!
+//! A minimized window is taken out of the layout but kept alive, so that taskbars can keep
+//! listing it through foreign-toplevel-management and restore it later.
+
+use smithay::desktop::Window;
+use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
+use smithay::utils::IsAlive as _;
+use smithay::wayland::compositor::is_mapped;
+
+use crate::layout::workspace::WorkspaceId;
+use crate::layout::{ActivateWindow, AddWindowTarget, LayoutElement as _};
+use crate::niri::{CastTarget, State};
+use crate::utils::transaction::Transaction;
+use crate::window::{Mapped, Unmapped};
+
+/// Window taken out of the layout by minimizing it.
+#[derive(Debug)]
+pub struct MinimizedWindow {
+    pub mapped: Mapped,
+    /// Workspace that the window was on.
+    workspace: WorkspaceId,
+    /// Window in the column to the left of the window, if it was tiled.
+    next_to: Option<Window>,
+    /// Whether the window was floating.
+    is_floating: bool,
+}
+
+impl State {
+    /// Takes the window out of the layout until it is restored.
+    pub fn minimize_window(&mut self, window: &Window) {
+        let Some((_, _, ws)) = self
+            .niri
+            .layout
+            .workspaces()
+            .find(|(_, _, ws)| ws.has_window(window))
+        else {
+            return;
+        };
+
+        let workspace = ws.id();
+        let is_floating = ws.is_floating(window);
+        let next_to = ws
+            .window_left_of(window)
+            .map(|mapped| mapped.window.clone());
+
+        // A swallowed window can only come back next to this one, so bring it back right away.
+        self.restore_swallowed_window(window);
+
+        let Some(removed) = self.niri.layout.remove_window(window, Transaction::new()) else {
+            return;
+        };
+
+        let mapped = removed.into_window();
+        let id = mapped.id();
+        self.niri
+            .stop_casts_for_target(CastTarget::Window { id: id.get() });
+        self.niri.window_mru_ui.remove_window(id);
+
+        self.niri.minimized_windows.push(MinimizedWindow {
+            mapped,
+            workspace,
+            next_to,
+            is_floating,
+        });
+
+        // FIXME: granular
+        self.niri.queue_redraw_all();
+    }
+
+    /// Puts the minimized window back where it was and focuses it.
+    pub fn unminimize_window(&mut self, window: &Window) {
+        let Some(idx) = self
+            .niri
+            .minimized_windows
+            .iter()
+            .position(|minimized| minimized.mapped.window == *window)
+        else {
+            return;
+        };
+
+        let minimized = self.niri.minimized_windows.remove(idx);
+        if !minimized.mapped.toplevel().alive() {
+            return;
+        }
+
+        let layout = &self.niri.layout;
+        let next_to = minimized.next_to.filter(|next_to| {
+            layout
+                .windows()
+                .any(|(_, mapped)| mapped.window == *next_to)
+        });
+        let target = if let Some(next_to) = &next_to {
+            AddWindowTarget::NextTo(next_to)
+        } else if layout.find_workspace_by_id(minimized.workspace).is_some() {
+            AddWindowTarget::Workspace(minimized.workspace)
+        } else {
+            AddWindowTarget::Auto
+        };
+
+        // Keep the current window width.
+        self.niri.layout.add_window(
+            minimized.mapped,
+            target,
+            None,
+            None,
+            false,
+            minimized.is_floating,
+            ActivateWindow::Yes,
+        );
+
+        self.niri.layout.activate_window(window);
+        self.niri.layer_shell_on_demand_focus = None;
+
+        // FIXME: granular
+        self.niri.queue_redraw_all();
+    }
+
+    /// Returns the minimized window with this surface.
+    pub fn find_minimized_window(&self, surface: &WlSurface) -> Option<Window> {
+        let idx = self.minimized_window_idx(surface)?;
+        Some(self.niri.minimized_windows[idx].mapped.window.clone())
+    }
+
+    /// Returns the minimized window with this id.
+    pub fn find_minimized_window_by_id(&self, id: u64) -> Option<Window> {
+        self.niri
+            .minimized_windows
+            .iter()
+            .find(|minimized| minimized.mapped.id().get() == id)
+            .map(|minimized| minimized.mapped.window.clone())
+    }
+
+    /// Handles a commit of a minimized window.
+    ///
+    /// Returns `false` if the surface isn't a minimized window.
+    pub fn minimized_window_commit(&mut self, surface: &WlSurface) -> bool {
+        let Some(idx) = self.minimized_window_idx(surface) else {
+            return false;
+        };
+
+        self.niri.minimized_windows[idx].mapped.window.on_commit();
+
+        if !is_mapped(surface) {
+            // The minimized toplevel got unmapped, so it has nothing to come back to.
+            let minimized = self.niri.minimized_windows.remove(idx);
+            let window = minimized.mapped.window.clone();
+            drop(minimized);
+            self.add_default_dmabuf_pre_commit_hook(surface);
+
+            let unmapped = Unmapped::new(window);
+            self.niri.unmapped_windows.insert(surface.clone(), unmapped);
+        }
+
+        true
+    }
+
+    /// Handles the destruction of a minimized window.
+    ///
+    /// Returns `false` if the surface isn't a minimized window.
+    pub fn minimized_window_destroyed(&mut self, surface: &WlSurface) -> bool {
+        let Some(idx) = self.minimized_window_idx(surface) else {
+            return false;
+        };
+
+        self.niri.minimized_windows.remove(idx);
+        self.add_default_dmabuf_pre_commit_hook(surface);
+        true
+    }
+
+    fn minimized_window_idx(&self, surface: &WlSurface) -> Option<usize> {
+        self.niri
+            .minimized_windows
+            .iter()
+            .position(|minimized| minimized.mapped.toplevel().wl_surface() == surface)
+    }
+}
@@ -1,10 +1,11 @@
 use std::cmp::{max, min};
 
+use niri_config::gestures::ThreeFingerSwipe;
 use niri_config::utils::MergeWith as _;
 use niri_config::window_rule::{Match, WindowRule};
 use niri_config::{
-    BlockOutFrom, BorderRule, CornerRadius, FloatingPosition, PresetSize, ShadowRule,
-    TabIndicatorRule,
+    BlockOutFrom, BorderRule, CornerRadius, FloatingPosition, NewWindowPlacement, PresetSize,
+    ShadowRule, TabIndicatorRule,
 };
 use niri_ipc::ColumnDisplay;
 use smithay::reexports::wayland_protocols::xdg::shell::server::xdg_toplevel;
@@ -19,6 +20,11 @@ use crate::utils::with_toplevel_role;
 pub mod mapped;
 pub use mapped::Mapped;
 
+pub mod minimize;
+pub use minimize::MinimizedWindow;
+
+pub mod swallow;
+
 pub mod unmapped;
 pub use unmapped::{InitialConfigureState, Unmapped};
 
@@ -73,6 +79,9 @@ pub struct ResolvedWindowRules {
     /// Whether the window should open focused.
     pub open_focused: Option<bool>,
 
+    /// Where in the scrolling layout the window should open.
+    pub new_window_placement: Option<NewWindowPlacement>,
+
     /// Extra bound on the minimum window width.
     pub min_width: Option<u16>,
     /// Extra bound on the minimum window height.
@@ -119,6 +128,30 @@ pub struct ResolvedWindowRules {
 
     /// Override whether to set the Tiled xdg-toplevel state on the window.
     pub tiled_state: Option<bool>,
+
+    /// Whether touchpad pinches over this window go to the window instead of the pinch gestures.
+    pub forward_pinch_gestures: Option<bool>,
+
+    /// Whether this window can inhibit the compositor keyboard shortcuts.
+    pub allow_keyboard_shortcuts_inhibit: Option<bool>,
+
+    /// Whether pointer input over this window skips the compositor touchpad and mouse gestures.
+    pub disable_pointer_gestures: Option<bool>,
+
+    /// Whether windows of the child processes of this window can take its place in the layout.
+    pub allow_swallowing: Option<bool>,
+
+    /// Whether the window stays above the other floating windows.
+    pub always_on_top: Option<bool>,
+
+    /// Whether gesture binds skip the window actions while this window is focused.
+    pub exclude_from_gesture_actions: Option<bool>,
+
+    /// Tags of this window, for the tag actions.
+    pub tags: Vec<String>,
+
+    /// Where the three-finger swipe over this window goes.
+    pub three_finger_swipe: ThreeFingerSwipe,
 }
 
 impl<'a> WindowRef<'a> {
@@ -174,7 +207,12 @@ impl<'a> WindowRef<'a> {
 }
 
 impl ResolvedWindowRules {
-    pub fn compute(rules: &[WindowRule], window: WindowRef, is_at_startup: bool) -> Self {
+    pub fn compute(
+        rules: &[WindowRule],
+        window: WindowRef,
+        is_at_startup: bool,
+        profile: Option<&str>,
+    ) -> Self {
         let _span = tracy_client::span!("ResolvedWindowRules::compute");
 
         let mut resolved = ResolvedWindowRules::default();
@@ -196,6 +234,12 @@ impl ResolvedWindowRules {
                         }
                     }
 
+                    if let Some(match_profile) = &m.profile {
+                        if Some(match_profile.as_str()) != profile {
+                            return false;
+                        }
+                    }
+
                     window_matches(window, role, m)
                 };
 
@@ -250,6 +294,9 @@ impl ResolvedWindowRules {
                 if let Some(x) = rule.open_focused {
                     resolved.open_focused = Some(x);
                 }
+                if let Some(x) = rule.new_window_placement {
+                    resolved.new_window_placement = Some(x);
+                }
 
                 if let Some(x) = rule.min_width {
                     resolved.min_width = Some(x);
@@ -296,6 +343,32 @@ impl ResolvedWindowRules {
                 if let Some(x) = rule.tiled_state {
                     resolved.tiled_state = Some(x);
                 }
+                if let Some(x) = rule.forward_pinch_gestures {
+                    resolved.forward_pinch_gestures = Some(x);
+                }
+                if let Some(x) = rule.allow_keyboard_shortcuts_inhibit {
+                    resolved.allow_keyboard_shortcuts_inhibit = Some(x);
+                }
+                if let Some(x) = rule.disable_pointer_gestures {
+                    resolved.disable_pointer_gestures = Some(x);
+                }
+                if let Some(x) = rule.allow_swallowing {
+                    resolved.allow_swallowing = Some(x);
+                }
+                if let Some(x) = rule.always_on_top {
+                    resolved.always_on_top = Some(x);
+                }
+                if let Some(x) = rule.exclude_from_gesture_actions {
+                    resolved.exclude_from_gesture_actions = Some(x);
+                }
+                for tag in rule.tags.iter().flatten() {
+                    if !resolved.tags.contains(tag) {
+                        resolved.tags.push(tag.clone());
+                    }
+                }
+                resolved
+                    .three_finger_swipe
+                    .merge_with(&rule.three_finger_swipe);
             }
 
             resolved.open_on_output = open_on_output.map(|x| x.to_owned());
@@ -0,0 +1,159 @@
+//! Window swallowing.
+//!
+//! A window opened by a child process of a terminal takes the place of the terminal in the layout,
+//! and the terminal comes back when that window closes.
+
+use smithay::desktop::Window;
+use smithay::reexports::wayland_server::protocol::wl_surface::WlSurface;
+use smithay::utils::IsAlive as _;
+use smithay::wayland::compositor::is_mapped;
+
+use crate::layout::{ActivateWindow, AddWindowTarget, LayoutElement as _};
+use crate::niri::{CastTarget, State};
+use crate::utils::transaction::Transaction;
+use crate::window::{Mapped, Unmapped};
+
+/// How many parent processes to walk through looking for the ancestor.
+const MAX_ANCESTRY_DEPTH: usize = 32;
+
+/// Returns the parent process id of the process.
+fn parent_pid(pid: i32) -> Option<i32> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+
+    // The process name can contain spaces and parentheses, so skip past the last parenthesis.
+    // Then comes the process state, followed by the parent pid.
+    let rest = &stat[stat.rfind(')')? + 1..];
+    rest.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Returns whether the process is a descendant of the ancestor process.
+fn is_descendant(mut pid: i32, ancestor: i32) -> bool {
+    for _ in 0..MAX_ANCESTRY_DEPTH {
+        match parent_pid(pid) {
+            Some(parent) if parent == ancestor => return true,
+            // Stop at init.
+            Some(parent) if parent > 1 => pid = parent,
+            _ => return false,
+        }
+    }
+
+    false
+}
+
+impl State {
+    /// Returns the window that the newly mapped window should swallow, if any.
+    ///
+    /// This is the focused tiled window with swallowing allowed, when the new window belongs to
+    /// one of its child processes.
+    pub fn window_to_swallow(&self, mapped: &Mapped) -> Option<Window> {
+        let pid = mapped.credentials()?.pid;
+
+        let focus = self.niri.layout.focus()?;
+        if focus.rules().allow_swallowing != Some(true) || focus.is_floating() {
+            return None;
+        }
+
+        let ancestor = focus.credentials()?.pid;
+        is_descendant(pid, ancestor).then(|| focus.window.clone())
+    }
+
+    /// Takes the window out of the layout until the child window closes.
+    pub fn swallow_window(&mut self, window: &Window, child: &Window) {
+        let Some(removed) = self.niri.layout.remove_window(window, Transaction::new()) else {
+            return;
+        };
+
+        let mapped = removed.into_window();
+        let id = mapped.id();
+        self.niri
+            .stop_casts_for_target(CastTarget::Window { id: id.get() });
+        self.niri.window_mru_ui.remove_window(id);
+
+        self.niri.swallowed_windows.insert(child.clone(), mapped);
+    }
+
+    /// Puts the window swallowed by the closing child window back into the layout.
+    ///
+    /// Must be called while the child window is still in the layout.
+    pub fn restore_swallowed_window(&mut self, child: &Window) {
+        let Some(mapped) = self.niri.swallowed_windows.remove(child) else {
+            return;
+        };
+
+        if !mapped.toplevel().alive() {
+            return;
+        }
+
+        // Keep the current window width, and take the focus back from the child window.
+        self.niri.layout.add_window(
+            mapped,
+            AddWindowTarget::NextTo(child),
+            None,
+            None,
+            false,
+            false,
+            ActivateWindow::Smart,
+        );
+    }
+
+    /// Handles a commit of a swallowed window.
+    ///
+    /// Returns `false` if the surface isn't a swallowed window.
+    pub fn swallowed_window_commit(&mut self, surface: &WlSurface) -> bool {
+        let Some(child) = self.swallowed_child(surface) else {
+            return false;
+        };
+
+        let mapped = &self.niri.swallowed_windows[&child];
+        mapped.window.on_commit();
+
+        if !is_mapped(surface) {
+            // The swallowed toplevel got unmapped, so it has nothing to come back to.
+            let mapped = self.niri.swallowed_windows.remove(&child).unwrap();
+            let window = mapped.window.clone();
+            drop(mapped);
+            self.add_default_dmabuf_pre_commit_hook(surface);
+
+            let unmapped = Unmapped::new(window);
+            self.niri.unmapped_windows.insert(surface.clone(), unmapped);
+        }
+
+        true
+    }
+
+    /// Handles the destruction of a swallowed window.
+    ///
+    /// Returns `false` if the surface isn't a swallowed window.
+    pub fn swallowed_window_destroyed(&mut self, surface: &WlSurface) -> bool {
+        let Some(child) = self.swallowed_child(surface) else {
+            return false;
+        };
+
+        self.niri.swallowed_windows.remove(&child);
+        self.add_default_dmabuf_pre_commit_hook(surface);
+        true
+    }
+
+    fn swallowed_child(&self, surface: &WlSurface) -> Option<Window> {
+        self.niri
+            .swallowed_windows
+            .iter()
+            .find(|(_, mapped)| mapped.toplevel().wl_surface() == surface)
+            .map(|(child, _)| child.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn own_process_descends_from_parent() {
+        let pid = std::process::id() as i32;
+        let parent = unsafe { libc::getppid() };
+
+        assert_eq!(parent_pid(pid), Some(parent));
+        assert!(is_descendant(pid, parent));
+        assert!(!is_descendant(parent, pid));
+    }
+}